@@ -215,7 +215,7 @@ fn bench_json_serialization(c: &mut Criterion) {
     let mut group = c.benchmark_group("json_serialization");
 
     // Benchmark different value types
-    let values = vec![
+    let values = [
         ("bool", DatabaseValue::from(true)),
         ("int", DatabaseValue::from(42i32)),
         ("long", DatabaseValue::from(123456789i64)),
@@ -0,0 +1,184 @@
+//! Procedural macros for `rust_database_system`
+//!
+//! This crate is re-exported by `rust_database_system` behind its `macros` feature;
+//! use it through `rust_database_system::core::row::FromRow` and
+//! `rust_database_system::core::migration::embed_migrations` rather than directly.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, LitStr};
+
+/// Embed a directory of migration SQL files into the binary
+///
+/// Takes a path relative to the invoking crate's `Cargo.toml` and expands to an
+/// `EmbeddedMigrations` holding the name and contents of every `.sql` file in
+/// it, sorted by name. The contents are read with `include_str!`, so editing a
+/// file triggers a rebuild; adding or removing a file needs a rebuild of the
+/// invoking crate.
+#[proc_macro]
+pub fn embed_migrations(input: TokenStream) -> TokenStream {
+    let dir = parse_macro_input!(input as LitStr);
+    expand_embed_migrations(&dir)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand_embed_migrations(dir: &LitStr) -> syn::Result<TokenStream2> {
+    let manifest_dir = std::env::var("CARGO_MANIFEST_DIR")
+        .map_err(|_| syn::Error::new_spanned(dir, "CARGO_MANIFEST_DIR is not set"))?;
+    let path = std::path::Path::new(&manifest_dir).join(dir.value());
+
+    let entries = std::fs::read_dir(&path).map_err(|e| {
+        syn::Error::new_spanned(
+            dir,
+            format!("cannot read migrations directory {}: {}", path.display(), e),
+        )
+    })?;
+
+    let mut files = Vec::new();
+    for entry in entries {
+        let file = entry
+            .map_err(|e| syn::Error::new_spanned(dir, e.to_string()))?
+            .path();
+        if !file.is_file() || file.extension().is_none_or(|ext| ext != "sql") {
+            continue;
+        }
+        let name = file
+            .file_name()
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| {
+                syn::Error::new_spanned(
+                    dir,
+                    format!("file name is not valid UTF-8: {}", file.display()),
+                )
+            })?
+            .to_string();
+        let full_path = file.to_str().ok_or_else(|| {
+            syn::Error::new_spanned(dir, format!("path is not valid UTF-8: {}", file.display()))
+        })?;
+        files.push((name, full_path.to_string()));
+    }
+    files.sort();
+
+    let entries = files
+        .iter()
+        .map(|(name, full_path)| quote!((#name, ::core::include_str!(#full_path))));
+    Ok(quote! {
+        ::rust_database_system::core::migration::EmbeddedMigrations::new(&[#(#entries),*])
+    })
+}
+
+/// Derive `FromRow` for a struct with named fields
+///
+/// Each field is decoded from the column of the same name with `FromValue`.
+/// Field attributes:
+///
+/// - `#[from_row(rename = "column")]` reads a differently named column
+/// - `#[from_row(default)]` uses `Default::default()` when the column is missing or NULL
+/// - `#[from_row(flatten)]` decodes the field (itself `FromRow`) from the same row
+#[proc_macro_derive(FromRow, attributes(from_row))]
+pub fn derive_from_row(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand_from_row(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+/// Parsed `#[from_row(...)]` options of one field
+#[derive(Default)]
+struct FieldOptions {
+    rename: Option<String>,
+    default: bool,
+    flatten: bool,
+}
+
+impl FieldOptions {
+    fn parse(field: &syn::Field) -> syn::Result<Self> {
+        let mut options = FieldOptions::default();
+
+        for attr in field.attrs.iter().filter(|a| a.path().is_ident("from_row")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("rename") {
+                    let name: LitStr = meta.value()?.parse()?;
+                    options.rename = Some(name.value());
+                } else if meta.path.is_ident("default") {
+                    options.default = true;
+                } else if meta.path.is_ident("flatten") {
+                    options.flatten = true;
+                } else {
+                    return Err(meta.error("expected `rename = \"...\"`, `default` or `flatten`"));
+                }
+                Ok(())
+            })?;
+        }
+
+        if options.flatten && (options.rename.is_some() || options.default) {
+            return Err(syn::Error::new_spanned(
+                field,
+                "`flatten` cannot be combined with `rename` or `default`",
+            ));
+        }
+
+        Ok(options)
+    }
+}
+
+fn expand_from_row(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "FromRow can only be derived for structs with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "FromRow can only be derived for structs",
+            ))
+        }
+    };
+
+    let krate = quote!(::rust_database_system);
+    let mut initializers = Vec::with_capacity(fields.len());
+
+    for field in fields {
+        let options = FieldOptions::parse(field)?;
+        let ident = field.ident.as_ref().expect("named field");
+        let ty = &field.ty;
+
+        let value = if options.flatten {
+            quote!(<#ty as #krate::core::row::FromRow>::from_row(row)?)
+        } else {
+            let column = options
+                .rename
+                .unwrap_or_else(|| ident.to_string().trim_start_matches("r#").to_string());
+            if options.default {
+                quote!(#krate::core::row::get_or_default::<#ty>(row, #column)?)
+            } else {
+                quote!(#krate::core::row::get::<#ty>(row, #column)?)
+            }
+        };
+
+        initializers.push(quote!(#ident: #value));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    Ok(quote! {
+        impl #impl_generics #krate::core::row::FromRow for #name #ty_generics #where_clause {
+            fn from_row(
+                row: &#krate::core::value::DatabaseRow,
+            ) -> #krate::core::error::Result<Self> {
+                ::core::result::Result::Ok(Self {
+                    #(#initializers,)*
+                })
+            }
+        }
+    })
+}
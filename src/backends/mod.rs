@@ -3,18 +3,40 @@
 //! This module contains concrete implementations of the Database trait
 //! for various database systems.
 
+pub mod pool;
+
 #[cfg(feature = "sqlite")]
 pub mod pooled_sqlite;
 #[cfg(feature = "sqlite")]
 pub mod sqlite;
 
+#[cfg(feature = "mongodb_support")]
+pub mod mongodb;
+#[cfg(feature = "mysql")]
+pub mod mysql;
+#[cfg(feature = "postgres")]
+pub mod pooled_postgres;
 #[cfg(feature = "postgres")]
 pub mod postgres;
+#[cfg(feature = "redis_support")]
+pub mod redis;
+
+pub use pool::{PoolConfig, PoolStats};
 
 #[cfg(feature = "sqlite")]
-pub use pooled_sqlite::{PoolConfig, PoolStats, PooledSqliteDatabase, PooledTransaction};
+pub use pooled_sqlite::{PooledSavepoint, PooledSqliteDatabase, PooledTransaction};
 #[cfg(feature = "sqlite")]
 pub use sqlite::SqliteDatabase;
 
+#[cfg(feature = "mongodb_support")]
+pub use self::mongodb::{MongoDatabase, MongoFilter};
+#[cfg(feature = "redis_support")]
+pub use self::redis::{RedisDatabase, RedisMessage, RedisSubscription};
+#[cfg(feature = "mysql")]
+pub use mysql::MysqlDatabase;
+#[cfg(feature = "postgres")]
+pub use pooled_postgres::{
+    PooledPostgresDatabase, PooledPostgresSavepoint, PooledPostgresTransaction,
+};
 #[cfg(feature = "postgres")]
 pub use postgres::PostgresDatabase;
@@ -0,0 +1,902 @@
+//! MongoDB database backend implementation
+//!
+//! This module provides a MongoDB implementation using the official mongodb driver.
+//! `MongoDatabase` exposes a document API (insert/find/update/delete/aggregate) and
+//! maps `begin_transaction`/`commit`/`rollback` onto a client session transaction.
+
+use crate::core::{
+    database::Database, database_types::DatabaseType, error::DatabaseError, error::Result,
+    value::DatabaseResult, value::DatabaseRow, value::DatabaseValue,
+};
+use async_trait::async_trait;
+use futures::TryStreamExt;
+use mongodb::bson::{doc, oid::ObjectId, spec::BinarySubtype, Binary, Bson, DateTime, Document};
+use mongodb::{Client, ClientSession, Collection};
+use std::future::IntoFuture;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Default timeout for database operations (30 seconds)
+const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Database used when the connection string does not name one
+const DEFAULT_DATABASE_NAME: &str = "test";
+
+/// Filter document builder
+///
+/// Builds MongoDB filter documents from `DatabaseValue`s. Several operators on the
+/// same field are merged into one operator document, so
+/// `MongoFilter::new().gte("age", 18).lt("age", 65)` yields
+/// `{ "age": { "$gte": 18, "$lt": 65 } }`.
+///
+/// # Example
+///
+/// ```
+/// use rust_database_system::backends::MongoFilter;
+/// use rust_database_system::core::value::DatabaseValue;
+///
+/// let filter = MongoFilter::new()
+///     .eq("status", "active")
+///     .gte("age", 18)
+///     .in_values("role", &[DatabaseValue::from("admin"), DatabaseValue::from("owner")]);
+/// assert_eq!(filter.into_document().len(), 3);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MongoFilter {
+    document: Document,
+}
+
+impl MongoFilter {
+    /// Create an empty filter matching every document
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Match documents whose `_id` is the given ObjectId
+    ///
+    /// Falls back to a string comparison if `hex` is not a valid ObjectId.
+    pub fn id(hex: &str) -> Self {
+        let id = ObjectId::parse_str(hex)
+            .map(Bson::ObjectId)
+            .unwrap_or_else(|_| Bson::String(hex.to_string()));
+        let mut filter = Self::new();
+        filter.document.insert("_id", id);
+        filter
+    }
+
+    /// Field equals value
+    pub fn eq(mut self, field: &str, value: impl Into<DatabaseValue>) -> Self {
+        self.document
+            .insert(field, MongoDatabase::value_to_bson(&value.into()));
+        self
+    }
+
+    /// Field does not equal value
+    pub fn ne(self, field: &str, value: impl Into<DatabaseValue>) -> Self {
+        self.operator(field, "$ne", MongoDatabase::value_to_bson(&value.into()))
+    }
+
+    /// Field greater than value
+    pub fn gt(self, field: &str, value: impl Into<DatabaseValue>) -> Self {
+        self.operator(field, "$gt", MongoDatabase::value_to_bson(&value.into()))
+    }
+
+    /// Field greater than or equal to value
+    pub fn gte(self, field: &str, value: impl Into<DatabaseValue>) -> Self {
+        self.operator(field, "$gte", MongoDatabase::value_to_bson(&value.into()))
+    }
+
+    /// Field less than value
+    pub fn lt(self, field: &str, value: impl Into<DatabaseValue>) -> Self {
+        self.operator(field, "$lt", MongoDatabase::value_to_bson(&value.into()))
+    }
+
+    /// Field less than or equal to value
+    pub fn lte(self, field: &str, value: impl Into<DatabaseValue>) -> Self {
+        self.operator(field, "$lte", MongoDatabase::value_to_bson(&value.into()))
+    }
+
+    /// Field equals one of the values
+    pub fn in_values(self, field: &str, values: &[DatabaseValue]) -> Self {
+        let values = values.iter().map(MongoDatabase::value_to_bson).collect();
+        self.operator(field, "$in", Bson::Array(values))
+    }
+
+    /// Field is present (or absent)
+    pub fn exists(self, field: &str, exists: bool) -> Self {
+        self.operator(field, "$exists", Bson::Boolean(exists))
+    }
+
+    /// Add an operator condition, merging with existing operators on the field
+    fn operator(mut self, field: &str, op: &str, value: Bson) -> Self {
+        match self.document.get_mut(field) {
+            Some(Bson::Document(ops)) => {
+                ops.insert(op, value);
+            }
+            _ => {
+                let mut ops = Document::new();
+                ops.insert(op, value);
+                self.document.insert(field, ops);
+            }
+        }
+        self
+    }
+
+    /// Convert into a BSON filter document
+    pub fn into_document(self) -> Document {
+        self.document
+    }
+}
+
+impl From<MongoFilter> for Document {
+    fn from(filter: MongoFilter) -> Self {
+        filter.into_document()
+    }
+}
+
+/// MongoDB database implementation
+///
+/// Documents are read as `DatabaseRow`s: top-level fields become columns, and
+/// nested documents and arrays are rendered as relaxed extended JSON strings.
+///
+/// Through the `Database` trait, `execute` and `query` run a database command given
+/// as extended JSON (e.g. `{"count": "users"}`); commands take no positional
+/// parameters, so the `*_with_params` methods return `UnsupportedOperation`.
+///
+/// Transactions require a replica set or sharded cluster. While one is open, every
+/// operation issued through this instance runs inside it.
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_database_system::backends::{MongoDatabase, MongoFilter};
+/// use rust_database_system::core::database::Database;
+/// use rust_database_system::core::value::{DatabaseRow, DatabaseValue};
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let db = MongoDatabase::new();
+///     db.connect("mongodb://localhost:27017/app").await?;
+///
+///     let mut user = DatabaseRow::new();
+///     user.insert("name".to_string(), DatabaseValue::from("Alice"));
+///     user.insert("age".to_string(), DatabaseValue::from(30));
+///     db.insert_one("users", &user).await?;
+///
+///     let adults = db.find("users", MongoFilter::new().gte("age", 18)).await?;
+///     println!("{} adults", adults.len());
+///
+///     Ok(())
+/// }
+/// ```
+pub struct MongoDatabase {
+    client: Arc<Mutex<Option<Client>>>,
+    database: Arc<Mutex<Option<mongodb::Database>>>,
+    session: Arc<Mutex<Option<ClientSession>>>,
+}
+
+impl MongoDatabase {
+    /// Create a new MongoDB database instance
+    pub fn new() -> Self {
+        Self {
+            client: Arc::new(Mutex::new(None)),
+            database: Arc::new(Mutex::new(None)),
+            session: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Convert a BSON value to a DatabaseValue
+    ///
+    /// ObjectIds and decimals become strings; nested documents and arrays become
+    /// relaxed extended JSON strings.
+    pub fn bson_to_value(value: Bson) -> DatabaseValue {
+        match value {
+            Bson::Null | Bson::Undefined => DatabaseValue::Null,
+            Bson::Boolean(b) => DatabaseValue::Bool(b),
+            Bson::Int32(i) => DatabaseValue::Int(i),
+            Bson::Int64(l) => DatabaseValue::Long(l),
+            Bson::Double(d) => DatabaseValue::Double(d),
+            Bson::String(s) => DatabaseValue::String(s),
+            Bson::Binary(binary) => DatabaseValue::Bytes(binary.bytes),
+            Bson::DateTime(dt) => DatabaseValue::Timestamp(dt.timestamp_millis() * 1000),
+            Bson::ObjectId(oid) => DatabaseValue::String(oid.to_hex()),
+            Bson::Symbol(s) => DatabaseValue::String(s),
+            Bson::Decimal128(d) => DatabaseValue::String(d.to_string()),
+            other => DatabaseValue::String(other.into_relaxed_extjson().to_string()),
+        }
+    }
+
+    /// Convert a DatabaseValue to a BSON value
+    ///
+    /// Timestamps (microseconds) are stored as BSON dates with millisecond precision.
+    pub fn value_to_bson(value: &DatabaseValue) -> Bson {
+        match value {
+            DatabaseValue::Null => Bson::Null,
+            DatabaseValue::Bool(b) => Bson::Boolean(*b),
+            DatabaseValue::Int(i) => Bson::Int32(*i),
+            DatabaseValue::Long(l) => Bson::Int64(*l),
+            DatabaseValue::Float(f) => Bson::Double(*f as f64),
+            DatabaseValue::Double(d) => Bson::Double(*d),
+            DatabaseValue::String(s) => Bson::String(s.clone()),
+            DatabaseValue::Bytes(b) => Bson::Binary(Binary {
+                subtype: BinarySubtype::Generic,
+                bytes: b.clone(),
+            }),
+            DatabaseValue::Timestamp(ts) => {
+                Bson::DateTime(DateTime::from_millis(ts.div_euclid(1000)))
+            }
+        }
+    }
+
+    /// Convert a BSON document to a DatabaseRow
+    pub fn document_to_row(document: Document) -> DatabaseRow {
+        document
+            .into_iter()
+            .map(|(key, value)| (key, Self::bson_to_value(value)))
+            .collect()
+    }
+
+    /// Convert a DatabaseRow to a BSON document
+    pub fn row_to_document(row: &DatabaseRow) -> Document {
+        row.iter()
+            .map(|(key, value)| (key.clone(), Self::value_to_bson(value)))
+            .collect()
+    }
+
+    /// Map a driver error to a DatabaseError
+    fn map_error(error: mongodb::error::Error) -> DatabaseError {
+        DatabaseError::MongodbError(error.to_string())
+    }
+
+    /// Run a driver operation with the default operation timeout
+    async fn timed<T>(operation: impl IntoFuture<Output = mongodb::error::Result<T>>) -> Result<T> {
+        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, operation)
+            .await
+            .map_err(
+                |_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64),
+            )?
+            .map_err(Self::map_error)
+    }
+
+    /// Get the current database handle
+    async fn current_database(&self) -> Result<mongodb::Database> {
+        self.database
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))
+    }
+
+    /// Get a collection handle
+    async fn collection(&self, name: &str) -> Result<Collection<Document>> {
+        Ok(self.current_database().await?.collection(name))
+    }
+
+    /// Switch to another database on the same server
+    ///
+    /// # Errors
+    ///
+    /// Returns error if not connected or a transaction is open
+    pub async fn use_database(&self, name: &str) -> Result<()> {
+        if self.session.lock().await.is_some() {
+            return Err(DatabaseError::transaction(
+                "Cannot switch database inside a transaction".to_string(),
+            ));
+        }
+
+        let client = self
+            .client
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+        *self.database.lock().await = Some(client.database(name));
+        Ok(())
+    }
+
+    /// Insert a document, returning its `_id`
+    pub async fn insert_one(
+        &self,
+        collection: &str,
+        document: &DatabaseRow,
+    ) -> Result<DatabaseValue> {
+        let coll = self.collection(collection).await?;
+        let document = Self::row_to_document(document);
+
+        let mut session = self.session.lock().await;
+        let result = match session.as_mut() {
+            Some(s) => Self::timed(coll.insert_one(document).session(s)).await?,
+            None => Self::timed(coll.insert_one(document)).await?,
+        };
+
+        Ok(Self::bson_to_value(result.inserted_id))
+    }
+
+    /// Insert several documents, returning the number inserted
+    pub async fn insert_many(&self, collection: &str, documents: &[DatabaseRow]) -> Result<u64> {
+        let coll = self.collection(collection).await?;
+        let documents: Vec<Document> = documents.iter().map(Self::row_to_document).collect();
+
+        let mut session = self.session.lock().await;
+        let result = match session.as_mut() {
+            Some(s) => Self::timed(coll.insert_many(documents).session(s)).await?,
+            None => Self::timed(coll.insert_many(documents)).await?,
+        };
+
+        Ok(result.inserted_ids.len() as u64)
+    }
+
+    /// Find all documents matching a filter
+    pub async fn find(
+        &self,
+        collection: &str,
+        filter: impl Into<Document>,
+    ) -> Result<DatabaseResult> {
+        let coll = self.collection(collection).await?;
+        let filter = filter.into();
+
+        let mut session = self.session.lock().await;
+        let documents: Vec<Document> = match session.as_mut() {
+            Some(s) => {
+                let mut cursor = Self::timed(coll.find(filter).session(&mut *s)).await?;
+                let mut documents = Vec::new();
+                while let Some(document) = cursor.next(&mut *s).await {
+                    documents.push(document.map_err(Self::map_error)?);
+                }
+                documents
+            }
+            None => {
+                let cursor = Self::timed(coll.find(filter)).await?;
+                Self::timed(cursor.try_collect()).await?
+            }
+        };
+
+        Ok(documents.into_iter().map(Self::document_to_row).collect())
+    }
+
+    /// Find the first document matching a filter
+    pub async fn find_one(
+        &self,
+        collection: &str,
+        filter: impl Into<Document>,
+    ) -> Result<Option<DatabaseRow>> {
+        let coll = self.collection(collection).await?;
+        let filter = filter.into();
+
+        let mut session = self.session.lock().await;
+        let document = match session.as_mut() {
+            Some(s) => Self::timed(coll.find_one(filter).session(s)).await?,
+            None => Self::timed(coll.find_one(filter)).await?,
+        };
+
+        Ok(document.map(Self::document_to_row))
+    }
+
+    /// Set fields on every document matching a filter, returning the number modified
+    pub async fn update(
+        &self,
+        collection: &str,
+        filter: impl Into<Document>,
+        changes: &DatabaseRow,
+    ) -> Result<u64> {
+        let update = doc! { "$set": Self::row_to_document(changes) };
+        self.update_with(collection, filter, update).await
+    }
+
+    /// Apply an update document (e.g. `{"$inc": {"visits": 1}}`) to every document
+    /// matching a filter, returning the number modified
+    pub async fn update_with(
+        &self,
+        collection: &str,
+        filter: impl Into<Document>,
+        update: Document,
+    ) -> Result<u64> {
+        let coll = self.collection(collection).await?;
+        let filter = filter.into();
+
+        let mut session = self.session.lock().await;
+        let result = match session.as_mut() {
+            Some(s) => Self::timed(coll.update_many(filter, update).session(s)).await?,
+            None => Self::timed(coll.update_many(filter, update)).await?,
+        };
+
+        Ok(result.modified_count)
+    }
+
+    /// Delete every document matching a filter, returning the number deleted
+    pub async fn delete(&self, collection: &str, filter: impl Into<Document>) -> Result<u64> {
+        let coll = self.collection(collection).await?;
+        let filter = filter.into();
+
+        let mut session = self.session.lock().await;
+        let result = match session.as_mut() {
+            Some(s) => Self::timed(coll.delete_many(filter).session(s)).await?,
+            None => Self::timed(coll.delete_many(filter)).await?,
+        };
+
+        Ok(result.deleted_count)
+    }
+
+    /// Count documents matching a filter
+    pub async fn count(&self, collection: &str, filter: impl Into<Document>) -> Result<u64> {
+        let coll = self.collection(collection).await?;
+        let filter = filter.into();
+
+        let mut session = self.session.lock().await;
+        match session.as_mut() {
+            Some(s) => Self::timed(coll.count_documents(filter).session(s)).await,
+            None => Self::timed(coll.count_documents(filter)).await,
+        }
+    }
+
+    /// Run an aggregation pipeline
+    pub async fn aggregate(
+        &self,
+        collection: &str,
+        pipeline: Vec<Document>,
+    ) -> Result<DatabaseResult> {
+        let coll = self.collection(collection).await?;
+
+        let mut session = self.session.lock().await;
+        let documents: Vec<Document> = match session.as_mut() {
+            Some(s) => {
+                let mut cursor = Self::timed(coll.aggregate(pipeline).session(&mut *s)).await?;
+                let mut documents = Vec::new();
+                while let Some(document) = cursor.next(&mut *s).await {
+                    documents.push(document.map_err(Self::map_error)?);
+                }
+                documents
+            }
+            None => {
+                let cursor = Self::timed(coll.aggregate(pipeline)).await?;
+                Self::timed(cursor.try_collect()).await?
+            }
+        };
+
+        Ok(documents.into_iter().map(Self::document_to_row).collect())
+    }
+
+    /// Parse a database command written as extended JSON
+    fn parse_command(command: &str) -> Result<Document> {
+        let json: serde_json::Value = serde_json::from_str(command)
+            .map_err(|e| DatabaseError::query(format!("Invalid command JSON: {}", e)))?;
+
+        match Bson::try_from(json) {
+            Ok(Bson::Document(document)) => Ok(document),
+            Ok(_) => Err(DatabaseError::query("Command must be a JSON object")),
+            Err(e) => Err(DatabaseError::query(format!("Invalid command: {}", e))),
+        }
+    }
+
+    /// Run a database command inside the open transaction, if any
+    async fn run_command(&self, command: &str) -> Result<Document> {
+        self.run_document(Self::parse_command(command)?).await
+    }
+
+    /// Run a parsed database command inside the open transaction, if any
+    async fn run_document(&self, command: Document) -> Result<Document> {
+        let database = self.current_database().await?;
+
+        let mut session = self.session.lock().await;
+        match session.as_mut() {
+            Some(s) => Self::timed(database.run_command(command).session(s)).await,
+            None => Self::timed(database.run_command(command)).await,
+        }
+    }
+
+    /// `getMore` command fetching the next batch of a command cursor, or `None`
+    /// once the server has closed the cursor
+    fn get_more_command(cursor: &Document) -> Result<Option<Document>> {
+        let id = match cursor.get("id") {
+            Some(Bson::Int64(id)) => *id,
+            Some(Bson::Int32(id)) => i64::from(*id),
+            _ => 0,
+        };
+        if id == 0 {
+            return Ok(None);
+        }
+
+        // The namespace is "<database>.<collection>"
+        let collection = cursor
+            .get_str("ns")
+            .ok()
+            .and_then(|ns| ns.split_once('.'))
+            .map(|(_, collection)| collection)
+            .ok_or_else(|| DatabaseError::query("Open cursor has no namespace"))?;
+        Ok(Some(doc! { "getMore": id, "collection": collection }))
+    }
+
+    /// Take the documents of a cursor batch as rows
+    fn batch_rows(cursor: &mut Document, field: &str) -> DatabaseResult {
+        match cursor.remove(field) {
+            Some(Bson::Array(batch)) => batch
+                .into_iter()
+                .filter_map(|item| match item {
+                    Bson::Document(document) => Some(Self::document_to_row(document)),
+                    _ => None,
+                })
+                .collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Run a transaction control operation on the open session
+    async fn finish_transaction(&self, commit: bool) -> Result<()> {
+        let mut session = self.session.lock().await;
+        let mut s = session
+            .take()
+            .ok_or_else(|| DatabaseError::transaction("Not in a transaction".to_string()))?;
+
+        let result = if commit {
+            Self::timed(s.commit_transaction()).await
+        } else {
+            Self::timed(s.abort_transaction()).await
+        };
+
+        result.map_err(|e| DatabaseError::transaction(e.to_string()))
+    }
+}
+
+impl Default for MongoDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Database for MongoDatabase {
+    fn database_type(&self) -> DatabaseType {
+        DatabaseType::Mongodb
+    }
+
+    /// Connect to a MongoDB deployment
+    ///
+    /// The database named in the connection string path is used, or `test` if
+    /// none is given.
+    async fn connect(&self, connection_string: &str) -> Result<()> {
+        // End any open transaction first
+        self.session.lock().await.take();
+
+        let client = tokio::time::timeout(
+            DEFAULT_OPERATION_TIMEOUT,
+            Client::with_uri_str(connection_string),
+        )
+        .await
+        .map_err(|_| {
+            DatabaseError::connection_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
+        })?
+        .map_err(|e| DatabaseError::InvalidConnectionString(e.to_string()))?;
+
+        let database = client
+            .default_database()
+            .unwrap_or_else(|| client.database(DEFAULT_DATABASE_NAME));
+
+        // The driver connects lazily, so ping to surface connection errors now
+        Self::timed(database.run_command(doc! { "ping": 1 }))
+            .await
+            .map_err(|e| DatabaseError::connection(e.to_string()))?;
+
+        *self.client.lock().await = Some(client);
+        *self.database.lock().await = Some(database);
+
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.database
+            .try_lock()
+            .map(|database| database.is_some())
+            .unwrap_or(false)
+    }
+
+    async fn disconnect(&self) -> Result<()> {
+        // Dropping the session aborts any open transaction on the server
+        self.session.lock().await.take();
+        self.database.lock().await.take();
+        self.client.lock().await.take();
+        Ok(())
+    }
+
+    /// Run a command and return its `n` field (the affected count for write commands)
+    async fn execute(&self, query: &str) -> Result<u64> {
+        let result = self.run_command(query).await?;
+
+        Ok(match result.get("n") {
+            Some(Bson::Int32(n)) => (*n).max(0) as u64,
+            Some(Bson::Int64(n)) => (*n).max(0) as u64,
+            _ => 0,
+        })
+    }
+
+    /// Run a command and return its result
+    ///
+    /// Cursor-returning commands such as `find` and `aggregate` yield every
+    /// document of the cursor, fetching later batches with `getMore`; any other
+    /// command yields its reply as a single row.
+    async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        let mut result = self.run_command(query).await?;
+
+        let mut cursor = match result.remove("cursor") {
+            Some(Bson::Document(cursor)) if cursor.contains_key("firstBatch") => cursor,
+            cursor => {
+                if let Some(cursor) = cursor {
+                    result.insert("cursor", cursor);
+                }
+                return Ok(vec![Self::document_to_row(result)]);
+            }
+        };
+
+        let mut rows = Self::batch_rows(&mut cursor, "firstBatch");
+        while let Some(get_more) = Self::get_more_command(&cursor)? {
+            let mut reply = self.run_document(get_more).await?;
+            cursor = match reply.remove("cursor") {
+                Some(Bson::Document(cursor)) => cursor,
+                _ => return Err(DatabaseError::query("getMore reply has no cursor")),
+            };
+            rows.extend(Self::batch_rows(&mut cursor, "nextBatch"));
+        }
+        Ok(rows)
+    }
+
+    async fn query_with_params(
+        &self,
+        _query: &str,
+        _params: &[DatabaseValue],
+    ) -> Result<DatabaseResult> {
+        Err(DatabaseError::unsupported(
+            "MongoDB commands take no positional parameters; use find() with a MongoFilter",
+        ))
+    }
+
+    async fn execute_with_params(&self, _query: &str, _params: &[DatabaseValue]) -> Result<u64> {
+        Err(DatabaseError::unsupported(
+            "MongoDB commands take no positional parameters; use the document API",
+        ))
+    }
+
+    async fn begin_transaction(&self) -> Result<()> {
+        let mut session = self.session.lock().await;
+
+        if session.is_some() {
+            return Err(DatabaseError::transaction(
+                "Already in a transaction".to_string(),
+            ));
+        }
+
+        let client = self
+            .client
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+
+        let mut s = Self::timed(client.start_session())
+            .await
+            .map_err(|e| DatabaseError::transaction(e.to_string()))?;
+        Self::timed(s.start_transaction())
+            .await
+            .map_err(|e| DatabaseError::transaction(e.to_string()))?;
+
+        *session = Some(s);
+        Ok(())
+    }
+
+    async fn commit(&self) -> Result<()> {
+        self.finish_transaction(true).await
+    }
+
+    async fn rollback(&self) -> Result<()> {
+        self.finish_transaction(false).await
+    }
+
+    fn in_transaction(&self) -> bool {
+        self.session
+            .try_lock()
+            .map(|session| session.is_some())
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(all(test, feature = "mongodb_support"))]
+mod tests {
+    use super::*;
+
+    fn get_mongodb_url() -> Option<String> {
+        std::env::var("MONGODB_URL").ok()
+    }
+
+    #[test]
+    fn test_value_conversion() {
+        let values = [
+            DatabaseValue::Null,
+            DatabaseValue::Bool(true),
+            DatabaseValue::Int(42),
+            DatabaseValue::Long(1 << 40),
+            DatabaseValue::Double(2.5),
+            DatabaseValue::String("text".to_string()),
+            DatabaseValue::Bytes(vec![1, 2, 3]),
+            DatabaseValue::Timestamp(1_700_000_000_123_000),
+        ];
+
+        for value in values {
+            let round_trip = MongoDatabase::bson_to_value(MongoDatabase::value_to_bson(&value));
+            assert_eq!(round_trip, value);
+        }
+
+        let oid = ObjectId::new();
+        assert_eq!(
+            MongoDatabase::bson_to_value(Bson::ObjectId(oid)),
+            DatabaseValue::String(oid.to_hex())
+        );
+        assert_eq!(
+            MongoDatabase::bson_to_value(Bson::Document(doc! { "a": 1 })),
+            DatabaseValue::String(r#"{"a":1}"#.to_string())
+        );
+    }
+
+    #[test]
+    fn test_document_to_row() {
+        let row = MongoDatabase::document_to_row(doc! { "name": "Alice", "age": 30 });
+        assert_eq!(
+            row.get("name"),
+            Some(&DatabaseValue::String("Alice".to_string()))
+        );
+        assert_eq!(row.get("age"), Some(&DatabaseValue::Int(30)));
+    }
+
+    #[test]
+    fn test_filter_builder() {
+        let filter = MongoFilter::new()
+            .eq("status", "active")
+            .gte("age", 18)
+            .lt("age", 65)
+            .in_values("role", &[DatabaseValue::from("admin")])
+            .into_document();
+
+        assert_eq!(
+            filter,
+            doc! {
+                "status": "active",
+                "age": { "$gte": 18, "$lt": 65 },
+                "role": { "$in": ["admin"] },
+            }
+        );
+
+        let oid = ObjectId::new();
+        assert_eq!(
+            MongoFilter::id(&oid.to_hex()).into_document(),
+            doc! { "_id": oid }
+        );
+        assert_eq!(
+            MongoFilter::id("custom").into_document(),
+            doc! { "_id": "custom" }
+        );
+    }
+
+    #[test]
+    fn test_parse_command() {
+        let command = MongoDatabase::parse_command(r#"{"count": "users", "query": {"age": 3}}"#)
+            .expect("valid command");
+        assert_eq!(command.get_str("count").ok(), Some("users"));
+        assert!(MongoDatabase::parse_command("[1, 2]").is_err());
+        assert!(MongoDatabase::parse_command("not json").is_err());
+    }
+
+    #[test]
+    fn test_get_more_command() {
+        let exhausted = doc! { "id": 0_i64, "ns": "app.users", "firstBatch": [] };
+        assert_eq!(MongoDatabase::get_more_command(&exhausted).unwrap(), None);
+
+        let open = doc! { "id": 42_i64, "ns": "app.audit.log", "nextBatch": [] };
+        assert_eq!(
+            MongoDatabase::get_more_command(&open).unwrap(),
+            Some(doc! { "getMore": 42_i64, "collection": "audit.log" })
+        );
+        assert!(MongoDatabase::get_more_command(&doc! { "id": 42_i64 }).is_err());
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features mongodb_support -- --ignored
+    async fn test_mongodb_documents() -> Result<()> {
+        let url = match get_mongodb_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: MONGODB_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = MongoDatabase::new();
+        db.connect(&url).await?;
+        db.delete("test_documents", MongoFilter::new()).await?;
+
+        let mut alice = DatabaseRow::new();
+        alice.insert("name".to_string(), DatabaseValue::from("Alice"));
+        alice.insert("age".to_string(), DatabaseValue::from(30));
+        let id = db.insert_one("test_documents", &alice).await?;
+
+        let mut bob = DatabaseRow::new();
+        bob.insert("name".to_string(), DatabaseValue::from("Bob"));
+        bob.insert("age".to_string(), DatabaseValue::from(17));
+        assert_eq!(db.insert_many("test_documents", &[bob]).await?, 1);
+
+        let adults = db
+            .find("test_documents", MongoFilter::new().gte("age", 18))
+            .await?;
+        assert_eq!(adults.len(), 1);
+        assert_eq!(adults[0].get("_id"), Some(&id));
+
+        let mut changes = DatabaseRow::new();
+        changes.insert("age".to_string(), DatabaseValue::from(18));
+        let modified = db
+            .update(
+                "test_documents",
+                MongoFilter::new().eq("name", "Bob"),
+                &changes,
+            )
+            .await?;
+        assert_eq!(modified, 1);
+        assert_eq!(
+            db.count("test_documents", MongoFilter::new().gte("age", 18))
+                .await?,
+            2
+        );
+
+        let totals = db
+            .aggregate(
+                "test_documents",
+                vec![doc! { "$group": { "_id": null, "total": { "$sum": "$age" } } }],
+            )
+            .await?;
+        assert_eq!(totals[0].get("total"), Some(&DatabaseValue::Int(48)));
+
+        let rows = db
+            .query(r#"{"find": "test_documents", "filter": {"name": "Alice"}}"#)
+            .await?;
+        assert_eq!(rows.len(), 1);
+
+        // Results beyond the first batch are fetched too
+        let rows = db
+            .query(r#"{"find": "test_documents", "batchSize": 1}"#)
+            .await?;
+        assert_eq!(rows.len(), 2);
+
+        assert_eq!(db.delete("test_documents", MongoFilter::new()).await?, 2);
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore] // Requires a replica set: cargo test --features mongodb_support -- --ignored
+    async fn test_mongodb_transaction() -> Result<()> {
+        let url = match get_mongodb_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: MONGODB_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = MongoDatabase::new();
+        db.connect(&url).await?;
+        db.delete("test_transactions", MongoFilter::new()).await?;
+
+        let mut row = DatabaseRow::new();
+        row.insert("value".to_string(), DatabaseValue::from(1));
+
+        db.begin_transaction().await?;
+        assert!(db.in_transaction());
+        db.insert_one("test_transactions", &row).await?;
+        db.rollback().await?;
+        assert_eq!(db.count("test_transactions", MongoFilter::new()).await?, 0);
+
+        db.begin_transaction().await?;
+        db.insert_one("test_transactions", &row).await?;
+        db.commit().await?;
+        assert!(!db.in_transaction());
+        assert_eq!(db.count("test_transactions", MongoFilter::new()).await?, 1);
+
+        db.delete("test_transactions", MongoFilter::new()).await?;
+        Ok(())
+    }
+}
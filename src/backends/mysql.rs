@@ -0,0 +1,799 @@
+//! MySQL/MariaDB database backend implementation
+//!
+//! This module provides a MySQL implementation of the Database trait using mysql_async.
+
+use crate::core::{
+    database::Database,
+    database_types::DatabaseType,
+    dialect::Dialect,
+    error::DatabaseError,
+    error::Result,
+    result_set::{ColumnInfo, ResultSet},
+    transaction::{
+        abandoned_rollback_sql, release_savepoint_sql, rollback_to_savepoint_sql, run_rolled_back,
+        savepoint_sql, AbandonedLevel, CallbackStack, TransactionCallbacks, TransactionOptions,
+    },
+    value::DatabaseResult,
+    value::DatabaseValue,
+};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Timelike};
+use mysql_async::consts::{ColumnFlags, ColumnType};
+use mysql_async::prelude::{Protocol, Queryable};
+use mysql_async::{Column, Conn, Params, QueryResult, Row, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Default timeout for database operations (30 seconds)
+const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Character set id MySQL reports for binary (BLOB/BINARY) columns
+const BINARY_CHARSET: u16 = 63;
+
+/// MySQL/MariaDB database implementation
+pub struct MysqlDatabase {
+    conn: Arc<Mutex<Option<Conn>>>,
+    /// Number of open transaction levels; levels above the first are savepoints
+    transaction_depth: Arc<Mutex<usize>>,
+    /// Level left open by a dropped `TransactionGuard`, rolled back before the next operation
+    abandoned: AbandonedLevel,
+    /// `on_commit` / `on_rollback` callbacks of every open transaction level
+    callbacks: CallbackStack,
+}
+
+impl MysqlDatabase {
+    /// Create a new MySQL database instance
+    pub fn new() -> Self {
+        Self {
+            conn: Arc::new(Mutex::new(None)),
+            transaction_depth: Arc::new(Mutex::new(0)),
+            abandoned: AbandonedLevel::default(),
+            callbacks: CallbackStack::default(),
+        }
+    }
+
+    /// Describe a MySQL column
+    fn column_info(column: &Column) -> ColumnInfo {
+        let column_type = format!("{:?}", column.column_type());
+        let info = ColumnInfo::new(column.name_str())
+            .with_declared_type(column_type.trim_start_matches("MYSQL_TYPE_"))
+            .with_nullable(!column.flags().contains(ColumnFlags::NOT_NULL_FLAG));
+
+        let table = column.org_table_str();
+        if table.is_empty() {
+            info
+        } else {
+            info.with_table(table)
+        }
+    }
+
+    /// Collect the first result set of a query result with its column metadata
+    async fn collect_result_set<P: Protocol>(
+        mut result: QueryResult<'_, '_, P>,
+    ) -> mysql_async::Result<ResultSet> {
+        let columns = result.columns().unwrap_or_default();
+        let rows: Vec<Row> = result.collect().await?;
+        result.drop_result().await?;
+
+        let values = rows
+            .into_iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .zip(row.unwrap())
+                    .map(|(column, value)| Self::convert_value(column, value))
+                    .collect()
+            })
+            .collect();
+
+        Ok(ResultSet::new(
+            columns.iter().map(Self::column_info).collect(),
+            values,
+        ))
+    }
+
+    /// Convert a MySQL value to a DatabaseValue using the column metadata
+    ///
+    /// Values arrive as raw bytes over the text protocol and as typed values over
+    /// the binary (prepared statement) protocol, so both forms are handled here.
+    fn convert_value(column: &Column, value: Value) -> DatabaseValue {
+        let is_bool =
+            column.column_type() == ColumnType::MYSQL_TYPE_TINY && column.column_length() == 1;
+
+        match value {
+            Value::NULL => DatabaseValue::Null,
+            Value::Int(v) if is_bool => DatabaseValue::Bool(v != 0),
+            Value::Int(v) => DatabaseValue::Long(v),
+            Value::UInt(v) => i64::try_from(v)
+                .map(DatabaseValue::Long)
+                .unwrap_or_else(|_| DatabaseValue::String(v.to_string())),
+            Value::Float(v) => DatabaseValue::Float(v),
+            Value::Double(v) => DatabaseValue::Double(v),
+            Value::Date(year, month, day, hour, minute, second, micros) => {
+                NaiveDate::from_ymd_opt(year as i32, month as u32, day as u32)
+                    .and_then(|date| {
+                        date.and_hms_micro_opt(hour as u32, minute as u32, second as u32, micros)
+                    })
+                    .map(|dt| DatabaseValue::Timestamp(dt.and_utc().timestamp_micros()))
+                    .unwrap_or(DatabaseValue::Null)
+            }
+            Value::Time(negative, days, hours, minutes, seconds, micros) => {
+                let hours = days * 24 + hours as u32;
+                DatabaseValue::String(format!(
+                    "{}{:02}:{:02}:{:02}.{:06}",
+                    if negative { "-" } else { "" },
+                    hours,
+                    minutes,
+                    seconds,
+                    micros
+                ))
+            }
+            Value::Bytes(bytes) => Self::convert_text_value(column, bytes, is_bool),
+        }
+    }
+
+    /// Convert a text protocol value according to its declared column type
+    fn convert_text_value(column: &Column, bytes: Vec<u8>, is_bool: bool) -> DatabaseValue {
+        let text = || String::from_utf8_lossy(&bytes).to_string();
+
+        match column.column_type() {
+            ColumnType::MYSQL_TYPE_TINY
+            | ColumnType::MYSQL_TYPE_SHORT
+            | ColumnType::MYSQL_TYPE_INT24
+            | ColumnType::MYSQL_TYPE_LONG
+            | ColumnType::MYSQL_TYPE_LONGLONG
+            | ColumnType::MYSQL_TYPE_YEAR => match text().parse::<i64>() {
+                Ok(v) if is_bool => DatabaseValue::Bool(v != 0),
+                Ok(v) => DatabaseValue::Long(v),
+                Err(_) => DatabaseValue::String(text()),
+            },
+            ColumnType::MYSQL_TYPE_FLOAT => text()
+                .parse::<f32>()
+                .map(DatabaseValue::Float)
+                .unwrap_or_else(|_| DatabaseValue::String(text())),
+            ColumnType::MYSQL_TYPE_DOUBLE => text()
+                .parse::<f64>()
+                .map(DatabaseValue::Double)
+                .unwrap_or_else(|_| DatabaseValue::String(text())),
+            ColumnType::MYSQL_TYPE_DATE => NaiveDate::parse_from_str(&text(), "%Y-%m-%d")
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|dt| DatabaseValue::Timestamp(dt.and_utc().timestamp_micros()))
+                .unwrap_or_else(|| DatabaseValue::String(text())),
+            ColumnType::MYSQL_TYPE_DATETIME
+            | ColumnType::MYSQL_TYPE_DATETIME2
+            | ColumnType::MYSQL_TYPE_TIMESTAMP
+            | ColumnType::MYSQL_TYPE_TIMESTAMP2 => {
+                NaiveDateTime::parse_from_str(&text(), "%Y-%m-%d %H:%M:%S%.f")
+                    .map(|dt| DatabaseValue::Timestamp(dt.and_utc().timestamp_micros()))
+                    .unwrap_or_else(|_| DatabaseValue::String(text()))
+            }
+            ColumnType::MYSQL_TYPE_BIT => DatabaseValue::Bytes(bytes),
+            _ if column.character_set() == BINARY_CHARSET => DatabaseValue::Bytes(bytes),
+            _ => DatabaseValue::String(text()),
+        }
+    }
+
+    /// Convert DatabaseValue to a MySQL parameter
+    fn value_to_param(value: &DatabaseValue) -> Value {
+        match value {
+            DatabaseValue::Null => Value::NULL,
+            DatabaseValue::Bool(v) => Value::Int(*v as i64),
+            DatabaseValue::Int(v) => Value::Int(*v as i64),
+            DatabaseValue::Long(v) => Value::Int(*v),
+            DatabaseValue::Float(v) => Value::Float(*v),
+            DatabaseValue::Double(v) => Value::Double(*v),
+            DatabaseValue::String(v) => Value::Bytes(v.clone().into_bytes()),
+            DatabaseValue::Bytes(v) => Value::Bytes(v.clone()),
+            DatabaseValue::Timestamp(v) => match DateTime::from_timestamp_micros(*v) {
+                Some(dt) => Value::Date(
+                    dt.year() as u16,
+                    dt.month() as u8,
+                    dt.day() as u8,
+                    dt.hour() as u8,
+                    dt.minute() as u8,
+                    dt.second() as u8,
+                    dt.timestamp_subsec_micros(),
+                ),
+                None => Value::Int(*v),
+            },
+        }
+    }
+
+    /// Convert a parameter slice into positional MySQL parameters
+    fn to_params(params: &[DatabaseValue]) -> Params {
+        if params.is_empty() {
+            Params::Empty
+        } else {
+            Params::Positional(params.iter().map(Self::value_to_param).collect())
+        }
+    }
+
+    /// Map a driver error to a DatabaseError
+    ///
+    /// Deadlocks (1213) and lock wait timeouts (1205) become retryable
+    /// transaction conflicts, duplicate entries (1062) unique violations.
+    fn map_error(error: mysql_async::Error) -> DatabaseError {
+        match Self::classified_error(&error) {
+            Some(classified) => classified,
+            None => DatabaseError::MysqlError(error.to_string()),
+        }
+    }
+
+    /// Classify deadlocks and lock wait timeouts as transaction conflicts, and
+    /// duplicate entries as unique violations
+    fn classified_error(error: &mysql_async::Error) -> Option<DatabaseError> {
+        match error {
+            mysql_async::Error::Server(server) if matches!(server.code, 1205 | 1213) => {
+                Some(DatabaseError::transaction_conflict(server.to_string()))
+            }
+            mysql_async::Error::Server(server) if server.code == 1062 => {
+                Some(DatabaseError::unique_violation(server.to_string()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Roll back the level abandoned by a dropped `TransactionGuard`, if any
+    ///
+    /// Must be called with the transaction depth locked.
+    async fn roll_back_abandoned(&self, transaction_depth: &mut usize) -> Result<()> {
+        let level = match self.abandoned.take() {
+            Some(level) => level,
+            None => return Ok(()),
+        };
+
+        // The level may already have been finished explicitly
+        if *transaction_depth >= level {
+            for statement in abandoned_rollback_sql(level) {
+                if let Err(e) = self.execute_control(&statement).await {
+                    self.abandoned.mark(level);
+                    return Err(e);
+                }
+            }
+            *transaction_depth = level - 1;
+        }
+        Ok(())
+    }
+
+    /// Roll back an abandoned level before running a statement
+    async fn resolve_abandoned(&self) -> Result<()> {
+        if self.abandoned.level().is_none() {
+            return Ok(());
+        }
+        let mut transaction_depth = self.transaction_depth.lock().await;
+        self.roll_back_abandoned(&mut transaction_depth).await
+    }
+
+    /// Run a transaction control statement on the current connection
+    async fn execute_control(&self, statement: &str) -> Result<()> {
+        let mut conn = self.conn.lock().await;
+        let conn = conn
+            .as_mut()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+
+        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, conn.query_drop(statement))
+            .await
+            .map_err(
+                |_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64),
+            )?
+            .map_err(|e| {
+                Self::classified_error(&e)
+                    .unwrap_or_else(|| DatabaseError::transaction(e.to_string()))
+            })
+    }
+}
+
+impl Default for MysqlDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Database for MysqlDatabase {
+    fn database_type(&self) -> DatabaseType {
+        DatabaseType::Mysql
+    }
+
+    async fn connect(&self, connection_string: &str) -> Result<()> {
+        // Clean up any existing connection first
+        let previous = self.conn.lock().await.take();
+        if let Some(conn) = previous {
+            let _ = conn.disconnect().await;
+        }
+
+        // Reset transaction depth
+        let closed_levels = {
+            let mut transaction_depth = self.transaction_depth.lock().await;
+            *transaction_depth = 0;
+            self.abandoned.take();
+            self.callbacks.truncate(1)
+        };
+        run_rolled_back(closed_levels);
+
+        let conn =
+            tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, Conn::from_url(connection_string))
+                .await
+                .map_err(|_| {
+                    DatabaseError::connection_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
+                })?
+                .map_err(|e| DatabaseError::connection(e.to_string()))?;
+
+        let mut guard = self.conn.lock().await;
+        *guard = Some(conn);
+
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.conn
+            .try_lock()
+            .map(|conn| conn.is_some())
+            .unwrap_or(false)
+    }
+
+    async fn disconnect(&self) -> Result<()> {
+        // Clear transaction depth
+        let closed_levels = {
+            let mut transaction_depth = self.transaction_depth.lock().await;
+            *transaction_depth = 0;
+            self.abandoned.take();
+            self.callbacks.truncate(1)
+        };
+        run_rolled_back(closed_levels);
+
+        let conn = self.conn.lock().await.take();
+        if let Some(conn) = conn {
+            conn.disconnect().await.map_err(Self::map_error)?;
+        }
+        Ok(())
+    }
+
+    async fn execute(&self, query: &str) -> Result<u64> {
+        self.resolve_abandoned().await?;
+        let mut conn = self.conn.lock().await;
+        let conn = conn
+            .as_mut()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+
+        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, conn.query_drop(query))
+            .await
+            .map_err(
+                |_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64),
+            )?
+            .map_err(Self::map_error)?;
+
+        Ok(conn.affected_rows())
+    }
+
+    async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        self.query_rows(query)
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    async fn query_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, params)
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.resolve_abandoned().await?;
+        let mut conn = self.conn.lock().await;
+        let conn = conn
+            .as_mut()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+
+        let query_future = async {
+            let result = conn.query_iter(query).await?;
+            Self::collect_result_set(result).await
+        };
+
+        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, query_future)
+            .await
+            .map_err(
+                |_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64),
+            )?
+            .map_err(Self::map_error)
+    }
+
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        self.resolve_abandoned().await?;
+        let mut conn = self.conn.lock().await;
+        let conn = conn
+            .as_mut()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+
+        let query_future = async {
+            let result = conn.exec_iter(query, Self::to_params(params)).await?;
+            Self::collect_result_set(result).await
+        };
+
+        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, query_future)
+            .await
+            .map_err(
+                |_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64),
+            )?
+            .map_err(Self::map_error)
+    }
+
+    async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        self.resolve_abandoned().await?;
+        let mut conn = self.conn.lock().await;
+        let conn = conn
+            .as_mut()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+
+        tokio::time::timeout(
+            DEFAULT_OPERATION_TIMEOUT,
+            conn.exec_drop(query, Self::to_params(params)),
+        )
+        .await
+        .map_err(|_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64))?
+        .map_err(Self::map_error)?;
+
+        Ok(conn.affected_rows())
+    }
+
+    async fn begin_transaction(&self) -> Result<()> {
+        self.begin_transaction_with_options(TransactionOptions::default())
+            .await
+    }
+
+    async fn begin_transaction_with_options(&self, options: TransactionOptions) -> Result<()> {
+        self.begin_transaction_level(options).await.map(|_| ())
+    }
+
+    async fn begin_transaction_level(&self, options: TransactionOptions) -> Result<usize> {
+        let mut transaction_depth = self.transaction_depth.lock().await;
+        self.roll_back_abandoned(&mut transaction_depth).await?;
+
+        // Nested transactions are backed by savepoints
+        if *transaction_depth == 0 {
+            for statement in options.begin_statements(Dialect::Mysql)? {
+                self.execute_control(&statement).await?;
+            }
+        } else {
+            options.ensure_nestable()?;
+            self.execute_control(&savepoint_sql(*transaction_depth))
+                .await?;
+        }
+        *transaction_depth += 1;
+        self.callbacks.push();
+
+        Ok(*transaction_depth)
+    }
+
+    async fn commit(&self) -> Result<()> {
+        let mut transaction_depth = self.transaction_depth.lock().await;
+        self.roll_back_abandoned(&mut transaction_depth).await?;
+
+        if *transaction_depth == 0 {
+            return Err(DatabaseError::transaction(
+                "Not in a transaction".to_string(),
+            ));
+        }
+
+        if *transaction_depth == 1 {
+            self.execute_control("COMMIT").await?;
+        } else {
+            self.execute_control(&release_savepoint_sql(*transaction_depth - 1))
+                .await?;
+        }
+        *transaction_depth -= 1;
+        let finished = self.callbacks.commit();
+        drop(transaction_depth);
+
+        // Callbacks run once the transaction state is unlocked
+        if let Some(callbacks) = finished {
+            callbacks.committed();
+        }
+        Ok(())
+    }
+
+    async fn rollback(&self) -> Result<()> {
+        let mut transaction_depth = self.transaction_depth.lock().await;
+        self.roll_back_abandoned(&mut transaction_depth).await?;
+
+        if *transaction_depth == 0 {
+            return Err(DatabaseError::transaction(
+                "Not in a transaction".to_string(),
+            ));
+        }
+
+        if *transaction_depth == 1 {
+            self.execute_control("ROLLBACK").await?;
+        } else {
+            for statement in rollback_to_savepoint_sql(*transaction_depth - 1) {
+                self.execute_control(&statement).await?;
+            }
+        }
+        *transaction_depth -= 1;
+        let finished = self.callbacks.rollback();
+        drop(transaction_depth);
+
+        // Callbacks run once the transaction state is unlocked
+        if let Some(callbacks) = finished {
+            callbacks.rolled_back();
+        }
+        Ok(())
+    }
+
+    fn in_transaction(&self) -> bool {
+        self.transaction_depth() > 0
+    }
+
+    fn transaction_depth(&self) -> usize {
+        let depth = self
+            .transaction_depth
+            .try_lock()
+            .map(|guard| *guard)
+            .unwrap_or(0);
+        self.abandoned.effective_depth(depth)
+    }
+
+    fn defer_rollback(&self, depth: usize) -> bool {
+        self.abandoned.mark(depth);
+        run_rolled_back(self.callbacks.truncate(depth));
+        true
+    }
+
+    fn transaction_callbacks(&self, depth: usize) -> Option<Arc<TransactionCallbacks>> {
+        self.callbacks.level(depth)
+    }
+}
+
+#[cfg(all(test, feature = "mysql"))]
+mod tests {
+    use super::*;
+
+    fn get_mysql_url() -> Option<String> {
+        std::env::var("MYSQL_URL").ok()
+    }
+
+    #[test]
+    fn test_value_to_param() {
+        assert_eq!(
+            MysqlDatabase::value_to_param(&DatabaseValue::Bool(true)),
+            Value::Int(1)
+        );
+        assert_eq!(
+            MysqlDatabase::value_to_param(&DatabaseValue::String("abc".to_string())),
+            Value::Bytes(b"abc".to_vec())
+        );
+        assert_eq!(
+            MysqlDatabase::value_to_param(&DatabaseValue::Timestamp(1_000_000)),
+            Value::Date(1970, 1, 1, 0, 0, 1, 0)
+        );
+        assert!(matches!(MysqlDatabase::to_params(&[]), Params::Empty));
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: MYSQL_URL=mysql://... cargo test --features mysql -- --ignored
+    async fn test_mysql_connect() {
+        let url = match get_mysql_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: MYSQL_URL not set");
+                return;
+            }
+        };
+
+        let db = MysqlDatabase::new();
+        assert!(db.connect(&url).await.is_ok());
+        assert!(db.is_connected());
+        assert!(db.disconnect().await.is_ok());
+        assert!(!db.is_connected());
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: MYSQL_URL=mysql://... cargo test --features mysql -- --ignored
+    async fn test_mysql_execute() -> Result<()> {
+        let url = match get_mysql_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: MYSQL_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = MysqlDatabase::new();
+        db.connect(&url).await?;
+
+        // Create a temporary table
+        let _ = db.execute("DROP TABLE IF EXISTS test_execute").await;
+        let result = db
+            .execute("CREATE TABLE test_execute (id INT AUTO_INCREMENT PRIMARY KEY, name TEXT)")
+            .await;
+        assert!(result.is_ok());
+
+        let affected = db
+            .execute("INSERT INTO test_execute (name) VALUES ('Alice')")
+            .await?;
+        assert_eq!(affected, 1);
+
+        // Cleanup
+        db.execute("DROP TABLE test_execute").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: MYSQL_URL=mysql://... cargo test --features mysql -- --ignored
+    async fn test_mysql_query() -> Result<()> {
+        let url = match get_mysql_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: MYSQL_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = MysqlDatabase::new();
+        db.connect(&url).await?;
+
+        // Create and populate test table
+        let _ = db.execute("DROP TABLE IF EXISTS test_query").await;
+        db.execute("CREATE TABLE test_query (id INT AUTO_INCREMENT PRIMARY KEY, name TEXT)")
+            .await?;
+        db.execute("INSERT INTO test_query (name) VALUES ('Alice')")
+            .await?;
+        db.execute("INSERT INTO test_query (name) VALUES ('Bob')")
+            .await?;
+
+        let results = db.query("SELECT * FROM test_query ORDER BY id").await?;
+        assert_eq!(results.len(), 2);
+
+        let name1 = results[0]
+            .get("name")
+            .ok_or_else(|| DatabaseError::ColumnNotFound("name".to_string()))?
+            .as_string();
+        assert_eq!(name1, "Alice");
+        assert_eq!(results[0].get("id"), Some(&DatabaseValue::Long(1)));
+
+        let name2 = results[1]
+            .get("name")
+            .ok_or_else(|| DatabaseError::ColumnNotFound("name".to_string()))?
+            .as_string();
+        assert_eq!(name2, "Bob");
+
+        // Cleanup
+        db.execute("DROP TABLE test_query").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: MYSQL_URL=mysql://... cargo test --features mysql -- --ignored
+    async fn test_mysql_value_types() -> Result<()> {
+        let url = match get_mysql_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: MYSQL_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = MysqlDatabase::new();
+        db.connect(&url).await?;
+
+        let _ = db.execute("DROP TABLE IF EXISTS test_types").await;
+        db.execute(
+            "CREATE TABLE test_types (
+                id INT PRIMARY KEY,
+                flag BOOLEAN,
+                big BIGINT,
+                ratio DOUBLE,
+                label VARCHAR(32),
+                payload BLOB,
+                created_at DATETIME(6),
+                missing TEXT
+            )",
+        )
+        .await?;
+
+        let created_at = 1_700_000_000_123_456i64;
+        db.execute_with_params(
+            "INSERT INTO test_types VALUES (?, ?, ?, ?, ?, ?, ?, ?)",
+            &[
+                DatabaseValue::Int(1),
+                DatabaseValue::Bool(true),
+                DatabaseValue::Long(9_000_000_000),
+                DatabaseValue::Double(2.5),
+                DatabaseValue::String("hello".to_string()),
+                DatabaseValue::Bytes(vec![0, 1, 255]),
+                DatabaseValue::Timestamp(created_at),
+                DatabaseValue::Null,
+            ],
+        )
+        .await?;
+
+        // Binary protocol (prepared statement) and text protocol must agree
+        let prepared = db
+            .query_with_params("SELECT * FROM test_types WHERE id = ?", &[1.into()])
+            .await?;
+        let text = db.query("SELECT * FROM test_types WHERE id = 1").await?;
+
+        for results in [&prepared, &text] {
+            assert_eq!(results.len(), 1);
+            let row = &results[0];
+            assert_eq!(row.get("flag"), Some(&DatabaseValue::Bool(true)));
+            assert_eq!(row.get("big"), Some(&DatabaseValue::Long(9_000_000_000)));
+            assert_eq!(row.get("ratio"), Some(&DatabaseValue::Double(2.5)));
+            assert_eq!(
+                row.get("label").map(|v| v.as_string()),
+                Some("hello".into())
+            );
+            assert_eq!(
+                row.get("payload"),
+                Some(&DatabaseValue::Bytes(vec![0, 1, 255]))
+            );
+            assert_eq!(
+                row.get("created_at"),
+                Some(&DatabaseValue::Timestamp(created_at))
+            );
+            assert_eq!(row.get("missing"), Some(&DatabaseValue::Null));
+        }
+
+        // Cleanup
+        db.execute("DROP TABLE test_types").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: MYSQL_URL=mysql://... cargo test --features mysql -- --ignored
+    async fn test_mysql_transaction() -> Result<()> {
+        let url = match get_mysql_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: MYSQL_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = MysqlDatabase::new();
+        db.connect(&url).await?;
+
+        // Create test table (InnoDB is required for transactional behaviour)
+        let _ = db.execute("DROP TABLE IF EXISTS test_transaction").await;
+        db.execute(
+            "CREATE TABLE test_transaction (id INT AUTO_INCREMENT PRIMARY KEY, name TEXT) ENGINE=InnoDB",
+        )
+        .await?;
+
+        // Test commit
+        db.begin_transaction().await?;
+        assert!(db.in_transaction());
+
+        db.execute("INSERT INTO test_transaction (name) VALUES ('Alice')")
+            .await?;
+        db.commit().await?;
+        assert!(!db.in_transaction());
+
+        let results = db.query("SELECT * FROM test_transaction").await?;
+        assert_eq!(results.len(), 1);
+
+        // Test rollback
+        db.begin_transaction().await?;
+        db.execute("INSERT INTO test_transaction (name) VALUES ('Bob')")
+            .await?;
+        db.rollback().await?;
+        assert!(!db.in_transaction());
+
+        let results = db.query("SELECT * FROM test_transaction").await?;
+        assert_eq!(results.len(), 1); // Still only Alice
+
+        // Cleanup
+        db.execute("DROP TABLE test_transaction").await?;
+        Ok(())
+    }
+}
@@ -0,0 +1,72 @@
+//! Shared connection pool configuration
+//!
+//! This module provides the configuration and statistics types used by every
+//! pooled backend (SQLite and PostgreSQL).
+
+use std::time::Duration;
+
+/// Default timeout for database operations (30 seconds)
+const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Pool configuration for pooled database connections
+#[derive(Debug, Clone)]
+pub struct PoolConfig {
+    /// Maximum number of connections in the pool
+    pub max_size: usize,
+    /// Timeout for acquiring a connection from the pool
+    pub timeout: Duration,
+    /// Timeout for database operations (query, execute, etc.)
+    pub operation_timeout: Duration,
+    /// Backend connection string (SQLite path or PostgreSQL connection string)
+    pub connection_string: String,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_size: 16,
+            timeout: Duration::from_secs(5),
+            operation_timeout: DEFAULT_OPERATION_TIMEOUT,
+            connection_string: String::new(),
+        }
+    }
+}
+
+impl PoolConfig {
+    /// Create a new pool configuration
+    pub fn new(connection_string: impl Into<String>) -> Self {
+        Self {
+            connection_string: connection_string.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Set maximum pool size
+    pub fn with_max_size(mut self, size: usize) -> Self {
+        self.max_size = size;
+        self
+    }
+
+    /// Set connection acquisition timeout
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Set database operation timeout (for query, execute, etc.)
+    pub fn with_operation_timeout(mut self, timeout: Duration) -> Self {
+        self.operation_timeout = timeout;
+        self
+    }
+}
+
+/// Pool statistics
+#[derive(Debug, Clone)]
+pub struct PoolStats {
+    /// Total number of connections in the pool
+    pub size: usize,
+    /// Number of available connections
+    pub available: usize,
+    /// Number of requests waiting for a connection
+    pub waiting: usize,
+}
@@ -0,0 +1,915 @@
+//! Pooled PostgreSQL database backend implementation
+//!
+//! This module provides a connection-pooled PostgreSQL implementation of the Database trait
+//! using deadpool-postgres, so concurrent tasks no longer serialize on a single client.
+
+use super::pool::{PoolConfig, PoolStats};
+use super::postgres::PostgresDatabase;
+use crate::core::{
+    database::Database, database_types::DatabaseType, dialect::Dialect, error::DatabaseError,
+    error::Result, result_set::ResultSet, result_set::RowStream,
+    transaction::release_savepoint_sql, transaction::rollback_to_savepoint_sql,
+    transaction::savepoint_sql, transaction::DropHook, transaction::DropReport,
+    transaction::Transaction, transaction::TransactionCallbacks, transaction::TransactionOptions,
+    value::DatabaseResult, value::DatabaseValue,
+};
+use async_trait::async_trait;
+use deadpool_postgres::{
+    Manager, ManagerConfig, Object, Pool, PoolError, RecyclingMethod, Runtime,
+};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+use tokio_postgres::types::ToSql;
+use tokio_postgres::{Client, NoTls};
+
+/// Execute a parameterized statement on a client with a timeout
+async fn execute_on(
+    client: &Client,
+    query: &str,
+    params: &[DatabaseValue],
+    timeout: Duration,
+) -> Result<u64> {
+    let postgres_params: Vec<Box<dyn ToSql + Sync + Send>> = params
+        .iter()
+        .map(PostgresDatabase::value_to_param)
+        .collect();
+    let param_refs: Vec<&(dyn ToSql + Sync)> = postgres_params
+        .iter()
+        .map(|p| p.as_ref() as &(dyn ToSql + Sync))
+        .collect();
+
+    tokio::time::timeout(timeout, client.execute(query, &param_refs))
+        .await
+        .map_err(|_| DatabaseError::query_timeout(timeout.as_millis() as u64))?
+        .map_err(PostgresDatabase::query_error)
+}
+
+/// Execute a script of several statements on a client with a timeout
+async fn batch_on(client: &Client, script: &str, timeout: Duration) -> Result<()> {
+    tokio::time::timeout(timeout, client.batch_execute(script))
+        .await
+        .map_err(|_| DatabaseError::query_timeout(timeout.as_millis() as u64))?
+        .map_err(PostgresDatabase::query_error)
+}
+
+/// Run a transaction control statement on a client with a timeout
+async fn control_on(client: &Client, statement: &str, timeout: Duration) -> Result<()> {
+    tokio::time::timeout(timeout, client.batch_execute(statement))
+        .await
+        .map_err(|_| DatabaseError::query_timeout(timeout.as_millis() as u64))?
+        .map_err(PostgresDatabase::transaction_error)
+}
+
+/// Pooled PostgreSQL database implementation
+///
+/// Every operation borrows a connection from the pool for its duration, so
+/// independent queries from different tasks run in parallel on separate connections.
+///
+/// Transactions must stay on a single connection; use [`PooledPostgresTransaction`]
+/// for them; the `begin_transaction`/`commit`/`rollback` methods of the `Database`
+/// trait return `UnsupportedOperation` on this backend.
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_database_system::backends::PooledPostgresDatabase;
+/// use rust_database_system::core::database::Database;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let db = PooledPostgresDatabase::new("host=localhost user=postgres").await?;
+///
+///     db.execute("CREATE TABLE users (id SERIAL PRIMARY KEY, name TEXT)").await?;
+///
+///     Ok(())
+/// }
+/// ```
+pub struct PooledPostgresDatabase {
+    pool: Pool,
+    operation_timeout: Duration,
+}
+
+impl PooledPostgresDatabase {
+    /// Create a new pooled PostgreSQL database instance with default configuration
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the connection string is invalid or no connection can be established
+    pub async fn new(connection_string: impl Into<String>) -> Result<Self> {
+        let config = PoolConfig::new(connection_string);
+        Self::with_config(config).await
+    }
+
+    /// Create a new pooled PostgreSQL database instance with custom configuration
+    ///
+    /// `max_size` bounds the number of open connections and `timeout` bounds both
+    /// connection creation and the wait for a free connection.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the connection string is invalid or no connection can be established
+    pub async fn with_config(config: PoolConfig) -> Result<Self> {
+        let pg_config: tokio_postgres::Config =
+            config
+                .connection_string
+                .parse()
+                .map_err(|e: tokio_postgres::Error| {
+                    DatabaseError::InvalidConnectionString(e.to_string())
+                })?;
+
+        let manager = Manager::from_config(
+            pg_config,
+            NoTls,
+            ManagerConfig {
+                recycling_method: RecyclingMethod::Fast,
+            },
+        );
+
+        let pool = Pool::builder(manager)
+            .max_size(config.max_size)
+            .wait_timeout(Some(config.timeout))
+            .create_timeout(Some(config.timeout))
+            .runtime(Runtime::Tokio1)
+            .build()
+            .map_err(|e| DatabaseError::connection(format!("Failed to create pool: {}", e)))?;
+
+        let db = Self {
+            pool,
+            operation_timeout: config.operation_timeout,
+        };
+
+        // Validate the configuration by opening the first connection eagerly
+        let _ = db.acquire().await?;
+
+        Ok(db)
+    }
+
+    /// Acquire a connection from the pool
+    async fn acquire(&self) -> Result<Object> {
+        self.pool.get().await.map_err(|e| match e {
+            PoolError::Timeout(_) => {
+                let status = self.pool.status();
+                DatabaseError::pool_exhausted(status.size, status.max_size)
+            }
+            e => DatabaseError::connection(format!("Failed to acquire connection: {}", e)),
+        })
+    }
+
+    /// Get pool statistics
+    pub fn stats(&self) -> PoolStats {
+        let status = self.pool.status();
+        PoolStats {
+            size: status.size,
+            available: status.available,
+            waiting: status.waiting,
+        }
+    }
+}
+
+#[async_trait]
+impl Database for PooledPostgresDatabase {
+    fn database_type(&self) -> DatabaseType {
+        DatabaseType::Postgres
+    }
+
+    async fn connect(&self, _connection_string: &str) -> Result<()> {
+        // Connection pool is already initialized in new()
+        // This method is a no-op but validates pool health
+        let _ = self.acquire().await?;
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        !self.pool.is_closed() && self.pool.status().size > 0
+    }
+
+    async fn disconnect(&self) -> Result<()> {
+        self.pool.close();
+        Ok(())
+    }
+
+    async fn execute(&self, query: &str) -> Result<u64> {
+        let conn = self.acquire().await?;
+        execute_on(&conn, query, &[], self.operation_timeout).await
+    }
+
+    async fn execute_batch(&self, script: &str) -> Result<()> {
+        let conn = self.acquire().await?;
+        batch_on(&conn, script, self.operation_timeout).await
+    }
+
+    async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, &[])
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    async fn query_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, params)
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.query_rows_with_params(query, &[]).await
+    }
+
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        let conn = self.acquire().await?;
+        PostgresDatabase::query_result_set(&conn, query, params, self.operation_timeout).await
+    }
+
+    async fn query_stream_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<RowStream> {
+        let conn = self.acquire().await?;
+        let rows = PostgresDatabase::query_row_stream(&conn, query, params, self.operation_timeout)
+            .await?;
+
+        // The pooled connection is returned once the stream is finished or dropped
+        Ok(PostgresDatabase::hold_connection(rows, conn))
+    }
+
+    async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        let conn = self.acquire().await?;
+        execute_on(&conn, query, params, self.operation_timeout).await
+    }
+
+    async fn begin_with_options<'a>(
+        &'a self,
+        options: TransactionOptions,
+    ) -> Result<Box<dyn Transaction + 'a>> {
+        Ok(Box::new(
+            PooledPostgresTransaction::begin_with_options(self, options).await?,
+        ))
+    }
+
+    async fn begin_owned(
+        &self,
+        options: TransactionOptions,
+    ) -> Result<Option<Box<dyn Transaction>>> {
+        Ok(Some(Box::new(
+            PooledPostgresTransaction::begin_with_options(self, options).await?,
+        )))
+    }
+
+    async fn begin_transaction(&self) -> Result<()> {
+        Err(DatabaseError::unsupported(
+            "begin_transaction() cannot pin a pooled connection; use begin() or bound_transaction()",
+        ))
+    }
+
+    async fn commit(&self) -> Result<()> {
+        Err(DatabaseError::unsupported(
+            "commit() cannot pin a pooled connection; use PooledPostgresTransaction::commit()",
+        ))
+    }
+
+    async fn rollback(&self) -> Result<()> {
+        Err(DatabaseError::unsupported(
+            "rollback() cannot pin a pooled connection; use PooledPostgresTransaction::rollback()",
+        ))
+    }
+
+    /// Always returns false: transaction state lives in [`PooledPostgresTransaction`].
+    fn in_transaction(&self) -> bool {
+        false
+    }
+}
+
+/// Transaction guard for pooled PostgreSQL connections
+///
+/// Holds one connection from the pool for its whole lifetime so every statement
+/// runs inside the same server-side transaction.
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_database_system::backends::{PooledPostgresDatabase, PooledPostgresTransaction};
+///
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// let db = PooledPostgresDatabase::new("host=localhost user=postgres").await?;
+///
+/// let tx = PooledPostgresTransaction::begin(&db).await?;
+/// tx.execute("UPDATE accounts SET balance = balance - 100 WHERE id = 1").await?;
+/// tx.execute("UPDATE accounts SET balance = balance + 100 WHERE id = 2").await?;
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Automatic Rollback
+///
+/// If the transaction is dropped without being committed or rolled back, its
+/// connection is detached from the pool and closed, which makes the server roll
+/// the transaction back. The connection is never handed to another task while
+/// the transaction is still open.
+///
+/// # Savepoints
+///
+/// [`savepoint()`](Self::savepoint) opens a nested transaction as a child guard;
+/// rolling it back discards only the work done since it was opened:
+///
+/// ```no_run
+/// # use rust_database_system::backends::{PooledPostgresDatabase, PooledPostgresTransaction};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let db = PooledPostgresDatabase::new("host=localhost user=postgres").await?;
+/// let mut tx = PooledPostgresTransaction::begin(&db).await?;
+/// tx.execute("INSERT INTO orders (id) VALUES (1)").await?;
+///
+/// let savepoint = tx.savepoint().await?;
+/// if savepoint.execute("INSERT INTO audit (order_id) VALUES (1)").await.is_err() {
+///     // Recovers the transaction from the failed statement
+///     savepoint.rollback().await?;
+/// } else {
+///     savepoint.commit().await?;
+/// }
+///
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PooledPostgresTransaction {
+    connection: Option<Object>,
+    committed: AtomicBool,
+    rolled_back: AtomicBool,
+    operation_timeout: Duration,
+    /// Outermost savepoint level dropped without commit or rollback; it is rolled
+    /// back before the next statement runs
+    abandoned_savepoint: std::sync::Mutex<Option<usize>>,
+    callbacks: TransactionCallbacks,
+    drop_hook: Option<DropHook>,
+}
+
+impl PooledPostgresTransaction {
+    /// Begin a new transaction
+    ///
+    /// Acquires a connection from the pool and begins a transaction on it.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - Pool cannot provide a connection
+    /// - BEGIN fails
+    pub async fn begin(db: &PooledPostgresDatabase) -> Result<Self> {
+        Self::begin_with_options(db, TransactionOptions::default()).await
+    }
+
+    /// Begin a new transaction with an isolation level, access mode or DEFERRABLE
+    ///
+    /// # Errors
+    ///
+    /// Returns error if [`begin()`](Self::begin) would, or `UnsupportedOperation`
+    /// if PostgreSQL cannot honour the options
+    pub async fn begin_with_options(
+        db: &PooledPostgresDatabase,
+        options: TransactionOptions,
+    ) -> Result<Self> {
+        let begin = options.begin_statements(Dialect::Postgres)?.join("; ");
+        let conn = db.acquire().await?;
+        let operation_timeout = db.operation_timeout;
+
+        control_on(&conn, &begin, operation_timeout).await?;
+
+        Ok(Self {
+            connection: Some(conn),
+            committed: AtomicBool::new(false),
+            rolled_back: AtomicBool::new(false),
+            operation_timeout,
+            abandoned_savepoint: std::sync::Mutex::new(None),
+            callbacks: TransactionCallbacks::default(),
+            drop_hook: None,
+        })
+    }
+
+    /// Begin a nested transaction backed by a savepoint
+    ///
+    /// The returned guard borrows this transaction, so it must be committed or
+    /// rolled back before the transaction can be finished. Dropping it without
+    /// either rolls the savepoint back before the next statement.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the transaction is finalized or SAVEPOINT fails
+    pub async fn savepoint(&mut self) -> Result<PooledPostgresSavepoint<'_>> {
+        PooledPostgresSavepoint::begin(self, 1).await
+    }
+
+    /// Get the transaction's dedicated connection, first rolling back any
+    /// abandoned savepoint
+    async fn connection(&self) -> Result<&Object> {
+        let conn = self.connection.as_ref().ok_or_else(|| {
+            DatabaseError::transaction("Transaction already finalized".to_string())
+        })?;
+
+        let abandoned = self
+            .abandoned_savepoint
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(level) = abandoned {
+            let statements = rollback_to_savepoint_sql(level).join("; ");
+            control_on(conn, &statements, self.operation_timeout).await?;
+        }
+
+        Ok(conn)
+    }
+
+    /// Execute a query that doesn't return results
+    pub async fn execute(&self, query: &str) -> Result<u64> {
+        execute_on(self.connection().await?, query, &[], self.operation_timeout).await
+    }
+
+    /// Execute a script of several statements separated by semicolons
+    pub async fn execute_batch(&self, script: &str) -> Result<()> {
+        batch_on(self.connection().await?, script, self.operation_timeout).await
+    }
+
+    /// Execute a parameterized query that doesn't return results
+    pub async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        execute_on(
+            self.connection().await?,
+            query,
+            params,
+            self.operation_timeout,
+        )
+        .await
+    }
+
+    /// Execute a SELECT query and return results
+    pub async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, &[])
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    /// Execute a parameterized SELECT query
+    pub async fn query_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, params)
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    /// Execute a SELECT query and return ordered rows with column metadata
+    pub async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.query_rows_with_params(query, &[]).await
+    }
+
+    /// Execute a parameterized SELECT query and return ordered rows with column metadata
+    pub async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        PostgresDatabase::query_result_set(
+            self.connection().await?,
+            query,
+            params,
+            self.operation_timeout,
+        )
+        .await
+    }
+
+    /// Commit the transaction
+    ///
+    /// Commits the transaction and returns the connection to the pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - Transaction already committed or rolled back
+    /// - COMMIT statement fails
+    pub async fn commit(mut self) -> Result<()> {
+        self.finish("COMMIT").await?;
+        self.committed.store(true, Ordering::Release);
+        self.callbacks.committed();
+        Ok(())
+    }
+
+    /// Rollback the transaction
+    ///
+    /// Rolls back the transaction and returns the connection to the pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if:
+    /// - Transaction already committed or rolled back
+    /// - ROLLBACK statement fails
+    pub async fn rollback(mut self) -> Result<()> {
+        self.finish("ROLLBACK").await?;
+        self.rolled_back.store(true, Ordering::Release);
+        self.callbacks.rolled_back();
+        Ok(())
+    }
+
+    /// Run `callback` once the transaction has committed
+    ///
+    /// Callbacks run in registration order after COMMIT succeeds and are
+    /// discarded if the transaction rolls back.
+    pub fn on_commit<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.callbacks.on_commit(Box::new(callback));
+    }
+
+    /// Run `callback` once the transaction has rolled back
+    ///
+    /// Callbacks run in registration order after ROLLBACK, when COMMIT fails, or
+    /// when the transaction is dropped without commit or rollback.
+    pub fn on_rollback<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.callbacks.on_rollback(Box::new(callback));
+    }
+
+    /// Call `hook` with a [`DropReport`] if the transaction, or one of its
+    /// savepoints, is dropped without commit or rollback
+    #[must_use]
+    pub fn with_drop_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&DropReport) + Send + Sync + 'static,
+    {
+        self.drop_hook = Some(std::sync::Arc::new(hook));
+        self
+    }
+
+    /// Run the final statement and release the connection back to the pool
+    async fn finish(&mut self, statement: &str) -> Result<()> {
+        if self.committed.load(Ordering::Acquire) {
+            return Err(DatabaseError::transaction(
+                "Transaction already committed".to_string(),
+            ));
+        }
+        if self.rolled_back.load(Ordering::Acquire) {
+            return Err(DatabaseError::transaction(
+                "Transaction already rolled back".to_string(),
+            ));
+        }
+
+        // Discard any abandoned savepoint before finishing the transaction
+        self.connection().await?;
+
+        let conn = self.connection.take().ok_or_else(|| {
+            DatabaseError::transaction("Transaction connection missing".to_string())
+        })?;
+
+        match control_on(&conn, statement, self.operation_timeout).await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // The transaction state is unknown; never return this connection to the pool.
+                // Closing it makes the server roll the transaction back.
+                drop(Object::take(conn));
+                self.callbacks.rolled_back();
+                Err(e)
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl Transaction for PooledPostgresTransaction {
+    async fn execute(&self, query: &str) -> Result<u64> {
+        PooledPostgresTransaction::execute(self, query).await
+    }
+
+    async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        PooledPostgresTransaction::execute_with_params(self, query, params).await
+    }
+
+    async fn execute_batch(&self, script: &str) -> Result<()> {
+        PooledPostgresTransaction::execute_batch(self, script).await
+    }
+
+    async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        PooledPostgresTransaction::query_rows(self, query).await
+    }
+
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        PooledPostgresTransaction::query_rows_with_params(self, query, params).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<()> {
+        PooledPostgresTransaction::commit(*self).await
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<()> {
+        PooledPostgresTransaction::rollback(*self).await
+    }
+}
+
+impl Drop for PooledPostgresTransaction {
+    fn drop(&mut self) {
+        if let Some(conn) = self.connection.take() {
+            // Detach the connection so an open transaction never leaks back into the pool
+            drop(Object::take(conn));
+            self.callbacks.rolled_back();
+            if let Some(hook) = &self.drop_hook {
+                hook(&DropReport::ConnectionDiscarded { depth: 1 });
+            }
+        }
+    }
+}
+
+/// Nested transaction inside a [`PooledPostgresTransaction`], backed by a savepoint
+///
+/// Created by [`PooledPostgresTransaction::savepoint`] or
+/// [`PooledPostgresSavepoint::savepoint`]. Statements run on the transaction's
+/// connection.
+pub struct PooledPostgresSavepoint<'a> {
+    transaction: &'a PooledPostgresTransaction,
+    level: usize,
+    finished: bool,
+}
+
+impl<'a> PooledPostgresSavepoint<'a> {
+    /// Open the savepoint for nesting level `level`
+    async fn begin(transaction: &'a PooledPostgresTransaction, level: usize) -> Result<Self> {
+        let conn = transaction.connection().await?;
+        control_on(conn, &savepoint_sql(level), transaction.operation_timeout).await?;
+
+        Ok(Self {
+            transaction,
+            level,
+            finished: false,
+        })
+    }
+
+    /// Nesting level of this savepoint (1 for a savepoint directly in the transaction)
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Begin a savepoint nested inside this one
+    pub async fn savepoint(&mut self) -> Result<PooledPostgresSavepoint<'_>> {
+        PooledPostgresSavepoint::begin(self.transaction, self.level + 1).await
+    }
+
+    /// Execute a query that doesn't return results
+    pub async fn execute(&self, query: &str) -> Result<u64> {
+        self.transaction.execute(query).await
+    }
+
+    /// Execute a parameterized query that doesn't return results
+    pub async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        self.transaction.execute_with_params(query, params).await
+    }
+
+    /// Execute a script of several statements separated by semicolons
+    pub async fn execute_batch(&self, script: &str) -> Result<()> {
+        self.transaction.execute_batch(script).await
+    }
+
+    /// Execute a SELECT query and return results
+    pub async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        self.transaction.query(query).await
+    }
+
+    /// Execute a parameterized SELECT query
+    pub async fn query_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<DatabaseResult> {
+        self.transaction.query_with_params(query, params).await
+    }
+
+    /// Execute a SELECT query and return ordered rows with column metadata
+    pub async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.transaction.query_rows(query).await
+    }
+
+    /// Execute a parameterized SELECT query and return ordered rows with column metadata
+    pub async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        self.transaction.query_rows_with_params(query, params).await
+    }
+
+    /// Release the savepoint, keeping its work in the enclosing transaction
+    pub async fn commit(mut self) -> Result<()> {
+        self.finish(&release_savepoint_sql(self.level)).await
+    }
+
+    /// Roll back the work done since the savepoint was opened
+    ///
+    /// This also recovers the transaction after a failed statement.
+    pub async fn rollback(mut self) -> Result<()> {
+        self.finish(&rollback_to_savepoint_sql(self.level).join("; "))
+            .await
+    }
+
+    /// Run the final statement for this savepoint
+    ///
+    /// If it fails, the savepoint counts as abandoned and is rolled back later.
+    async fn finish(&mut self, statements: &str) -> Result<()> {
+        let conn = self.transaction.connection().await?;
+        control_on(conn, statements, self.transaction.operation_timeout).await?;
+        self.finished = true;
+        Ok(())
+    }
+}
+
+impl Drop for PooledPostgresSavepoint<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let mut abandoned = self
+                .transaction
+                .abandoned_savepoint
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            *abandoned = Some(abandoned.map_or(self.level, |level| level.min(self.level)));
+            drop(abandoned);
+            // The savepoint is rolled back before the next statement of its transaction
+            if let Some(hook) = &self.transaction.drop_hook {
+                hook(&DropReport::RollbackDeferred {
+                    depth: self.level + 1,
+                });
+            }
+        }
+    }
+}
+
+#[cfg(all(test, feature = "postgres"))]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    fn get_postgres_url() -> Option<String> {
+        std::env::var("POSTGRES_URL").ok()
+    }
+
+    #[tokio::test]
+    async fn test_invalid_connection_string() {
+        let result = PooledPostgresDatabase::new("port=not-a-number").await;
+        assert!(matches!(
+            result,
+            Err(DatabaseError::InvalidConnectionString(_))
+        ));
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features postgres -- --ignored
+    async fn test_pooled_postgres_concurrent_queries() -> Result<()> {
+        let url = match get_postgres_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: POSTGRES_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = Arc::new(
+            PooledPostgresDatabase::with_config(PoolConfig::new(url).with_max_size(4)).await?,
+        );
+
+        let _ = db
+            .execute("DROP TABLE IF EXISTS test_pooled_concurrent")
+            .await;
+        db.execute("CREATE TABLE test_pooled_concurrent (id SERIAL PRIMARY KEY, name TEXT)")
+            .await?;
+
+        let mut handles = vec![];
+        for i in 0..10 {
+            let db_clone = Arc::clone(&db);
+            handles.push(tokio::spawn(async move {
+                // pg_sleep forces overlapping statements onto separate connections
+                db_clone
+                    .query("SELECT 1 AS one FROM pg_sleep(0.05)")
+                    .await?;
+                db_clone
+                    .execute_with_params(
+                        "INSERT INTO test_pooled_concurrent (name) VALUES ($1)",
+                        &[DatabaseValue::String(format!("User{}", i))],
+                    )
+                    .await
+            }));
+        }
+
+        for handle in handles {
+            handle.await.unwrap()?;
+        }
+
+        let stats = db.stats();
+        assert!(stats.size > 1 && stats.size <= 4);
+
+        let results = db
+            .query("SELECT COUNT(*) AS count FROM test_pooled_concurrent")
+            .await?;
+        assert_eq!(results[0].get("count"), Some(&DatabaseValue::Long(10)));
+
+        db.execute("DROP TABLE test_pooled_concurrent").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features postgres -- --ignored
+    async fn test_pooled_postgres_transaction() -> Result<()> {
+        let url = match get_postgres_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: POSTGRES_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = PooledPostgresDatabase::new(url).await?;
+
+        let _ = db.execute("DROP TABLE IF EXISTS test_pooled_tx").await;
+        db.execute("CREATE TABLE test_pooled_tx (id SERIAL PRIMARY KEY, name TEXT)")
+            .await?;
+
+        // Committed rows are visible from other pooled connections
+        let tx = PooledPostgresTransaction::begin(&db).await?;
+        tx.execute("INSERT INTO test_pooled_tx (name) VALUES ('Alice')")
+            .await?;
+        tx.commit().await?;
+
+        // Rolled back rows are not
+        let tx = PooledPostgresTransaction::begin(&db).await?;
+        tx.execute("INSERT INTO test_pooled_tx (name) VALUES ('Bob')")
+            .await?;
+        tx.rollback().await?;
+
+        // Dropped transactions roll back and do not leak into the pool
+        {
+            let tx = PooledPostgresTransaction::begin(&db).await?;
+            tx.execute("INSERT INTO test_pooled_tx (name) VALUES ('Carol')")
+                .await?;
+        }
+
+        // Savepoints discard only their own work, including after a failed statement
+        let mut tx = PooledPostgresTransaction::begin(&db).await?;
+        tx.execute("INSERT INTO test_pooled_tx (name) VALUES ('Dave')")
+            .await?;
+        {
+            let mut savepoint = tx.savepoint().await?;
+            savepoint
+                .execute("INSERT INTO test_pooled_tx (name) VALUES ('Erin')")
+                .await?;
+            let inner = savepoint.savepoint().await?;
+            assert_eq!(inner.level(), 2);
+            assert!(inner
+                .execute("INSERT INTO missing_table VALUES (1)")
+                .await
+                .is_err());
+            inner.rollback().await?;
+            savepoint.commit().await?;
+        }
+        {
+            let savepoint = tx.savepoint().await?;
+            savepoint
+                .execute("INSERT INTO test_pooled_tx (name) VALUES ('Frank')")
+                .await?;
+            // Dropped savepoints are rolled back before the next statement
+        }
+        tx.commit().await?;
+
+        let results = db
+            .query("SELECT name FROM test_pooled_tx ORDER BY id")
+            .await?;
+        let names: Vec<String> = results
+            .iter()
+            .map(|row| row.get("name").unwrap().as_string())
+            .collect();
+        assert_eq!(names, vec!["Alice", "Dave", "Erin"]);
+        db.execute("DELETE FROM test_pooled_tx WHERE name <> 'Alice'")
+            .await?;
+
+        let results = db.query("SELECT name FROM test_pooled_tx").await?;
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].get("name").map(|v| v.as_string()),
+            Some("Alice".into())
+        );
+
+        assert!(matches!(
+            db.begin_transaction().await,
+            Err(DatabaseError::UnsupportedOperation(_))
+        ));
+
+        db.execute("DROP TABLE test_pooled_tx").await?;
+        Ok(())
+    }
+}
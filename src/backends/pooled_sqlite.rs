@@ -5,74 +5,28 @@
 
 #[cfg(feature = "sqlite")]
 use crate::core::{
-    database::Database, database_types::DatabaseType, error::DatabaseError, error::Result,
-    value::DatabaseResult, value::DatabaseRow, value::DatabaseValue,
+    database::Database, database_types::DatabaseType, dialect::Dialect, error::DatabaseError,
+    error::Result, result_set::row_channel, result_set::ResultSet, result_set::RowStream,
+    transaction::release_savepoint_sql, transaction::rollback_to_savepoint_sql,
+    transaction::savepoint_sql, transaction::DropHook, transaction::DropReport,
+    transaction::Transaction, transaction::TransactionCallbacks, transaction::TransactionOptions,
+    value::DatabaseResult, value::DatabaseValue,
 };
 use async_trait::async_trait;
 use std::time::Duration;
 
+pub use super::pool::{PoolConfig, PoolStats};
+
 #[cfg(feature = "sqlite")]
 use deadpool_sqlite::{Config, Pool, Runtime};
 #[cfg(feature = "sqlite")]
-use rusqlite::{params_from_iter, Row};
+use rusqlite::params_from_iter;
+
+#[cfg(feature = "sqlite")]
+use super::sqlite::SqliteDatabase;
 #[cfg(feature = "sqlite")]
 use std::sync::atomic::{AtomicBool, Ordering};
 
-/// Default timeout for database operations (30 seconds)
-const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
-
-/// Pool configuration for SQLite connections
-#[derive(Debug, Clone)]
-pub struct PoolConfig {
-    /// Maximum number of connections in the pool
-    pub max_size: usize,
-    /// Timeout for acquiring a connection from the pool
-    pub timeout: Duration,
-    /// Timeout for database operations (query, execute, etc.)
-    pub operation_timeout: Duration,
-    /// SQLite connection string
-    pub connection_string: String,
-}
-
-impl Default for PoolConfig {
-    fn default() -> Self {
-        Self {
-            max_size: 16,
-            timeout: Duration::from_secs(5),
-            operation_timeout: DEFAULT_OPERATION_TIMEOUT,
-            connection_string: String::new(),
-        }
-    }
-}
-
-impl PoolConfig {
-    /// Create a new pool configuration
-    pub fn new(connection_string: impl Into<String>) -> Self {
-        Self {
-            connection_string: connection_string.into(),
-            ..Default::default()
-        }
-    }
-
-    /// Set maximum pool size
-    pub fn with_max_size(mut self, size: usize) -> Self {
-        self.max_size = size;
-        self
-    }
-
-    /// Set connection acquisition timeout
-    pub fn with_timeout(mut self, timeout: Duration) -> Self {
-        self.timeout = timeout;
-        self
-    }
-
-    /// Set database operation timeout (for query, execute, etc.)
-    pub fn with_operation_timeout(mut self, timeout: Duration) -> Self {
-        self.operation_timeout = timeout;
-        self
-    }
-}
-
 /// Pooled SQLite database implementation
 ///
 /// This implementation uses a connection pool to efficiently manage multiple
@@ -149,28 +103,6 @@ impl PooledSqliteDatabase {
         })
     }
 
-    /// Convert a rusqlite Row to a DatabaseRow
-    fn row_to_database_row(row: &Row) -> rusqlite::Result<DatabaseRow> {
-        let mut db_row = DatabaseRow::new();
-        let column_count = row.as_ref().column_count();
-
-        for i in 0..column_count {
-            let column_name = row.as_ref().column_name(i)?.to_string();
-            let value = match row.get_ref(i)? {
-                rusqlite::types::ValueRef::Null => DatabaseValue::Null,
-                rusqlite::types::ValueRef::Integer(v) => DatabaseValue::Long(v),
-                rusqlite::types::ValueRef::Real(v) => DatabaseValue::Double(v),
-                rusqlite::types::ValueRef::Text(v) => {
-                    DatabaseValue::String(String::from_utf8_lossy(v).to_string())
-                }
-                rusqlite::types::ValueRef::Blob(v) => DatabaseValue::Bytes(v.to_vec()),
-            };
-            db_row.insert(column_name, value);
-        }
-
-        Ok(db_row)
-    }
-
     /// Convert DatabaseValue to rusqlite parameter
     fn value_to_param(value: &DatabaseValue) -> Box<dyn rusqlite::ToSql> {
         match value {
@@ -197,17 +129,6 @@ impl PooledSqliteDatabase {
     }
 }
 
-/// Pool statistics
-#[derive(Debug, Clone)]
-pub struct PoolStats {
-    /// Total number of connections in the pool
-    pub size: usize,
-    /// Number of available connections
-    pub available: usize,
-    /// Number of requests waiting for a connection
-    pub waiting: usize,
-}
-
 #[cfg(feature = "sqlite")]
 #[async_trait]
 impl Database for PooledSqliteDatabase {
@@ -258,33 +179,27 @@ impl Database for PooledSqliteDatabase {
         Ok(affected as u64)
     }
 
-    async fn query(&self, query: &str) -> Result<DatabaseResult> {
-        let query = query.to_string();
+    async fn execute_batch(&self, script: &str) -> Result<()> {
+        let script = script.to_string();
 
         let conn = self.pool.get().await.map_err(|e| {
             DatabaseError::connection(format!("Failed to acquire connection: {}", e))
         })?;
 
-        let results = tokio::time::timeout(
+        tokio::time::timeout(
             self.operation_timeout,
-            conn.interact(move |conn| {
-                let mut stmt = conn.prepare(&query)?;
-                let rows = stmt.query_map([], Self::row_to_database_row)?;
-
-                let mut results = Vec::new();
-                for row_result in rows {
-                    results.push(row_result?);
-                }
-
-                Ok::<_, rusqlite::Error>(results)
-            }),
+            conn.interact(move |conn| conn.execute_batch(&script)),
         )
         .await
         .map_err(|_| DatabaseError::query_timeout(self.operation_timeout.as_millis() as u64))?
         .map_err(|e| DatabaseError::other(format!("Interact error: {}", e)))?
-        .map_err(DatabaseError::from)?;
+        .map_err(DatabaseError::from)
+    }
 
-        Ok(results)
+    async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, &[])
+            .await
+            .map(ResultSet::into_database_result)
     }
 
     async fn query_with_params(
@@ -292,6 +207,20 @@ impl Database for PooledSqliteDatabase {
         query: &str,
         params: &[DatabaseValue],
     ) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, params)
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.query_rows_with_params(query, &[]).await
+    }
+
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
         let query = query.to_string();
         let params = params.to_vec();
 
@@ -301,24 +230,7 @@ impl Database for PooledSqliteDatabase {
 
         let results = tokio::time::timeout(
             self.operation_timeout,
-            conn.interact(move |conn| {
-                // Convert DatabaseValue to rusqlite parameters
-                let rusqlite_params: Vec<Box<dyn rusqlite::ToSql>> =
-                    params.iter().map(Self::value_to_param).collect();
-
-                let mut stmt = conn.prepare(&query)?;
-                let rows = stmt.query_map(
-                    params_from_iter(rusqlite_params.iter()),
-                    Self::row_to_database_row,
-                )?;
-
-                let mut results = Vec::new();
-                for row_result in rows {
-                    results.push(row_result?);
-                }
-
-                Ok::<_, rusqlite::Error>(results)
-            }),
+            conn.interact(move |conn| SqliteDatabase::query_result_set(conn, &query, &params)),
         )
         .await
         .map_err(|_| DatabaseError::query_timeout(self.operation_timeout.as_millis() as u64))?
@@ -328,6 +240,34 @@ impl Database for PooledSqliteDatabase {
         Ok(results)
     }
 
+    async fn query_stream_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<RowStream> {
+        let query = query.to_string();
+        let params = params.to_vec();
+
+        let conn = self.pool.get().await.map_err(|e| {
+            DatabaseError::connection(format!("Failed to acquire connection: {}", e))
+        })?;
+
+        let (prepared_tx, prepared_rx) = tokio::sync::oneshot::channel();
+        let (sender, stream) = row_channel();
+
+        // The pooled connection is returned once the stream is finished or dropped
+        tokio::spawn(async move {
+            let _ = conn
+                .interact(move |conn| {
+                    SqliteDatabase::send_rows(conn, &query, &params, prepared_tx, sender)
+                })
+                .await;
+        });
+
+        SqliteDatabase::await_prepared(prepared_rx, self.operation_timeout).await?;
+        Ok(stream)
+    }
+
     async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
         let query = query.to_string();
         let params = params.to_vec();
@@ -357,110 +297,44 @@ impl Database for PooledSqliteDatabase {
         Ok(affected as u64)
     }
 
-    async fn begin_transaction(&self) -> Result<()> {
-        let conn = self.pool.get().await.map_err(|e| {
-            DatabaseError::connection(format!("Failed to acquire connection: {}", e))
-        })?;
+    async fn begin_with_options<'a>(
+        &'a self,
+        options: TransactionOptions,
+    ) -> Result<Box<dyn Transaction + 'a>> {
+        Ok(Box::new(
+            PooledTransaction::begin_with_options(self, options).await?,
+        ))
+    }
 
-        tokio::time::timeout(
-            self.operation_timeout,
-            conn.interact(|conn| {
-                conn.execute("BEGIN TRANSACTION", [])?;
-                Ok::<_, rusqlite::Error>(())
-            }),
-        )
-        .await
-        .map_err(|_| DatabaseError::query_timeout(self.operation_timeout.as_millis() as u64))?
-        .map_err(|e| DatabaseError::other(format!("Interact error: {}", e)))?
-        .map_err(DatabaseError::from)?;
+    async fn begin_owned(
+        &self,
+        options: TransactionOptions,
+    ) -> Result<Option<Box<dyn Transaction>>> {
+        Ok(Some(Box::new(
+            PooledTransaction::begin_with_options(self, options).await?,
+        )))
+    }
 
-        Ok(())
+    async fn begin_transaction(&self) -> Result<()> {
+        Err(DatabaseError::unsupported(
+            "begin_transaction() cannot pin a pooled connection; use begin() or bound_transaction()",
+        ))
     }
 
     async fn commit(&self) -> Result<()> {
-        let conn = self.pool.get().await.map_err(|e| {
-            DatabaseError::connection(format!("Failed to acquire connection: {}", e))
-        })?;
-
-        tokio::time::timeout(
-            self.operation_timeout,
-            conn.interact(|conn| {
-                conn.execute("COMMIT", [])?;
-                Ok::<_, rusqlite::Error>(())
-            }),
-        )
-        .await
-        .map_err(|_| DatabaseError::query_timeout(self.operation_timeout.as_millis() as u64))?
-        .map_err(|e| DatabaseError::other(format!("Interact error: {}", e)))?
-        .map_err(DatabaseError::from)?;
-
-        Ok(())
+        Err(DatabaseError::unsupported(
+            "commit() cannot pin a pooled connection; use PooledTransaction::commit()",
+        ))
     }
 
     async fn rollback(&self) -> Result<()> {
-        let conn = self.pool.get().await.map_err(|e| {
-            DatabaseError::connection(format!("Failed to acquire connection: {}", e))
-        })?;
-
-        tokio::time::timeout(
-            self.operation_timeout,
-            conn.interact(|conn| {
-                conn.execute("ROLLBACK", [])?;
-                Ok::<_, rusqlite::Error>(())
-            }),
-        )
-        .await
-        .map_err(|_| DatabaseError::query_timeout(self.operation_timeout.as_millis() as u64))?
-        .map_err(|e| DatabaseError::other(format!("Interact error: {}", e)))?
-        .map_err(DatabaseError::from)?;
-
-        Ok(())
+        Err(DatabaseError::unsupported(
+            "rollback() cannot pin a pooled connection; use PooledTransaction::rollback()",
+        ))
     }
 
-    /// **WARNING**: This method is unreliable with connection pooling and always returns false.
-    ///
-    /// Use [`PooledTransaction`] instead for safe transaction management.
-    ///
-    /// # Example
-    ///
-    /// ```no_run
-    /// # use rust_database_system::backends::{PooledSqliteDatabase, PooledTransaction};
-    /// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
-    /// # let db = PooledSqliteDatabase::new("test.db").await?;
-    /// let mut tx = PooledTransaction::begin(&db).await?;
-    /// tx.execute("INSERT INTO...").await?;
-    /// tx.commit().await?;
-    /// # Ok(())
-    /// # }
-    /// ```
+    /// Always returns false: transaction state lives in [`PooledTransaction`].
     fn in_transaction(&self) -> bool {
-        // CRITICAL WARNING: This method is unreliable with connection pooling!
-        //
-        // With connection pooling, transaction state is per-connection.
-        // This method always returns false because:
-        // 1. Different pool connections may have different transaction states
-        // 2. The connection used for this check may differ from query connections
-        // 3. Checking actual state would require acquiring a connection from the pool
-        //
-        // RECOMMENDED: Use PooledTransaction for safe transaction management:
-        //
-        //   use rust_database_system::backends::PooledTransaction;
-        //   let db = PooledSqliteDatabase::new("test.db").await?;
-        //   let mut tx = PooledTransaction::begin(&db).await?;
-        //   tx.execute("INSERT INTO...").await?;
-        //   tx.commit().await?;
-        //
-        // PooledTransaction ensures:
-        // - Same connection used for entire transaction
-        // - Automatic rollback on drop if not committed
-        // - No transaction state confusion
-        // - Safe error handling
-
-        eprintln!(
-            "[DATABASE WARNING] in_transaction() is deprecated and unreliable with connection pools. \
-             This method always returns false. Use PooledTransaction for safe transaction management."
-        );
-
         false
     }
 }
@@ -508,12 +382,38 @@ impl Database for PooledSqliteDatabase {
 /// # Ok(())
 /// # }
 /// ```
+///
+/// # Savepoints
+///
+/// [`savepoint()`](Self::savepoint) opens a nested transaction as a child guard
+/// that borrows the transaction until it is committed or rolled back:
+///
+/// ```no_run
+/// # use rust_database_system::backends::{PooledSqliteDatabase, PooledTransaction};
+/// # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+/// # let db = PooledSqliteDatabase::new("test.db").await?;
+/// let mut tx = PooledTransaction::begin(&db).await?;
+/// tx.execute("INSERT INTO orders (id) VALUES (1)").await?;
+///
+/// let savepoint = tx.savepoint().await?;
+/// savepoint.execute("INSERT INTO audit (order_id) VALUES (1)").await?;
+/// savepoint.rollback().await?; // only the audit row is discarded
+///
+/// tx.commit().await?;
+/// # Ok(())
+/// # }
+/// ```
 #[cfg(feature = "sqlite")]
 pub struct PooledTransaction {
     connection: Option<deadpool_sqlite::Object>,
     committed: AtomicBool,
     rolled_back: AtomicBool,
     operation_timeout: Duration,
+    /// Outermost savepoint level dropped without commit or rollback; it is rolled
+    /// back before the next statement runs
+    abandoned_savepoint: std::sync::Mutex<Option<usize>>,
+    callbacks: TransactionCallbacks,
+    drop_hook: Option<DropHook>,
 }
 
 #[cfg(feature = "sqlite")]
@@ -529,6 +429,24 @@ impl PooledTransaction {
     /// - Pool cannot provide a connection
     /// - BEGIN TRANSACTION fails
     pub async fn begin(db: &PooledSqliteDatabase) -> Result<Self> {
+        Self::begin_with_options(db, TransactionOptions::default()).await
+    }
+
+    /// Begin a new transaction with a locking mode or other options
+    ///
+    /// `BEGIN IMMEDIATE` is useful on a pool: the write lock is taken up front, so
+    /// concurrent writers wait for it instead of failing mid-transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if [`begin()`](Self::begin) would, or `UnsupportedOperation`
+    /// if SQLite cannot honour the options
+    pub async fn begin_with_options(
+        db: &PooledSqliteDatabase,
+        options: TransactionOptions,
+    ) -> Result<Self> {
+        let begin = options.begin_statements(Dialect::Sqlite)?.join("; ");
+
         let conn = db.pool.get().await.map_err(|e| {
             DatabaseError::connection(format!(
                 "Failed to acquire connection for transaction: {}",
@@ -539,34 +457,77 @@ impl PooledTransaction {
         let operation_timeout = db.operation_timeout;
 
         // Begin transaction on the acquired connection
-        tokio::time::timeout(
-            operation_timeout,
-            conn.interact(|conn| {
-                conn.execute("BEGIN TRANSACTION", [])?;
-                Ok::<_, rusqlite::Error>(())
-            }),
-        )
-        .await
-        .map_err(|_| DatabaseError::query_timeout(operation_timeout.as_millis() as u64))?
-        .map_err(|e| DatabaseError::other(format!("Interact error: {}", e)))?
-        .map_err(DatabaseError::from)?;
+        Self::run_control(&conn, begin, operation_timeout).await?;
 
         Ok(Self {
             connection: Some(conn),
             committed: AtomicBool::new(false),
             rolled_back: AtomicBool::new(false),
             operation_timeout,
+            abandoned_savepoint: std::sync::Mutex::new(None),
+            callbacks: TransactionCallbacks::default(),
+            drop_hook: None,
         })
     }
 
-    /// Execute a query that doesn't return results
+    /// Begin a nested transaction backed by a savepoint
     ///
-    /// Uses the transaction's dedicated connection.
-    pub async fn execute(&self, query: &str) -> Result<u64> {
+    /// The returned guard borrows this transaction, so it must be committed or
+    /// rolled back before the transaction can be finished. Dropping it without
+    /// either rolls the savepoint back before the next statement.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the transaction is finalized or SAVEPOINT fails
+    pub async fn savepoint(&mut self) -> Result<PooledSavepoint<'_>> {
+        PooledSavepoint::begin(self, 1).await
+    }
+
+    /// Get the transaction's connection, first rolling back any abandoned savepoint
+    async fn connection(&self) -> Result<&deadpool_sqlite::Object> {
         let conn = self.connection.as_ref().ok_or_else(|| {
             DatabaseError::transaction("Transaction already finalized".to_string())
         })?;
 
+        let abandoned = self
+            .abandoned_savepoint
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .take();
+        if let Some(level) = abandoned {
+            Self::run_control(
+                conn,
+                rollback_to_savepoint_sql(level).join("; "),
+                self.operation_timeout,
+            )
+            .await?;
+        }
+
+        Ok(conn)
+    }
+
+    /// Run transaction control statements on a connection with a timeout
+    async fn run_control(
+        conn: &deadpool_sqlite::Object,
+        statements: String,
+        timeout: Duration,
+    ) -> Result<()> {
+        tokio::time::timeout(
+            timeout,
+            conn.interact(move |conn| conn.execute_batch(&statements)),
+        )
+        .await
+        .map_err(|_| DatabaseError::query_timeout(timeout.as_millis() as u64))?
+        .map_err(|e| DatabaseError::other(format!("Interact error: {}", e)))?
+        .map_err(DatabaseError::from)
+    }
+
+    /// Execute a query that doesn't return results
+    ///
+    /// Uses the transaction's dedicated connection.
+    pub async fn execute(&self, query: &str) -> Result<u64> {
+        let conn = self.connection().await?;
+
         let query = query.to_string();
 
         let affected = tokio::time::timeout(
@@ -584,11 +545,15 @@ impl PooledTransaction {
         Ok(affected as u64)
     }
 
+    /// Execute a script of several statements separated by semicolons
+    pub async fn execute_batch(&self, script: &str) -> Result<()> {
+        let conn = self.connection().await?;
+        Self::run_control(conn, script.to_string(), self.operation_timeout).await
+    }
+
     /// Execute a parameterized query that doesn't return results
     pub async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
-        let conn = self.connection.as_ref().ok_or_else(|| {
-            DatabaseError::transaction("Transaction already finalized".to_string())
-        })?;
+        let conn = self.connection().await?;
 
         let query = query.to_string();
         let params = params.to_vec();
@@ -617,32 +582,9 @@ impl PooledTransaction {
 
     /// Execute a SELECT query and return results
     pub async fn query(&self, query: &str) -> Result<DatabaseResult> {
-        let conn = self.connection.as_ref().ok_or_else(|| {
-            DatabaseError::transaction("Transaction already finalized".to_string())
-        })?;
-
-        let query = query.to_string();
-
-        let results = tokio::time::timeout(
-            self.operation_timeout,
-            conn.interact(move |conn| {
-                let mut stmt = conn.prepare(&query)?;
-                let rows = stmt.query_map([], PooledSqliteDatabase::row_to_database_row)?;
-
-                let mut results = Vec::new();
-                for row_result in rows {
-                    results.push(row_result?);
-                }
-
-                Ok::<_, rusqlite::Error>(results)
-            }),
-        )
-        .await
-        .map_err(|_| DatabaseError::query_timeout(self.operation_timeout.as_millis() as u64))?
-        .map_err(|e| DatabaseError::other(format!("Interact error: {}", e)))?
-        .map_err(DatabaseError::from)?;
-
-        Ok(results)
+        self.query_rows_with_params(query, &[])
+            .await
+            .map(ResultSet::into_database_result)
     }
 
     /// Execute a parameterized SELECT query
@@ -651,34 +593,30 @@ impl PooledTransaction {
         query: &str,
         params: &[DatabaseValue],
     ) -> Result<DatabaseResult> {
-        let conn = self.connection.as_ref().ok_or_else(|| {
-            DatabaseError::transaction("Transaction already finalized".to_string())
-        })?;
+        self.query_rows_with_params(query, params)
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    /// Execute a SELECT query and return ordered rows with column metadata
+    pub async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.query_rows_with_params(query, &[]).await
+    }
+
+    /// Execute a parameterized SELECT query and return ordered rows with column metadata
+    pub async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        let conn = self.connection().await?;
 
         let query = query.to_string();
         let params = params.to_vec();
 
         let results = tokio::time::timeout(
             self.operation_timeout,
-            conn.interact(move |conn| {
-                let rusqlite_params: Vec<Box<dyn rusqlite::ToSql>> = params
-                    .iter()
-                    .map(PooledSqliteDatabase::value_to_param)
-                    .collect();
-
-                let mut stmt = conn.prepare(&query)?;
-                let rows = stmt.query_map(
-                    params_from_iter(rusqlite_params.iter()),
-                    PooledSqliteDatabase::row_to_database_row,
-                )?;
-
-                let mut results = Vec::new();
-                for row_result in rows {
-                    results.push(row_result?);
-                }
-
-                Ok::<_, rusqlite::Error>(results)
-            }),
+            conn.interact(move |conn| SqliteDatabase::query_result_set(conn, &query, &params)),
         )
         .await
         .map_err(|_| DatabaseError::query_timeout(self.operation_timeout.as_millis() as u64))?
@@ -699,36 +637,9 @@ impl PooledTransaction {
     /// - Transaction already committed or rolled back
     /// - COMMIT statement fails
     pub async fn commit(mut self) -> Result<()> {
-        if self.committed.load(Ordering::Acquire) {
-            return Err(DatabaseError::transaction(
-                "Transaction already committed".to_string(),
-            ));
-        }
-        if self.rolled_back.load(Ordering::Acquire) {
-            return Err(DatabaseError::transaction(
-                "Transaction already rolled back".to_string(),
-            ));
-        }
-
-        let conn = self.connection.take().ok_or_else(|| {
-            DatabaseError::transaction("Transaction connection missing".to_string())
-        })?;
-
-        tokio::time::timeout(
-            self.operation_timeout,
-            conn.interact(|conn| {
-                conn.execute("COMMIT", [])?;
-                Ok::<_, rusqlite::Error>(())
-            }),
-        )
-        .await
-        .map_err(|_| DatabaseError::query_timeout(self.operation_timeout.as_millis() as u64))?
-        .map_err(|e| DatabaseError::other(format!("Interact error: {}", e)))?
-        .map_err(DatabaseError::from)?;
-
+        self.finish("COMMIT").await?;
         self.committed.store(true, Ordering::Release);
-        // Connection automatically returned to pool when dropped
-
+        self.callbacks.committed();
         Ok(())
     }
 
@@ -743,6 +654,47 @@ impl PooledTransaction {
     /// - Transaction already committed or rolled back
     /// - ROLLBACK statement fails
     pub async fn rollback(mut self) -> Result<()> {
+        self.finish("ROLLBACK").await?;
+        self.rolled_back.store(true, Ordering::Release);
+        self.callbacks.rolled_back();
+        Ok(())
+    }
+
+    /// Run `callback` once the transaction has committed
+    ///
+    /// Callbacks run in registration order after COMMIT succeeds and are
+    /// discarded if the transaction rolls back.
+    pub fn on_commit<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.callbacks.on_commit(Box::new(callback));
+    }
+
+    /// Run `callback` once the transaction has rolled back
+    ///
+    /// Callbacks run in registration order after ROLLBACK, when COMMIT fails, or
+    /// when the transaction is dropped without commit or rollback.
+    pub fn on_rollback<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.callbacks.on_rollback(Box::new(callback));
+    }
+
+    /// Call `hook` with a [`DropReport`] if the transaction, or one of its
+    /// savepoints, is dropped without commit or rollback
+    #[must_use]
+    pub fn with_drop_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&DropReport) + Send + Sync + 'static,
+    {
+        self.drop_hook = Some(std::sync::Arc::new(hook));
+        self
+    }
+
+    /// Run the final statement and release the connection back to the pool
+    async fn finish(&mut self, statement: &'static str) -> Result<()> {
         if self.committed.load(Ordering::Acquire) {
             return Err(DatabaseError::transaction(
                 "Transaction already committed".to_string(),
@@ -754,46 +706,194 @@ impl PooledTransaction {
             ));
         }
 
+        // Discard any abandoned savepoint before finishing the transaction
+        self.connection().await?;
         let conn = self.connection.take().ok_or_else(|| {
             DatabaseError::transaction("Transaction connection missing".to_string())
         })?;
 
-        tokio::time::timeout(
-            self.operation_timeout,
-            conn.interact(|conn| {
-                conn.execute("ROLLBACK", [])?;
-                Ok::<_, rusqlite::Error>(())
-            }),
-        )
-        .await
-        .map_err(|_| DatabaseError::query_timeout(self.operation_timeout.as_millis() as u64))?
-        .map_err(|e| DatabaseError::other(format!("Interact error: {}", e)))?
-        .map_err(DatabaseError::from)?;
+        match Self::run_control(&conn, statement.to_string(), self.operation_timeout).await {
+            Ok(()) => Ok(()),
+            Err(e) => {
+                // The transaction may still be open; never return this connection to the pool.
+                // Closing it makes SQLite roll the transaction back.
+                drop(deadpool_sqlite::Object::take(conn));
+                self.callbacks.rolled_back();
+                Err(e)
+            }
+        }
+    }
+}
 
-        self.rolled_back.store(true, Ordering::Release);
-        // Connection automatically returned to pool when dropped
+#[cfg(feature = "sqlite")]
+#[async_trait]
+impl Transaction for PooledTransaction {
+    async fn execute(&self, query: &str) -> Result<u64> {
+        PooledTransaction::execute(self, query).await
+    }
 
-        Ok(())
+    async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        PooledTransaction::execute_with_params(self, query, params).await
+    }
+
+    async fn execute_batch(&self, script: &str) -> Result<()> {
+        PooledTransaction::execute_batch(self, script).await
+    }
+
+    async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        PooledTransaction::query_rows(self, query).await
+    }
+
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        PooledTransaction::query_rows_with_params(self, query, params).await
+    }
+
+    async fn commit(self: Box<Self>) -> Result<()> {
+        PooledTransaction::commit(*self).await
+    }
+
+    async fn rollback(self: Box<Self>) -> Result<()> {
+        PooledTransaction::rollback(*self).await
     }
 }
 
 #[cfg(feature = "sqlite")]
 impl Drop for PooledTransaction {
     fn drop(&mut self) {
-        // If transaction not explicitly committed or rolled back, warn user
-        if !self.committed.load(Ordering::Acquire)
-            && !self.rolled_back.load(Ordering::Acquire)
-            && self.connection.is_some()
-        {
-            eprintln!(
-                "[DATABASE WARNING] PooledTransaction dropped without commit or rollback. \
-                 The transaction will be automatically rolled back when the connection is returned to the pool. \
-                 Always explicitly call commit() or rollback() to avoid this warning."
-            );
-            // Note: We cannot perform async rollback in Drop.
-            // The connection will be returned to the pool and SQLite will automatically
-            // rollback the transaction when the connection is reused or closed.
-            // This is safe but not ideal - users should explicitly commit or rollback.
+        if let Some(conn) = self.connection.take() {
+            // Detach the connection so an open transaction never leaks back into the pool
+            drop(deadpool_sqlite::Object::take(conn));
+            self.callbacks.rolled_back();
+            if let Some(hook) = &self.drop_hook {
+                hook(&DropReport::ConnectionDiscarded { depth: 1 });
+            }
+        }
+    }
+}
+
+/// Nested transaction inside a [`PooledTransaction`], backed by a savepoint
+///
+/// Created by [`PooledTransaction::savepoint`] or [`PooledSavepoint::savepoint`].
+/// Statements run on the transaction's connection.
+#[cfg(feature = "sqlite")]
+pub struct PooledSavepoint<'a> {
+    transaction: &'a PooledTransaction,
+    level: usize,
+    finished: bool,
+}
+
+#[cfg(feature = "sqlite")]
+impl<'a> PooledSavepoint<'a> {
+    /// Open the savepoint for nesting level `level`
+    async fn begin(transaction: &'a PooledTransaction, level: usize) -> Result<Self> {
+        let conn = transaction.connection().await?;
+        PooledTransaction::run_control(conn, savepoint_sql(level), transaction.operation_timeout)
+            .await?;
+
+        Ok(Self {
+            transaction,
+            level,
+            finished: false,
+        })
+    }
+
+    /// Nesting level of this savepoint (1 for a savepoint directly in the transaction)
+    pub fn level(&self) -> usize {
+        self.level
+    }
+
+    /// Begin a savepoint nested inside this one
+    pub async fn savepoint(&mut self) -> Result<PooledSavepoint<'_>> {
+        PooledSavepoint::begin(self.transaction, self.level + 1).await
+    }
+
+    /// Execute a query that doesn't return results
+    pub async fn execute(&self, query: &str) -> Result<u64> {
+        self.transaction.execute(query).await
+    }
+
+    /// Execute a parameterized query that doesn't return results
+    pub async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        self.transaction.execute_with_params(query, params).await
+    }
+
+    /// Execute a script of several statements separated by semicolons
+    pub async fn execute_batch(&self, script: &str) -> Result<()> {
+        self.transaction.execute_batch(script).await
+    }
+
+    /// Execute a SELECT query and return results
+    pub async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        self.transaction.query(query).await
+    }
+
+    /// Execute a parameterized SELECT query
+    pub async fn query_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<DatabaseResult> {
+        self.transaction.query_with_params(query, params).await
+    }
+
+    /// Execute a SELECT query and return ordered rows with column metadata
+    pub async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.transaction.query_rows(query).await
+    }
+
+    /// Execute a parameterized SELECT query and return ordered rows with column metadata
+    pub async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        self.transaction.query_rows_with_params(query, params).await
+    }
+
+    /// Release the savepoint, keeping its work in the enclosing transaction
+    pub async fn commit(mut self) -> Result<()> {
+        self.finish(release_savepoint_sql(self.level)).await
+    }
+
+    /// Roll back the work done since the savepoint was opened
+    pub async fn rollback(mut self) -> Result<()> {
+        self.finish(rollback_to_savepoint_sql(self.level).join("; "))
+            .await
+    }
+
+    /// Run the final statement for this savepoint
+    ///
+    /// If it fails, the savepoint counts as abandoned and is rolled back later.
+    async fn finish(&mut self, statements: String) -> Result<()> {
+        let conn = self.transaction.connection().await?;
+        PooledTransaction::run_control(conn, statements, self.transaction.operation_timeout)
+            .await?;
+        self.finished = true;
+        Ok(())
+    }
+}
+
+#[cfg(feature = "sqlite")]
+impl Drop for PooledSavepoint<'_> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let mut abandoned = self
+                .transaction
+                .abandoned_savepoint
+                .lock()
+                .unwrap_or_else(|e| e.into_inner());
+            *abandoned = Some(abandoned.map_or(self.level, |level| level.min(self.level)));
+            drop(abandoned);
+            // The savepoint is rolled back before the next statement of its transaction
+            if let Some(hook) = &self.transaction.drop_hook {
+                hook(&DropReport::RollbackDeferred {
+                    depth: self.level + 1,
+                });
+            }
         }
     }
 }
@@ -877,6 +977,293 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_pooled_query_stream() -> Result<()> {
+        use futures::TryStreamExt;
+
+        let db = PooledSqliteDatabase::new(":memory:").await?;
+
+        let rows: Vec<_> = db
+            .query_stream_with_params(
+                "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < ?) \
+                 SELECT x FROM n",
+                &[DatabaseValue::Int(1000)],
+            )
+            .await?
+            .try_collect()
+            .await?;
+        assert_eq!(rows.len(), 1000);
+        assert_eq!(rows[999].try_get::<i64, _>("x")?, 1000);
+
+        assert!(db.query_stream("SELECT * FROM missing").await.is_err());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pooled_transaction_with_options() -> Result<()> {
+        use crate::core::transaction::{AccessMode, LockingMode};
+
+        let db = PooledSqliteDatabase::new(":memory:").await?;
+
+        let options = TransactionOptions::new().with_locking_mode(LockingMode::Immediate);
+        let tx = PooledTransaction::begin_with_options(&db, options).await?;
+        tx.execute("CREATE TABLE test (name TEXT)").await?;
+        tx.commit().await?;
+
+        let read_only = TransactionOptions::new().with_access_mode(AccessMode::ReadOnly);
+        assert!(matches!(
+            PooledTransaction::begin_with_options(&db, read_only).await,
+            Err(DatabaseError::UnsupportedOperation(_))
+        ));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pooled_transaction_savepoints() -> Result<()> {
+        let db = PooledSqliteDatabase::new(":memory:").await?;
+
+        let mut tx = PooledTransaction::begin(&db).await?;
+        tx.execute("CREATE TABLE test (name TEXT)").await?;
+        tx.execute("INSERT INTO test VALUES ('outer')").await?;
+
+        {
+            let mut savepoint = tx.savepoint().await?;
+            savepoint
+                .execute("INSERT INTO test VALUES ('kept')")
+                .await?;
+
+            let inner = savepoint.savepoint().await?;
+            assert_eq!(inner.level(), 2);
+            inner
+                .execute("INSERT INTO test VALUES ('discarded')")
+                .await?;
+            inner.rollback().await?;
+
+            savepoint.commit().await?;
+        }
+        {
+            let savepoint = tx.savepoint().await?;
+            savepoint
+                .execute("INSERT INTO test VALUES ('dropped')")
+                .await?;
+            // Dropped without commit or rollback
+        }
+
+        let results = tx.query("SELECT name FROM test ORDER BY rowid").await?;
+        let names: Vec<String> = results
+            .iter()
+            .map(|row| row.get("name").unwrap().as_string())
+            .collect();
+        assert_eq!(names, vec!["outer", "kept"]);
+
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pooled_transaction_callbacks() -> Result<()> {
+        use std::sync::{Arc, Mutex};
+
+        let db = PooledSqliteDatabase::new(":memory:").await?;
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let record = |name: &'static str| {
+            let events = Arc::clone(&events);
+            move || events.lock().unwrap().push(name)
+        };
+
+        let tx = PooledTransaction::begin(&db).await?;
+        tx.on_commit(record("commit 1"));
+        tx.on_commit(record("commit 2"));
+        tx.on_rollback(record("never"));
+        tx.commit().await?;
+        assert_eq!(*events.lock().unwrap(), vec!["commit 1", "commit 2"]);
+
+        events.lock().unwrap().clear();
+        let tx = PooledTransaction::begin(&db).await?;
+        tx.on_commit(record("never"));
+        tx.on_rollback(record("rollback"));
+        tx.rollback().await?;
+        assert_eq!(*events.lock().unwrap(), vec!["rollback"]);
+
+        events.lock().unwrap().clear();
+        {
+            let tx = PooledTransaction::begin(&db).await?;
+            tx.on_rollback(record("dropped"));
+        }
+        assert_eq!(*events.lock().unwrap(), vec!["dropped"]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pooled_transaction_drop_hook() -> Result<()> {
+        use crate::core::transaction::DropReport;
+        use std::sync::Mutex;
+
+        let db = PooledSqliteDatabase::new(":memory:").await?;
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let record = {
+            let reports = Arc::clone(&reports);
+            move |report: &DropReport| reports.lock().unwrap().push(format!("{:?}", report))
+        };
+
+        {
+            let mut tx = PooledTransaction::begin(&db).await?.with_drop_hook(record);
+            tx.execute("CREATE TABLE test (name TEXT)").await?;
+            {
+                let savepoint = tx.savepoint().await?;
+                savepoint
+                    .execute("INSERT INTO test VALUES ('dropped')")
+                    .await?;
+            }
+            assert!(tx.query("SELECT name FROM test").await?.is_empty());
+        }
+
+        assert_eq!(
+            *reports.lock().unwrap(),
+            vec![
+                format!("{:?}", DropReport::RollbackDeferred { depth: 2 }),
+                format!("{:?}", DropReport::ConnectionDiscarded { depth: 1 }),
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pooled_transaction_with_retry() -> Result<()> {
+        use crate::core::retry::RetryPolicy;
+        use std::sync::atomic::{AtomicU32, Ordering};
+
+        let path = std::env::temp_dir().join(format!("pooled_retry_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let db = PooledSqliteDatabase::new(path.to_str().unwrap()).await?;
+        db.execute("CREATE TABLE test (name TEXT)").await?;
+
+        let policy = RetryPolicy::new()
+            .with_initial_backoff(Duration::from_millis(1))
+            .with_jitter(false);
+        let attempts = AtomicU32::new(0);
+        let outcome = db
+            .transaction_with_retry(&policy, |tx| {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+                Box::pin(async move {
+                    tx.execute("INSERT INTO test (name) VALUES ('row')").await?;
+                    if attempt == 1 {
+                        return Err(DatabaseError::from(rusqlite::Error::SqliteFailure(
+                            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+                            Some("database is locked".to_string()),
+                        )));
+                    }
+                    let rows = tx.query("SELECT name FROM test").await?;
+                    Ok(rows.len())
+                })
+            })
+            .await?;
+
+        // The first attempt was rolled back, so the second saw only its own row
+        assert_eq!(outcome.attempts, 2);
+        assert_eq!(outcome.value, 1);
+        assert_eq!(db.query("SELECT name FROM test").await?.len(), 1);
+
+        let _ = std::fs::remove_file(&path);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pooled_bound_transaction() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("pooled_bound_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let db = PooledSqliteDatabase::new(path.to_str().unwrap()).await?;
+        db.execute("CREATE TABLE test (name TEXT)").await?;
+
+        let seen = db
+            .bound_transaction(|tx| {
+                Box::pin(async move {
+                    tx.execute("INSERT INTO test (name) VALUES ('kept')")
+                        .await?;
+                    Ok(tx.query("SELECT name FROM test").await?.len())
+                })
+            })
+            .await?;
+        assert_eq!(seen, 1);
+
+        let failed: Result<()> = db
+            .bound_transaction(|tx| {
+                Box::pin(async move {
+                    tx.execute("INSERT INTO test (name) VALUES ('dropped')")
+                        .await?;
+                    Err(DatabaseError::QueryError("abort".to_string()))
+                })
+            })
+            .await;
+        assert!(failed.is_err());
+        assert_eq!(db.query("SELECT name FROM test").await?.len(), 1);
+
+        let _ = std::fs::remove_file(&path);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_pooled_transaction_guard() -> Result<()> {
+        use crate::core::transaction::{DropReport, TransactionGuard};
+        use std::sync::Mutex;
+
+        let path = std::env::temp_dir().join(format!("pooled_guard_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let db = Arc::new(PooledSqliteDatabase::new(path.to_str().unwrap()).await?);
+        db.execute("CREATE TABLE test (name TEXT)").await?;
+
+        let tx = TransactionGuard::begin(Arc::clone(&db)).await?;
+        tx.execute("INSERT INTO test VALUES ('outer')").await?;
+        let child = tx.savepoint().await?;
+        child
+            .execute("INSERT INTO test VALUES ('discarded')")
+            .await?;
+        child.rollback().await?;
+        let child = tx.savepoint().await?;
+        child.execute("INSERT INTO test VALUES ('kept')").await?;
+        child.commit().await?;
+        {
+            let savepoint = tx.savepoint().await?;
+            savepoint
+                .execute("INSERT INTO test VALUES ('dropped')")
+                .await?;
+        }
+        // Statements of other connections cannot see the uncommitted rows
+        assert!(db.query("SELECT name FROM test").await?.is_empty());
+        assert_eq!(tx.query("SELECT name FROM test").await?.len(), 2);
+        tx.commit().await?;
+
+        let results = db.query("SELECT name FROM test ORDER BY rowid").await?;
+        let names: Vec<String> = results
+            .iter()
+            .map(|row| row.get("name").unwrap().as_string())
+            .collect();
+        assert_eq!(names, vec!["outer", "kept"]);
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        {
+            let reports = Arc::clone(&reports);
+            let tx = TransactionGuard::begin(Arc::clone(&db))
+                .await?
+                .with_drop_hook(move |report| {
+                    reports.lock().unwrap().push(format!("{:?}", report))
+                });
+            tx.execute("INSERT INTO test VALUES ('rolled back')")
+                .await?;
+        }
+        assert_eq!(
+            *reports.lock().unwrap(),
+            vec![format!(
+                "{:?}",
+                DropReport::ConnectionDiscarded { depth: 1 }
+            )]
+        );
+        assert_eq!(db.query("SELECT name FROM test").await?.len(), 2);
+
+        let _ = std::fs::remove_file(&path);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_pool_stats() -> Result<()> {
         let db = PooledSqliteDatabase::new(":memory:").await?;
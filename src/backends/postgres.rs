@@ -3,14 +3,26 @@
 //! This module provides a PostgreSQL implementation of the Database trait using tokio-postgres.
 
 use crate::core::{
-    database::Database, database_types::DatabaseType, error::DatabaseError, error::Result,
-    value::DatabaseResult, value::DatabaseRow, value::DatabaseValue,
+    database::Database,
+    database_types::DatabaseType,
+    dialect::Dialect,
+    error::DatabaseError,
+    error::Result,
+    result_set::{ColumnInfo, ResultSet, Row as ResultRow, RowStream},
+    transaction::{
+        abandoned_rollback_sql, release_savepoint_sql, rollback_to_savepoint_sql, run_rolled_back,
+        savepoint_sql, AbandonedLevel, CallbackStack, TransactionCallbacks, TransactionOptions,
+    },
+    value::DatabaseResult,
+    value::DatabaseValue,
 };
 use async_trait::async_trait;
+use futures::{Stream, StreamExt};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::Mutex;
-use tokio_postgres::{Client, NoTls, Row};
+use tokio_postgres::error::SqlState;
+use tokio_postgres::{Client, NoTls, Row, Statement};
 
 /// Default timeout for database operations (30 seconds)
 const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
@@ -18,7 +30,12 @@ const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
 /// PostgreSQL database implementation
 pub struct PostgresDatabase {
     client: Arc<Mutex<Option<Client>>>,
-    in_transaction: Arc<Mutex<bool>>,
+    /// Number of open transaction levels; levels above the first are savepoints
+    transaction_depth: Arc<Mutex<usize>>,
+    /// Level left open by a dropped `TransactionGuard`, rolled back before the next operation
+    abandoned: AbandonedLevel,
+    /// `on_commit` / `on_rollback` callbacks of every open transaction level
+    callbacks: CallbackStack,
 }
 
 impl PostgresDatabase {
@@ -26,16 +43,17 @@ impl PostgresDatabase {
     pub fn new() -> Self {
         Self {
             client: Arc::new(Mutex::new(None)),
-            in_transaction: Arc::new(Mutex::new(false)),
+            transaction_depth: Arc::new(Mutex::new(0)),
+            abandoned: AbandonedLevel::default(),
+            callbacks: CallbackStack::default(),
         }
     }
 
-    /// Convert a tokio_postgres Row to a DatabaseRow
-    fn row_to_database_row(row: &Row) -> DatabaseRow {
-        let mut db_row = DatabaseRow::new();
+    /// Convert a tokio_postgres Row to values in column order
+    pub(crate) fn row_values(row: &Row) -> Vec<DatabaseValue> {
+        let mut values = Vec::with_capacity(row.len());
 
         for (idx, column) in row.columns().iter().enumerate() {
-            let column_name = column.name().to_string();
             let value = match column.type_().name() {
                 "bool" => row
                     .get::<_, Option<bool>>(idx)
@@ -76,14 +94,183 @@ impl PostgresDatabase {
                         .unwrap_or(DatabaseValue::Null)
                 }
             };
-            db_row.insert(column_name, value);
+            values.push(value);
         }
 
-        db_row
+        values
+    }
+
+    /// Prepare and run a parameterized query on a client with a timeout
+    ///
+    /// The statement is prepared first so column metadata is available even when
+    /// the query returns no rows.
+    pub(crate) async fn query_result_set(
+        client: &Client,
+        query: &str,
+        params: &[DatabaseValue],
+        timeout: Duration,
+    ) -> Result<ResultSet> {
+        // Convert DatabaseValue to postgres parameters and extract references
+        let postgres_params: Vec<Box<dyn tokio_postgres::types::ToSql + Sync + Send>> =
+            params.iter().map(Self::value_to_param).collect();
+
+        // Create a slice of trait object references
+        let param_refs: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = postgres_params
+            .iter()
+            .map(|p| p.as_ref() as &(dyn tokio_postgres::types::ToSql + Sync))
+            .collect();
+
+        let query_future = async {
+            let statement = client.prepare(query).await?;
+            let rows = client.query(&statement, &param_refs).await?;
+            Ok::<_, tokio_postgres::Error>((statement, rows))
+        };
+
+        let (statement, rows) = tokio::time::timeout(timeout, query_future)
+            .await
+            .map_err(|_| DatabaseError::query_timeout(timeout.as_millis() as u64))?
+            .map_err(Self::query_error)?;
+
+        Ok(ResultSet::new(
+            Self::column_infos(&statement),
+            rows.iter().map(Self::row_values).collect(),
+        ))
+    }
+
+    /// Prepare a parameterized query on a client and stream its rows
+    ///
+    /// Rows are read from the connection as the stream is polled; tokio-postgres
+    /// stops reading from the socket while the stream is not consumed, so the
+    /// server is throttled instead of the result being buffered.
+    pub(crate) async fn query_row_stream(
+        client: &Client,
+        query: &str,
+        params: &[DatabaseValue],
+        timeout: Duration,
+    ) -> Result<impl Stream<Item = Result<ResultRow>> + Send + 'static> {
+        let postgres_params: Vec<Box<dyn tokio_postgres::types::ToSql + Sync + Send>> =
+            params.iter().map(Self::value_to_param).collect();
+
+        let query_future = async {
+            let statement = client.prepare(query).await?;
+            let param_refs = postgres_params
+                .iter()
+                .map(|p| p.as_ref() as &(dyn tokio_postgres::types::ToSql + Sync));
+            let rows = client.query_raw(&statement, param_refs).await?;
+            Ok::<_, tokio_postgres::Error>((statement, rows))
+        };
+
+        let (statement, rows) = tokio::time::timeout(timeout, query_future)
+            .await
+            .map_err(|_| DatabaseError::query_timeout(timeout.as_millis() as u64))?
+            .map_err(Self::query_error)?;
+
+        let columns: Arc<[ColumnInfo]> = Self::column_infos(&statement).into();
+        Ok(rows.map(move |row| {
+            row.map(|row| ResultRow::new(Arc::clone(&columns), Self::row_values(&row)))
+                .map_err(Self::query_error)
+        }))
+    }
+
+    /// Keep `connection` alive until `rows` is exhausted or dropped
+    pub(crate) fn hold_connection<S, C>(rows: S, connection: C) -> RowStream
+    where
+        S: Stream<Item = Result<ResultRow>> + Send + 'static,
+        C: Send + 'static,
+    {
+        Box::pin(futures::stream::unfold(
+            (Box::pin(rows), connection),
+            |(mut rows, connection)| async move {
+                let row = rows.next().await?;
+                Some((row, (rows, connection)))
+            },
+        ))
+    }
+
+    /// Describe the result columns of a prepared statement
+    fn column_infos(statement: &Statement) -> Vec<ColumnInfo> {
+        statement
+            .columns()
+            .iter()
+            .map(|column| ColumnInfo::new(column.name()).with_declared_type(column.type_().name()))
+            .collect()
+    }
+
+    /// Map a driver error from a query, keeping transaction conflicts retryable
+    /// and unique violations recognizable
+    pub(crate) fn query_error(error: tokio_postgres::Error) -> DatabaseError {
+        Self::classified_error(&error).unwrap_or_else(|| DatabaseError::query(error.to_string()))
+    }
+
+    /// Map a driver error from a transaction control statement, keeping
+    /// transaction conflicts retryable
+    pub(crate) fn transaction_error(error: tokio_postgres::Error) -> DatabaseError {
+        Self::classified_error(&error)
+            .unwrap_or_else(|| DatabaseError::transaction(error.to_string()))
+    }
+
+    /// Classify serialization failures (`40001`) and deadlocks (`40P01`) as
+    /// transaction conflicts, and unique violations (`23505`)
+    fn classified_error(error: &tokio_postgres::Error) -> Option<DatabaseError> {
+        let db_error = error.as_db_error()?;
+        let code = db_error.code();
+        if *code == SqlState::T_R_SERIALIZATION_FAILURE || *code == SqlState::T_R_DEADLOCK_DETECTED
+        {
+            Some(DatabaseError::transaction_conflict(db_error.to_string()))
+        } else if *code == SqlState::UNIQUE_VIOLATION {
+            Some(DatabaseError::unique_violation(db_error.to_string()))
+        } else {
+            None
+        }
+    }
+
+    /// Roll back the level abandoned by a dropped `TransactionGuard`, if any
+    ///
+    /// Must be called with the transaction depth locked.
+    async fn roll_back_abandoned(&self, transaction_depth: &mut usize) -> Result<()> {
+        let level = match self.abandoned.take() {
+            Some(level) => level,
+            None => return Ok(()),
+        };
+
+        // The level may already have been finished explicitly
+        if *transaction_depth >= level {
+            let statement = abandoned_rollback_sql(level).join("; ");
+            if let Err(e) = self.run_transaction_statement(&statement).await {
+                self.abandoned.mark(level);
+                return Err(e);
+            }
+            *transaction_depth = level - 1;
+        }
+        Ok(())
+    }
+
+    /// Roll back an abandoned level before running a statement
+    async fn resolve_abandoned(&self) -> Result<()> {
+        if self.abandoned.level().is_none() {
+            return Ok(());
+        }
+        let mut transaction_depth = self.transaction_depth.lock().await;
+        self.roll_back_abandoned(&mut transaction_depth).await
+    }
+
+    /// Run a transaction control statement with the default timeout
+    async fn run_transaction_statement(&self, statement: &str) -> Result<()> {
+        let client = self.client.lock().await;
+        let client = client
+            .as_ref()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+
+        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, client.batch_execute(statement))
+            .await
+            .map_err(
+                |_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64),
+            )?
+            .map_err(Self::transaction_error)
     }
 
     /// Convert DatabaseValue to postgres parameter
-    fn value_to_param(
+    pub(crate) fn value_to_param(
         value: &DatabaseValue,
     ) -> Box<dyn tokio_postgres::types::ToSql + Sync + Send> {
         match value {
@@ -119,11 +306,14 @@ impl Database for PostgresDatabase {
             *client = None;
         }
 
-        // Reset transaction flag
-        {
-            let mut in_transaction = self.in_transaction.lock().await;
-            *in_transaction = false;
-        }
+        // Reset transaction depth
+        let closed_levels = {
+            let mut transaction_depth = self.transaction_depth.lock().await;
+            *transaction_depth = 0;
+            self.abandoned.take();
+            self.callbacks.truncate(1)
+        };
+        run_rolled_back(closed_levels);
 
         let connection_string = connection_string.to_string();
         let client_arc = Arc::clone(&self.client);
@@ -170,11 +360,14 @@ impl Database for PostgresDatabase {
     }
 
     async fn disconnect(&self) -> Result<()> {
-        // Clear transaction flag
-        {
-            let mut in_transaction = self.in_transaction.lock().await;
-            *in_transaction = false;
-        }
+        // Clear transaction depth
+        let closed_levels = {
+            let mut transaction_depth = self.transaction_depth.lock().await;
+            *transaction_depth = 0;
+            self.abandoned.take();
+            self.callbacks.truncate(1)
+        };
+        run_rolled_back(closed_levels);
 
         let mut client = self.client.lock().await;
         *client = None;
@@ -182,6 +375,7 @@ impl Database for PostgresDatabase {
     }
 
     async fn execute(&self, query: &str) -> Result<u64> {
+        self.resolve_abandoned().await?;
         let client = self.client.lock().await;
         let client = client
             .as_ref()
@@ -191,32 +385,33 @@ impl Database for PostgresDatabase {
 
         let affected = tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, execute_future)
             .await
-            .map_err(|_| {
-                DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
-            })?
-            .map_err(|e| DatabaseError::query(e.to_string()))?;
+            .map_err(
+                |_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64),
+            )?
+            .map_err(Self::query_error)?;
 
         Ok(affected)
     }
 
-    async fn query(&self, query: &str) -> Result<DatabaseResult> {
+    async fn execute_batch(&self, script: &str) -> Result<()> {
+        self.resolve_abandoned().await?;
         let client = self.client.lock().await;
         let client = client
             .as_ref()
             .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
 
-        let query_future = client.query(query, &[]);
-
-        let rows = tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, query_future)
+        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, client.batch_execute(script))
             .await
-            .map_err(|_| {
-                DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
-            })?
-            .map_err(|e| DatabaseError::query(e.to_string()))?;
-
-        let results: Vec<DatabaseRow> = rows.iter().map(Self::row_to_database_row).collect();
+            .map_err(
+                |_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64),
+            )?
+            .map_err(Self::query_error)
+    }
 
-        Ok(results)
+    async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, &[])
+            .await
+            .map(ResultSet::into_database_result)
     }
 
     async fn query_with_params(
@@ -224,36 +419,49 @@ impl Database for PostgresDatabase {
         query: &str,
         params: &[DatabaseValue],
     ) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, params)
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.query_rows_with_params(query, &[]).await
+    }
+
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        self.resolve_abandoned().await?;
         let client = self.client.lock().await;
         let client = client
             .as_ref()
             .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
 
-        // Convert DatabaseValue to postgres parameters and extract references
-        let postgres_params: Vec<Box<dyn tokio_postgres::types::ToSql + Sync + Send>> =
-            params.iter().map(Self::value_to_param).collect();
-
-        // Create a slice of trait object references
-        let param_refs: Vec<&(dyn tokio_postgres::types::ToSql + Sync)> = postgres_params
-            .iter()
-            .map(|p| p.as_ref() as &(dyn tokio_postgres::types::ToSql + Sync))
-            .collect();
-
-        let query_future = client.query(query, &param_refs);
-
-        let rows = tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, query_future)
-            .await
-            .map_err(|_| {
-                DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
-            })?
-            .map_err(|e| DatabaseError::query(e.to_string()))?;
+        Self::query_result_set(client, query, params, DEFAULT_OPERATION_TIMEOUT).await
+    }
 
-        let results: Vec<DatabaseRow> = rows.iter().map(Self::row_to_database_row).collect();
+    async fn query_stream_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<RowStream> {
+        self.resolve_abandoned().await?;
+        let client = Arc::clone(&self.client).lock_owned().await;
+        let rows = {
+            let conn = client
+                .as_ref()
+                .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+            Self::query_row_stream(conn, query, params, DEFAULT_OPERATION_TIMEOUT).await?
+        };
 
-        Ok(results)
+        // The client stays locked until the stream is finished or dropped
+        Ok(Self::hold_connection(rows, client))
     }
 
     async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        self.resolve_abandoned().await?;
         let client = self.client.lock().await;
         let client = client
             .as_ref()
@@ -273,103 +481,119 @@ impl Database for PostgresDatabase {
 
         let affected = tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, execute_future)
             .await
-            .map_err(|_| {
-                DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
-            })?
-            .map_err(|e| DatabaseError::query(e.to_string()))?;
+            .map_err(
+                |_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64),
+            )?
+            .map_err(Self::query_error)?;
 
         Ok(affected)
     }
 
     async fn begin_transaction(&self) -> Result<()> {
-        let mut in_transaction = self.in_transaction.lock().await;
-
-        if *in_transaction {
-            return Err(DatabaseError::transaction(
-                "Already in a transaction".to_string(),
-            ));
-        }
+        self.begin_transaction_with_options(TransactionOptions::default())
+            .await
+    }
 
-        let client = self.client.lock().await;
-        let client = client
-            .as_ref()
-            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+    async fn begin_transaction_with_options(&self, options: TransactionOptions) -> Result<()> {
+        self.begin_transaction_level(options).await.map(|_| ())
+    }
 
-        let begin_future = client.execute("BEGIN", &[]);
+    async fn begin_transaction_level(&self, options: TransactionOptions) -> Result<usize> {
+        let mut transaction_depth = self.transaction_depth.lock().await;
+        self.roll_back_abandoned(&mut transaction_depth).await?;
 
-        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, begin_future)
-            .await
-            .map_err(|_| {
-                DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
-            })?
-            .map_err(|e| DatabaseError::transaction(e.to_string()))?;
+        // Nested transactions are backed by savepoints
+        let statement = if *transaction_depth == 0 {
+            options.begin_statements(Dialect::Postgres)?.join("; ")
+        } else {
+            options.ensure_nestable()?;
+            savepoint_sql(*transaction_depth)
+        };
+        self.run_transaction_statement(&statement).await?;
 
-        *in_transaction = true;
+        *transaction_depth += 1;
+        self.callbacks.push();
 
-        Ok(())
+        Ok(*transaction_depth)
     }
 
     async fn commit(&self) -> Result<()> {
-        let mut in_transaction = self.in_transaction.lock().await;
+        let mut transaction_depth = self.transaction_depth.lock().await;
+        self.roll_back_abandoned(&mut transaction_depth).await?;
 
-        if !*in_transaction {
+        if *transaction_depth == 0 {
             return Err(DatabaseError::transaction(
                 "Not in a transaction".to_string(),
             ));
         }
 
-        let client = self.client.lock().await;
-        let client = client
-            .as_ref()
-            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
-
-        let commit_future = client.execute("COMMIT", &[]);
-
-        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, commit_future)
-            .await
-            .map_err(|_| {
-                DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
-            })?
-            .map_err(|e| DatabaseError::transaction(e.to_string()))?;
+        let statement = if *transaction_depth == 1 {
+            "COMMIT".to_string()
+        } else {
+            release_savepoint_sql(*transaction_depth - 1)
+        };
+        self.run_transaction_statement(&statement).await?;
 
-        *in_transaction = false;
+        *transaction_depth -= 1;
+        let finished = self.callbacks.commit();
+        drop(transaction_depth);
 
+        // Callbacks run once the transaction state is unlocked
+        if let Some(callbacks) = finished {
+            callbacks.committed();
+        }
         Ok(())
     }
 
     async fn rollback(&self) -> Result<()> {
-        let mut in_transaction = self.in_transaction.lock().await;
+        let mut transaction_depth = self.transaction_depth.lock().await;
+        self.roll_back_abandoned(&mut transaction_depth).await?;
 
-        if !*in_transaction {
+        if *transaction_depth == 0 {
             return Err(DatabaseError::transaction(
                 "Not in a transaction".to_string(),
             ));
         }
 
-        let client = self.client.lock().await;
-        let client = client
-            .as_ref()
-            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
-
-        let rollback_future = client.execute("ROLLBACK", &[]);
-
-        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, rollback_future)
-            .await
-            .map_err(|_| {
-                DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
-            })?
-            .map_err(|e| DatabaseError::transaction(e.to_string()))?;
+        let statement = if *transaction_depth == 1 {
+            "ROLLBACK".to_string()
+        } else {
+            rollback_to_savepoint_sql(*transaction_depth - 1).join("; ")
+        };
+        self.run_transaction_statement(&statement).await?;
 
-        *in_transaction = false;
+        *transaction_depth -= 1;
+        let finished = self.callbacks.rollback();
+        drop(transaction_depth);
 
+        // Callbacks run once the transaction state is unlocked
+        if let Some(callbacks) = finished {
+            callbacks.rolled_back();
+        }
         Ok(())
     }
 
     fn in_transaction(&self) -> bool {
-        self.in_transaction
+        self.transaction_depth() > 0
+    }
+
+    fn transaction_depth(&self) -> usize {
+        let depth = self
+            .transaction_depth
             .try_lock()
             .map(|guard| *guard)
-            .unwrap_or(false)
+            .unwrap_or(0);
+        self.abandoned.effective_depth(depth)
+    }
+
+    fn defer_rollback(&self, depth: usize) -> bool {
+        self.abandoned.mark(depth);
+        run_rolled_back(self.callbacks.truncate(depth));
+        true
+    }
+
+    fn transaction_callbacks(&self, depth: usize) -> Option<Arc<TransactionCallbacks>> {
+        self.callbacks.level(depth)
     }
 }
 
@@ -377,8 +601,8 @@ impl Drop for PostgresDatabase {
     fn drop(&mut self) {
         // Attempt to rollback any open transaction
         // Note: This is best-effort cleanup since Drop cannot be async
-        if let Ok(in_trans) = self.in_transaction.try_lock() {
-            if *in_trans {
+        if let Ok(transaction_depth) = self.transaction_depth.try_lock() {
+            if *transaction_depth > 0 {
                 // Connection will handle cleanup when dropped
                 // We can't execute async rollback here
             }
@@ -390,6 +614,7 @@ impl Drop for PostgresDatabase {
 #[cfg(all(test, feature = "postgres"))]
 mod tests {
     use super::*;
+    use crate::core::transaction::{AccessMode, IsolationLevel, LockingMode};
 
     fn get_postgres_url() -> Option<String> {
         std::env::var("POSTGRES_URL").ok()
@@ -528,6 +753,46 @@ mod tests {
         let results = db.query("SELECT * FROM test_transaction").await?;
         assert_eq!(results.len(), 1); // Still only Alice
 
+        // Test nested rollback recovering from a failed statement
+        db.begin_transaction().await?;
+        db.execute("INSERT INTO test_transaction (name) VALUES ('Carol')")
+            .await?;
+        db.begin_transaction().await?;
+        assert_eq!(db.transaction_depth(), 2);
+        assert!(db
+            .execute("INSERT INTO missing_table VALUES (1)")
+            .await
+            .is_err());
+        db.rollback().await?;
+        db.commit().await?;
+        assert!(!db.in_transaction());
+
+        let results = db.query("SELECT * FROM test_transaction").await?;
+        assert_eq!(results.len(), 2); // Alice and Carol
+
+        // Test transaction options
+        let options = TransactionOptions::new()
+            .with_isolation_level(IsolationLevel::Serializable)
+            .with_access_mode(AccessMode::ReadOnly);
+        db.begin_transaction_with_options(options).await?;
+        let results = db.query("SHOW transaction_isolation").await?;
+        assert_eq!(
+            results[0].get("transaction_isolation"),
+            Some(&DatabaseValue::String("serializable".to_string()))
+        );
+        assert!(db
+            .execute("INSERT INTO test_transaction (name) VALUES ('Dave')")
+            .await
+            .is_err());
+        db.rollback().await?;
+
+        let sqlite_only = TransactionOptions::new().with_locking_mode(LockingMode::Immediate);
+        assert!(matches!(
+            db.begin_transaction_with_options(sqlite_only).await,
+            Err(DatabaseError::UnsupportedOperation(_))
+        ));
+        assert!(!db.in_transaction());
+
         // Cleanup
         db.execute("DROP TABLE test_transaction").await?;
         Ok(())
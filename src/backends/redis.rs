@@ -0,0 +1,868 @@
+//! Redis database backend implementation
+//!
+//! This module provides a Redis implementation using the redis crate. Besides the
+//! subset of the Database trait that maps onto Redis, `RedisDatabase` exposes a
+//! dedicated key-value API (strings, expiry, counters, hashes, lists and pub/sub).
+
+use crate::core::{
+    database::Database, database_types::DatabaseType, error::DatabaseError, error::Result,
+    value::DatabaseResult, value::DatabaseRow, value::DatabaseValue,
+};
+use async_trait::async_trait;
+use futures::StreamExt;
+use redis::aio::{MultiplexedConnection, PubSubStream};
+use redis::{Client, Cmd, Value};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+/// Default timeout for database operations (30 seconds)
+const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Redis database implementation
+///
+/// Raw commands can be sent through the `Database` trait: the query string holds the
+/// command and its whitespace-separated arguments, and parameters are appended as
+/// extra arguments, so values containing spaces should be passed as parameters.
+///
+/// `begin_transaction`/`commit`/`rollback` map onto MULTI/EXEC/DISCARD. While a
+/// transaction is open the server only queues commands, so use `execute` or
+/// `execute_with_params` to queue them and [`RedisDatabase::exec`] to read their
+/// replies; the typed key-value methods refuse to run inside MULTI.
+///
+/// # Example
+///
+/// ```no_run
+/// use rust_database_system::backends::RedisDatabase;
+/// use rust_database_system::core::database::Database;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Box<dyn std::error::Error>> {
+///     let db = RedisDatabase::new();
+///     db.connect("redis://localhost:6379").await?;
+///
+///     db.set_with_ttl("session:42", "alice", Duration::from_secs(60)).await?;
+///     let visits = db.incr("visits", 1).await?;
+///     println!("visit #{}", visits);
+///
+///     Ok(())
+/// }
+/// ```
+pub struct RedisDatabase {
+    client: Arc<Mutex<Option<Client>>>,
+    conn: Arc<Mutex<Option<MultiplexedConnection>>>,
+    in_transaction: Arc<Mutex<bool>>,
+}
+
+/// A message received on a subscribed channel
+#[derive(Debug, Clone, PartialEq)]
+pub struct RedisMessage {
+    /// Channel the message was published on
+    pub channel: String,
+    /// Message payload
+    pub payload: DatabaseValue,
+}
+
+/// Subscription to one or more Redis channels
+///
+/// Each subscription owns a dedicated pub/sub connection, so it does not block
+/// commands issued through the `RedisDatabase` it was created from.
+pub struct RedisSubscription {
+    stream: PubSubStream,
+}
+
+impl RedisSubscription {
+    /// Wait for the next message
+    ///
+    /// Returns `None` once the underlying connection is closed.
+    pub async fn next_message(&mut self) -> Option<RedisMessage> {
+        let msg = self.stream.next().await?;
+        Some(RedisMessage {
+            channel: msg.get_channel_name().to_string(),
+            payload: RedisDatabase::bytes_to_value(msg.get_payload_bytes().to_vec()),
+        })
+    }
+}
+
+impl RedisDatabase {
+    /// Create a new Redis database instance
+    pub fn new() -> Self {
+        Self {
+            client: Arc::new(Mutex::new(None)),
+            conn: Arc::new(Mutex::new(None)),
+            in_transaction: Arc::new(Mutex::new(false)),
+        }
+    }
+
+    /// Convert a binary-safe Redis string to a DatabaseValue
+    ///
+    /// Valid UTF-8 becomes `String`, anything else is kept as `Bytes`.
+    fn bytes_to_value(bytes: Vec<u8>) -> DatabaseValue {
+        match String::from_utf8(bytes) {
+            Ok(s) => DatabaseValue::String(s),
+            Err(e) => DatabaseValue::Bytes(e.into_bytes()),
+        }
+    }
+
+    /// Convert a Redis reply to a DatabaseValue
+    ///
+    /// Aggregate replies nested inside another reply have no scalar equivalent and
+    /// are rendered as their debug representation.
+    fn convert_value(value: Value) -> DatabaseValue {
+        match value {
+            Value::Nil => DatabaseValue::Null,
+            Value::Int(i) => DatabaseValue::Long(i),
+            Value::BulkString(bytes) => Self::bytes_to_value(bytes),
+            Value::SimpleString(s) => DatabaseValue::String(s),
+            Value::Okay => DatabaseValue::String("OK".to_string()),
+            Value::Double(d) => DatabaseValue::Double(d),
+            Value::Boolean(b) => DatabaseValue::Bool(b),
+            Value::VerbatimString { text, .. } => DatabaseValue::String(text),
+            Value::BigNumber(n) => DatabaseValue::String(n.to_string()),
+            Value::Attribute { data, .. } => Self::convert_value(*data),
+            other => DatabaseValue::String(format!("{:?}", other)),
+        }
+    }
+
+    /// Convert a Redis reply to rows
+    ///
+    /// Arrays and sets produce one row per element with a `value` column, maps
+    /// produce one row per entry with `key` and `value` columns, nil produces no
+    /// rows and any other reply produces a single row.
+    fn reply_to_rows(value: Value) -> DatabaseResult {
+        let single = |value: DatabaseValue| {
+            let mut row = DatabaseRow::new();
+            row.insert("value".to_string(), value);
+            row
+        };
+
+        match value {
+            Value::Nil => Vec::new(),
+            Value::Array(items) | Value::Set(items) => items
+                .into_iter()
+                .map(|item| single(Self::convert_value(item)))
+                .collect(),
+            Value::Map(entries) => entries
+                .into_iter()
+                .map(|(key, value)| {
+                    let mut row = DatabaseRow::new();
+                    row.insert("key".to_string(), Self::convert_value(key));
+                    row.insert("value".to_string(), Self::convert_value(value));
+                    row
+                })
+                .collect(),
+            Value::Attribute { data, .. } => Self::reply_to_rows(*data),
+            other => vec![single(Self::convert_value(other))],
+        }
+    }
+
+    /// Convert a Redis reply to an affected count
+    fn reply_to_count(value: &Value) -> u64 {
+        match value {
+            Value::Nil => 0,
+            Value::Int(i) => (*i).max(0) as u64,
+            Value::Array(items) | Value::Set(items) => items.len() as u64,
+            Value::Map(entries) => entries.len() as u64,
+            // Nothing is applied until EXEC
+            Value::SimpleString(s) if s == "QUEUED" => 0,
+            _ => 1,
+        }
+    }
+
+    /// Convert a DatabaseValue to a Redis argument
+    ///
+    /// Redis stores strings only, so numbers are sent in their decimal form,
+    /// booleans as `1`/`0`, timestamps as microseconds and NULL as an empty string.
+    fn value_to_arg(value: &DatabaseValue) -> Vec<u8> {
+        match value {
+            DatabaseValue::Null => Vec::new(),
+            DatabaseValue::Bool(b) => {
+                if *b {
+                    b"1".to_vec()
+                } else {
+                    b"0".to_vec()
+                }
+            }
+            DatabaseValue::Int(i) => i.to_string().into_bytes(),
+            DatabaseValue::Long(l) => l.to_string().into_bytes(),
+            DatabaseValue::Float(f) => f.to_string().into_bytes(),
+            DatabaseValue::Double(d) => d.to_string().into_bytes(),
+            DatabaseValue::String(s) => s.as_bytes().to_vec(),
+            DatabaseValue::Bytes(b) => b.clone(),
+            DatabaseValue::Timestamp(ts) => ts.to_string().into_bytes(),
+        }
+    }
+
+    /// Build a command from a raw command string and extra parameters
+    fn build_command(query: &str, params: &[DatabaseValue]) -> Result<Cmd> {
+        let mut parts = query.split_whitespace();
+        let name = parts
+            .next()
+            .ok_or_else(|| DatabaseError::query("Empty Redis command"))?;
+
+        let mut cmd = redis::cmd(name);
+        for part in parts {
+            cmd.arg(part);
+        }
+        for param in params {
+            cmd.arg(Self::value_to_arg(param));
+        }
+        Ok(cmd)
+    }
+
+    /// Expect an integer reply
+    fn expect_int(value: Value) -> Result<i64> {
+        match value {
+            Value::Int(i) => Ok(i),
+            other => Err(DatabaseError::type_mismatch(
+                "integer",
+                &format!("{:?}", other),
+            )),
+        }
+    }
+
+    /// Convert a reply to an optional value, mapping nil to `None`
+    fn optional(value: Value) -> Option<DatabaseValue> {
+        match value {
+            Value::Nil => None,
+            other => Some(Self::convert_value(other)),
+        }
+    }
+
+    /// Send a command on the shared connection and return the raw reply
+    async fn send(&self, cmd: &Cmd) -> Result<Value> {
+        let mut conn = self.conn.lock().await;
+        let conn = conn
+            .as_mut()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+
+        tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, cmd.query_async::<Value>(conn))
+            .await
+            .map_err(
+                |_| DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64),
+            )?
+            .map_err(DatabaseError::from)
+    }
+
+    /// Send a command for the typed key-value API
+    ///
+    /// Fails while MULTI is open because the server would only reply `QUEUED`.
+    async fn run(&self, cmd: &Cmd) -> Result<Value> {
+        let in_transaction = self.in_transaction.lock().await;
+        if *in_transaction {
+            return Err(DatabaseError::transaction(
+                "Typed key-value commands cannot run inside MULTI; queue commands with execute() and read replies from exec()".to_string(),
+            ));
+        }
+        self.send(cmd).await
+    }
+
+    /// Get the value of a key
+    pub async fn get(&self, key: &str) -> Result<Option<DatabaseValue>> {
+        let reply = self.run(redis::cmd("GET").arg(key)).await?;
+        Ok(Self::optional(reply))
+    }
+
+    /// Set the value of a key
+    pub async fn set(&self, key: &str, value: impl Into<DatabaseValue>) -> Result<()> {
+        let value = Self::value_to_arg(&value.into());
+        self.run(redis::cmd("SET").arg(key).arg(value)).await?;
+        Ok(())
+    }
+
+    /// Set the value of a key with a time-to-live
+    pub async fn set_with_ttl(
+        &self,
+        key: &str,
+        value: impl Into<DatabaseValue>,
+        ttl: Duration,
+    ) -> Result<()> {
+        let value = Self::value_to_arg(&value.into());
+        self.run(
+            redis::cmd("SET")
+                .arg(key)
+                .arg(value)
+                .arg("PX")
+                .arg(ttl.as_millis() as u64),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Delete a key, returning whether it existed
+    pub async fn delete(&self, key: &str) -> Result<bool> {
+        let reply = self.run(redis::cmd("DEL").arg(key)).await?;
+        Ok(Self::expect_int(reply)? > 0)
+    }
+
+    /// Check whether a key exists
+    pub async fn exists(&self, key: &str) -> Result<bool> {
+        let reply = self.run(redis::cmd("EXISTS").arg(key)).await?;
+        Ok(Self::expect_int(reply)? > 0)
+    }
+
+    /// Set a time-to-live on a key, returning whether the key exists
+    pub async fn expire(&self, key: &str, ttl: Duration) -> Result<bool> {
+        let reply = self
+            .run(redis::cmd("PEXPIRE").arg(key).arg(ttl.as_millis() as u64))
+            .await?;
+        Ok(Self::expect_int(reply)? == 1)
+    }
+
+    /// Get the remaining time-to-live of a key
+    ///
+    /// Returns `None` if the key does not exist or has no expiry.
+    pub async fn ttl(&self, key: &str) -> Result<Option<Duration>> {
+        let reply = self.run(redis::cmd("PTTL").arg(key)).await?;
+        let millis = Self::expect_int(reply)?;
+        Ok((millis >= 0).then(|| Duration::from_millis(millis as u64)))
+    }
+
+    /// Increment the integer value of a key, returning the new value
+    ///
+    /// A missing key is treated as 0; use a negative `delta` to decrement.
+    pub async fn incr(&self, key: &str, delta: i64) -> Result<i64> {
+        let reply = self.run(redis::cmd("INCRBY").arg(key).arg(delta)).await?;
+        Self::expect_int(reply)
+    }
+
+    /// Set a hash field, returning whether the field is new
+    pub async fn hset(
+        &self,
+        key: &str,
+        field: &str,
+        value: impl Into<DatabaseValue>,
+    ) -> Result<bool> {
+        let value = Self::value_to_arg(&value.into());
+        let reply = self
+            .run(redis::cmd("HSET").arg(key).arg(field).arg(value))
+            .await?;
+        Ok(Self::expect_int(reply)? > 0)
+    }
+
+    /// Get a hash field
+    pub async fn hget(&self, key: &str, field: &str) -> Result<Option<DatabaseValue>> {
+        let reply = self.run(redis::cmd("HGET").arg(key).arg(field)).await?;
+        Ok(Self::optional(reply))
+    }
+
+    /// Get all fields of a hash as a row
+    pub async fn hgetall(&self, key: &str) -> Result<DatabaseRow> {
+        let reply = self.run(redis::cmd("HGETALL").arg(key)).await?;
+
+        let pairs: Vec<(Value, Value)> = match reply {
+            Value::Map(entries) => entries,
+            Value::Array(items) => {
+                let mut items = items.into_iter();
+                let mut pairs = Vec::new();
+                while let (Some(field), Some(value)) = (items.next(), items.next()) {
+                    pairs.push((field, value));
+                }
+                pairs
+            }
+            Value::Nil => Vec::new(),
+            other => {
+                return Err(DatabaseError::type_mismatch(
+                    "hash",
+                    &format!("{:?}", other),
+                ))
+            }
+        };
+
+        Ok(pairs
+            .into_iter()
+            .map(|(field, value)| {
+                (
+                    Self::convert_value(field).as_string(),
+                    Self::convert_value(value),
+                )
+            })
+            .collect())
+    }
+
+    /// Delete a hash field, returning whether it existed
+    pub async fn hdel(&self, key: &str, field: &str) -> Result<bool> {
+        let reply = self.run(redis::cmd("HDEL").arg(key).arg(field)).await?;
+        Ok(Self::expect_int(reply)? > 0)
+    }
+
+    /// Push a value to the head of a list, returning the new length
+    pub async fn lpush(&self, key: &str, value: impl Into<DatabaseValue>) -> Result<u64> {
+        let value = Self::value_to_arg(&value.into());
+        let reply = self.run(redis::cmd("LPUSH").arg(key).arg(value)).await?;
+        Ok(Self::expect_int(reply)? as u64)
+    }
+
+    /// Push a value to the tail of a list, returning the new length
+    pub async fn rpush(&self, key: &str, value: impl Into<DatabaseValue>) -> Result<u64> {
+        let value = Self::value_to_arg(&value.into());
+        let reply = self.run(redis::cmd("RPUSH").arg(key).arg(value)).await?;
+        Ok(Self::expect_int(reply)? as u64)
+    }
+
+    /// Pop a value from the head of a list
+    pub async fn lpop(&self, key: &str) -> Result<Option<DatabaseValue>> {
+        let reply = self.run(redis::cmd("LPOP").arg(key)).await?;
+        Ok(Self::optional(reply))
+    }
+
+    /// Pop a value from the tail of a list
+    pub async fn rpop(&self, key: &str) -> Result<Option<DatabaseValue>> {
+        let reply = self.run(redis::cmd("RPOP").arg(key)).await?;
+        Ok(Self::optional(reply))
+    }
+
+    /// Get a range of list elements (inclusive, negative indexes count from the tail)
+    pub async fn lrange(&self, key: &str, start: i64, stop: i64) -> Result<Vec<DatabaseValue>> {
+        let reply = self
+            .run(redis::cmd("LRANGE").arg(key).arg(start).arg(stop))
+            .await?;
+
+        match reply {
+            Value::Array(items) => Ok(items.into_iter().map(Self::convert_value).collect()),
+            other => Err(DatabaseError::type_mismatch(
+                "list",
+                &format!("{:?}", other),
+            )),
+        }
+    }
+
+    /// Get the length of a list
+    pub async fn llen(&self, key: &str) -> Result<u64> {
+        let reply = self.run(redis::cmd("LLEN").arg(key)).await?;
+        Ok(Self::expect_int(reply)? as u64)
+    }
+
+    /// Publish a message, returning the number of subscribers that received it
+    pub async fn publish(&self, channel: &str, message: impl Into<DatabaseValue>) -> Result<u64> {
+        let message = Self::value_to_arg(&message.into());
+        let reply = self
+            .run(redis::cmd("PUBLISH").arg(channel).arg(message))
+            .await?;
+        Ok(Self::expect_int(reply)? as u64)
+    }
+
+    /// Subscribe to one or more channels on a dedicated connection
+    ///
+    /// # Errors
+    ///
+    /// Returns error if not connected or the subscription fails
+    pub async fn subscribe(&self, channels: &[&str]) -> Result<RedisSubscription> {
+        let client = self
+            .client
+            .lock()
+            .await
+            .clone()
+            .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+
+        let subscribe = async {
+            let mut pubsub = client.get_async_pubsub().await?;
+            for channel in channels {
+                pubsub.subscribe(*channel).await?;
+            }
+            Ok::<_, redis::RedisError>(pubsub)
+        };
+
+        let pubsub = tokio::time::timeout(DEFAULT_OPERATION_TIMEOUT, subscribe)
+            .await
+            .map_err(|_| {
+                DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
+            })??;
+
+        Ok(RedisSubscription {
+            stream: pubsub.into_on_message(),
+        })
+    }
+
+    /// Commit the open MULTI block and return the reply of every queued command
+    ///
+    /// # Errors
+    ///
+    /// Returns error if no transaction is open, or if EXEC was aborted because a
+    /// watched key changed.
+    pub async fn exec(&self) -> Result<Vec<DatabaseValue>> {
+        let mut in_transaction = self.in_transaction.lock().await;
+
+        if !*in_transaction {
+            return Err(DatabaseError::transaction(
+                "Not in a transaction".to_string(),
+            ));
+        }
+
+        let reply = self.send(&redis::cmd("EXEC")).await;
+        // EXEC always ends the MULTI block, even when it fails
+        *in_transaction = false;
+
+        match reply.map_err(|e| DatabaseError::transaction(e.to_string()))? {
+            Value::Array(items) => Ok(items.into_iter().map(Self::convert_value).collect()),
+            Value::Nil => Err(DatabaseError::transaction(
+                "Transaction aborted: a watched key was modified".to_string(),
+            )),
+            other => Err(DatabaseError::type_mismatch(
+                "array",
+                &format!("{:?}", other),
+            )),
+        }
+    }
+}
+
+impl Default for RedisDatabase {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Database for RedisDatabase {
+    fn database_type(&self) -> DatabaseType {
+        DatabaseType::Redis
+    }
+
+    async fn connect(&self, connection_string: &str) -> Result<()> {
+        let client = Client::open(connection_string)
+            .map_err(|e| DatabaseError::InvalidConnectionString(e.to_string()))?;
+
+        let conn = tokio::time::timeout(
+            DEFAULT_OPERATION_TIMEOUT,
+            client.get_multiplexed_async_connection(),
+        )
+        .await
+        .map_err(|_| {
+            DatabaseError::connection_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64)
+        })?
+        .map_err(|e| DatabaseError::connection(e.to_string()))?;
+
+        // Reset transaction flag
+        {
+            let mut in_transaction = self.in_transaction.lock().await;
+            *in_transaction = false;
+        }
+
+        *self.client.lock().await = Some(client);
+        *self.conn.lock().await = Some(conn);
+
+        Ok(())
+    }
+
+    fn is_connected(&self) -> bool {
+        self.conn
+            .try_lock()
+            .map(|conn| conn.is_some())
+            .unwrap_or(false)
+    }
+
+    async fn disconnect(&self) -> Result<()> {
+        // Clear transaction flag
+        {
+            let mut in_transaction = self.in_transaction.lock().await;
+            *in_transaction = false;
+        }
+
+        // Dropping the multiplexed connection closes it
+        self.conn.lock().await.take();
+        self.client.lock().await.take();
+        Ok(())
+    }
+
+    async fn execute(&self, query: &str) -> Result<u64> {
+        self.execute_with_params(query, &[]).await
+    }
+
+    async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        self.query_with_params(query, &[]).await
+    }
+
+    async fn query_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<DatabaseResult> {
+        let cmd = Self::build_command(query, params)?;
+        let reply = self.send(&cmd).await?;
+        Ok(Self::reply_to_rows(reply))
+    }
+
+    async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        let cmd = Self::build_command(query, params)?;
+        let reply = self.send(&cmd).await?;
+        Ok(Self::reply_to_count(&reply))
+    }
+
+    async fn begin_transaction(&self) -> Result<()> {
+        let mut in_transaction = self.in_transaction.lock().await;
+
+        if *in_transaction {
+            return Err(DatabaseError::transaction(
+                "Already in a transaction".to_string(),
+            ));
+        }
+
+        self.send(&redis::cmd("MULTI"))
+            .await
+            .map_err(|e| DatabaseError::transaction(e.to_string()))?;
+        *in_transaction = true;
+
+        Ok(())
+    }
+
+    async fn commit(&self) -> Result<()> {
+        self.exec().await.map(|_| ())
+    }
+
+    /// Discard the queued commands
+    ///
+    /// Redis cannot undo commands once EXEC ran, so this only drops the MULTI queue.
+    async fn rollback(&self) -> Result<()> {
+        let mut in_transaction = self.in_transaction.lock().await;
+
+        if !*in_transaction {
+            return Err(DatabaseError::transaction(
+                "Not in a transaction".to_string(),
+            ));
+        }
+
+        self.send(&redis::cmd("DISCARD"))
+            .await
+            .map_err(|e| DatabaseError::transaction(e.to_string()))?;
+        *in_transaction = false;
+
+        Ok(())
+    }
+
+    fn in_transaction(&self) -> bool {
+        self.in_transaction
+            .try_lock()
+            .map(|guard| *guard)
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(all(test, feature = "redis_support"))]
+mod tests {
+    use super::*;
+
+    fn get_redis_url() -> Option<String> {
+        std::env::var("REDIS_URL").ok()
+    }
+
+    #[test]
+    fn test_value_conversion() {
+        assert_eq!(
+            RedisDatabase::convert_value(Value::Nil),
+            DatabaseValue::Null
+        );
+        assert_eq!(
+            RedisDatabase::convert_value(Value::Int(7)),
+            DatabaseValue::Long(7)
+        );
+        assert_eq!(
+            RedisDatabase::convert_value(Value::BulkString(b"hello".to_vec())),
+            DatabaseValue::String("hello".to_string())
+        );
+        assert_eq!(
+            RedisDatabase::convert_value(Value::BulkString(vec![0xff, 0xfe])),
+            DatabaseValue::Bytes(vec![0xff, 0xfe])
+        );
+        assert_eq!(
+            RedisDatabase::convert_value(Value::Okay),
+            DatabaseValue::String("OK".to_string())
+        );
+
+        assert_eq!(
+            RedisDatabase::value_to_arg(&DatabaseValue::Bool(true)),
+            b"1".to_vec()
+        );
+        assert_eq!(
+            RedisDatabase::value_to_arg(&DatabaseValue::Long(-42)),
+            b"-42".to_vec()
+        );
+        assert!(RedisDatabase::value_to_arg(&DatabaseValue::Null).is_empty());
+    }
+
+    #[test]
+    fn test_reply_to_rows() {
+        let rows = RedisDatabase::reply_to_rows(Value::Array(vec![
+            Value::BulkString(b"a".to_vec()),
+            Value::BulkString(b"b".to_vec()),
+        ]));
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[1].get("value"),
+            Some(&DatabaseValue::String("b".to_string()))
+        );
+
+        assert!(RedisDatabase::reply_to_rows(Value::Nil).is_empty());
+        assert_eq!(RedisDatabase::reply_to_count(&Value::Int(3)), 3);
+        assert_eq!(
+            RedisDatabase::reply_to_count(&Value::SimpleString("QUEUED".to_string())),
+            0
+        );
+    }
+
+    #[test]
+    fn test_build_command_rejects_empty() {
+        assert!(RedisDatabase::build_command("   ", &[]).is_err());
+        assert!(RedisDatabase::build_command("SET key", &[DatabaseValue::Int(1)]).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_not_connected() {
+        let db = RedisDatabase::new();
+        let result = db.get("key").await;
+        assert!(result.unwrap_err().to_string().contains("Not connected"));
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features redis_support -- --ignored
+    async fn test_redis_key_value() -> Result<()> {
+        let url = match get_redis_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: REDIS_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = RedisDatabase::new();
+        db.connect(&url).await?;
+
+        db.set("test:kv:name", "Alice").await?;
+        assert_eq!(
+            db.get("test:kv:name").await?,
+            Some(DatabaseValue::String("Alice".to_string()))
+        );
+
+        db.set_with_ttl("test:kv:session", 1i64, Duration::from_secs(60))
+            .await?;
+        let ttl = db.ttl("test:kv:session").await?.expect("ttl set");
+        assert!(ttl <= Duration::from_secs(60));
+        assert!(db.expire("test:kv:name", Duration::from_secs(30)).await?);
+
+        db.delete("test:kv:counter").await?;
+        assert_eq!(db.incr("test:kv:counter", 5).await?, 5);
+        assert_eq!(db.incr("test:kv:counter", -2).await?, 3);
+
+        assert!(db.delete("test:kv:name").await?);
+        assert!(!db.exists("test:kv:name").await?);
+        assert_eq!(db.get("test:kv:name").await?, None);
+
+        db.delete("test:kv:session").await?;
+        db.delete("test:kv:counter").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features redis_support -- --ignored
+    async fn test_redis_hash_and_list() -> Result<()> {
+        let url = match get_redis_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: REDIS_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = RedisDatabase::new();
+        db.connect(&url).await?;
+        db.delete("test:hash").await?;
+        db.delete("test:list").await?;
+
+        assert!(db.hset("test:hash", "name", "Bob").await?);
+        db.hset("test:hash", "age", 25i64).await?;
+        assert_eq!(
+            db.hget("test:hash", "age").await?,
+            Some(DatabaseValue::String("25".to_string()))
+        );
+        let row = db.hgetall("test:hash").await?;
+        assert_eq!(row.len(), 2);
+        assert!(db.hdel("test:hash", "age").await?);
+
+        db.rpush("test:list", "b").await?;
+        db.rpush("test:list", "c").await?;
+        assert_eq!(db.lpush("test:list", "a").await?, 3);
+        assert_eq!(db.llen("test:list").await?, 3);
+        assert_eq!(
+            db.lrange("test:list", 0, -1).await?,
+            vec![
+                DatabaseValue::String("a".to_string()),
+                DatabaseValue::String("b".to_string()),
+                DatabaseValue::String("c".to_string()),
+            ]
+        );
+        assert_eq!(
+            db.rpop("test:list").await?,
+            Some(DatabaseValue::String("c".to_string()))
+        );
+
+        db.delete("test:hash").await?;
+        db.delete("test:list").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features redis_support -- --ignored
+    async fn test_redis_transaction() -> Result<()> {
+        let url = match get_redis_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: REDIS_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = RedisDatabase::new();
+        db.connect(&url).await?;
+        db.delete("test:tx").await?;
+
+        db.begin_transaction().await?;
+        assert!(db.in_transaction());
+        db.execute("INCRBY test:tx 10").await?;
+        db.execute_with_params("INCRBY test:tx", &[DatabaseValue::Int(5)])
+            .await?;
+        assert!(db.get("test:tx").await.is_err());
+        let replies = db.exec().await?;
+        assert_eq!(
+            replies,
+            vec![DatabaseValue::Long(10), DatabaseValue::Long(15)]
+        );
+
+        db.begin_transaction().await?;
+        db.execute("SET test:tx 0").await?;
+        db.rollback().await?;
+        assert!(!db.in_transaction());
+        assert_eq!(
+            db.get("test:tx").await?,
+            Some(DatabaseValue::String("15".to_string()))
+        );
+
+        db.delete("test:tx").await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features redis_support -- --ignored
+    async fn test_redis_pubsub() -> Result<()> {
+        let url = match get_redis_url() {
+            Some(url) => url,
+            None => {
+                eprintln!("Skipping test: REDIS_URL not set");
+                return Ok(());
+            }
+        };
+
+        let db = RedisDatabase::new();
+        db.connect(&url).await?;
+
+        let mut subscription = db.subscribe(&["test:events"]).await?;
+        assert_eq!(db.publish("test:events", "hello").await?, 1);
+
+        let message = tokio::time::timeout(Duration::from_secs(5), subscription.next_message())
+            .await
+            .expect("message timeout")
+            .expect("subscription closed");
+        assert_eq!(message.channel, "test:events");
+        assert_eq!(message.payload, DatabaseValue::String("hello".to_string()));
+        Ok(())
+    }
+}
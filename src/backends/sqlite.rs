@@ -4,16 +4,28 @@
 
 #[cfg(feature = "sqlite")]
 use crate::core::{
-    database::Database, database_types::DatabaseType, error::DatabaseError, error::Result,
-    value::DatabaseResult, value::DatabaseRow, value::DatabaseValue,
+    database::Database,
+    database_types::DatabaseType,
+    dialect::Dialect,
+    error::DatabaseError,
+    error::Result,
+    result_set::{row_channel, ColumnInfo, ResultSet, Row, RowStream},
+    transaction::{
+        abandoned_rollback_sql, release_savepoint_sql, rollback_to_savepoint_sql, run_rolled_back,
+        savepoint_sql, AbandonedLevel, CallbackStack, TransactionCallbacks, TransactionOptions,
+    },
+    value::DatabaseResult,
+    value::DatabaseValue,
 };
 use async_trait::async_trait;
 use std::sync::Arc;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, MutexGuard};
 
 #[cfg(feature = "sqlite")]
-use rusqlite::{params_from_iter, Connection, Row};
+use rusqlite::{params_from_iter, types::ValueRef, Connection, Statement};
 use std::time::Duration;
+#[cfg(feature = "sqlite")]
+use tokio::sync::{mpsc, oneshot};
 
 /// Default timeout for database operations (30 seconds)
 const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
@@ -22,7 +34,12 @@ const DEFAULT_OPERATION_TIMEOUT: Duration = Duration::from_secs(30);
 #[cfg(feature = "sqlite")]
 pub struct SqliteDatabase {
     connection: Arc<Mutex<Option<Connection>>>,
-    in_transaction: Arc<Mutex<bool>>,
+    /// Number of open transaction levels; levels above the first are savepoints
+    transaction_depth: Arc<Mutex<usize>>,
+    /// Level left open by a dropped `TransactionGuard`, rolled back before the next operation
+    abandoned: Arc<AbandonedLevel>,
+    /// `on_commit` / `on_rollback` callbacks of every open transaction level
+    callbacks: Arc<CallbackStack>,
 }
 
 #[cfg(feature = "sqlite")]
@@ -31,34 +48,182 @@ impl SqliteDatabase {
     pub fn new() -> Self {
         Self {
             connection: Arc::new(Mutex::new(None)),
-            in_transaction: Arc::new(Mutex::new(false)),
+            transaction_depth: Arc::new(Mutex::new(0)),
+            abandoned: Arc::new(AbandonedLevel::default()),
+            callbacks: Arc::new(CallbackStack::default()),
+        }
+    }
+
+    /// Roll back the level abandoned by a dropped `TransactionGuard`, if any
+    ///
+    /// Must be called with the transaction depth and connection locked.
+    fn roll_back_abandoned(
+        conn: &Connection,
+        transaction_depth: &mut usize,
+        abandoned: &AbandonedLevel,
+    ) -> Result<()> {
+        let level = match abandoned.take() {
+            Some(level) => level,
+            None => return Ok(()),
+        };
+
+        // The level may already have been finished explicitly
+        if *transaction_depth >= level {
+            if let Err(e) = conn.execute_batch(&abandoned_rollback_sql(level).join("; ")) {
+                abandoned.mark(level);
+                return Err(e.into());
+            }
+            *transaction_depth = level - 1;
+        }
+        Ok(())
+    }
+
+    /// Lock the connection for a statement, first rolling back an abandoned level
+    fn lock_connection<'a>(
+        connection_arc: &'a Mutex<Option<Connection>>,
+        transaction_depth_arc: &Mutex<usize>,
+        abandoned: &AbandonedLevel,
+    ) -> Result<MutexGuard<'a, Option<Connection>>> {
+        if abandoned.level().is_none() {
+            return Ok(connection_arc.blocking_lock());
+        }
+
+        // Same lock order as begin/commit/rollback
+        let mut transaction_depth = transaction_depth_arc.blocking_lock();
+        let connection = connection_arc.blocking_lock();
+        if let Some(conn) = connection.as_ref() {
+            Self::roll_back_abandoned(conn, &mut transaction_depth, abandoned)?;
         }
+        Ok(connection)
     }
 
-    /// Convert a rusqlite Row to a DatabaseRow
-    fn row_to_database_row(row: &Row) -> rusqlite::Result<DatabaseRow> {
-        let mut db_row = DatabaseRow::new();
-        let column_count = row.as_ref().column_count();
+    /// Convert a rusqlite value to a DatabaseValue
+    pub(crate) fn value_from_ref(value: ValueRef) -> DatabaseValue {
+        match value {
+            ValueRef::Null => DatabaseValue::Null,
+            ValueRef::Integer(v) => DatabaseValue::Long(v),
+            ValueRef::Real(v) => DatabaseValue::Double(v),
+            ValueRef::Text(v) => DatabaseValue::String(String::from_utf8_lossy(v).to_string()),
+            ValueRef::Blob(v) => DatabaseValue::Bytes(v.to_vec()),
+        }
+    }
 
-        for i in 0..column_count {
-            let column_name = row.as_ref().column_name(i)?.to_string();
-            let value = match row.get_ref(i)? {
-                rusqlite::types::ValueRef::Null => DatabaseValue::Null,
-                rusqlite::types::ValueRef::Integer(v) => DatabaseValue::Long(v),
-                rusqlite::types::ValueRef::Real(v) => DatabaseValue::Double(v),
-                rusqlite::types::ValueRef::Text(v) => {
-                    DatabaseValue::String(String::from_utf8_lossy(v).to_string())
+    /// Describe the result columns of a prepared statement
+    fn column_infos(stmt: &Statement) -> Vec<ColumnInfo> {
+        stmt.columns()
+            .iter()
+            .map(|column| {
+                let info = ColumnInfo::new(column.name());
+                match column.decl_type() {
+                    Some(declared_type) => info.with_declared_type(declared_type),
+                    None => info,
                 }
-                rusqlite::types::ValueRef::Blob(v) => DatabaseValue::Bytes(v.to_vec()),
+            })
+            .collect()
+    }
+
+    /// Read every column of a row in order
+    fn row_values(
+        row: &rusqlite::Row,
+        column_count: usize,
+    ) -> rusqlite::Result<Vec<DatabaseValue>> {
+        (0..column_count)
+            .map(|i| row.get_ref(i).map(Self::value_from_ref))
+            .collect()
+    }
+
+    /// Run a query on a connection and collect the rows with their declared column types
+    pub(crate) fn query_result_set(
+        conn: &Connection,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> rusqlite::Result<ResultSet> {
+        let mut stmt = conn.prepare(query)?;
+        let columns = Self::column_infos(&stmt);
+        let column_count = columns.len();
+
+        // Convert DatabaseValue to rusqlite parameters
+        let rusqlite_params: Vec<Box<dyn rusqlite::ToSql>> =
+            params.iter().map(Self::value_to_param).collect();
+
+        let rows = stmt.query_map(params_from_iter(rusqlite_params.iter()), |row| {
+            Self::row_values(row, column_count)
+        })?;
+
+        let mut results = Vec::new();
+        for row_result in rows {
+            results.push(row_result?);
+        }
+
+        Ok(ResultSet::new(columns, results))
+    }
+
+    /// Run a query on a connection and send its rows one by one
+    ///
+    /// Blocks the calling thread until the rows are exhausted or the receiver is
+    /// dropped, waiting whenever the channel is full. Preparation errors are
+    /// reported through `prepared`, which is completed before the first row is
+    /// read.
+    pub(crate) fn send_rows(
+        conn: &Connection,
+        query: &str,
+        params: &[DatabaseValue],
+        prepared: oneshot::Sender<Result<()>>,
+        sender: mpsc::Sender<Result<Row>>,
+    ) {
+        let mut stmt = match conn.prepare(query) {
+            Ok(stmt) => stmt,
+            Err(e) => {
+                let _ = prepared.send(Err(e.into()));
+                return;
+            }
+        };
+        let columns: Arc<[ColumnInfo]> = Self::column_infos(&stmt).into();
+
+        // Convert DatabaseValue to rusqlite parameters
+        let rusqlite_params: Vec<Box<dyn rusqlite::ToSql>> =
+            params.iter().map(Self::value_to_param).collect();
+
+        let mut rows = match stmt.query(params_from_iter(rusqlite_params.iter())) {
+            Ok(rows) => rows,
+            Err(e) => {
+                let _ = prepared.send(Err(e.into()));
+                return;
+            }
+        };
+        if prepared.send(Ok(())).is_err() {
+            return;
+        }
+
+        loop {
+            let row = match rows.next() {
+                Ok(Some(row)) => Self::row_values(row, columns.len())
+                    .map(|values| Row::new(Arc::clone(&columns), values))
+                    .map_err(DatabaseError::from),
+                Ok(None) => break,
+                Err(e) => Err(e.into()),
             };
-            db_row.insert(column_name, value);
+
+            let failed = row.is_err();
+            if sender.blocking_send(row).is_err() || failed {
+                break;
+            }
         }
+    }
 
-        Ok(db_row)
+    /// Wait for a streaming query to be prepared
+    pub(crate) async fn await_prepared(
+        prepared: oneshot::Receiver<Result<()>>,
+        timeout: Duration,
+    ) -> Result<()> {
+        tokio::time::timeout(timeout, prepared)
+            .await
+            .map_err(|_| DatabaseError::query_timeout(timeout.as_millis() as u64))?
+            .map_err(|_| DatabaseError::other("Streaming query task ended unexpectedly"))?
     }
 
     /// Convert DatabaseValue to rusqlite parameter
-    fn value_to_param(value: &DatabaseValue) -> Box<dyn rusqlite::ToSql> {
+    pub(crate) fn value_to_param(value: &DatabaseValue) -> Box<dyn rusqlite::ToSql> {
         match value {
             DatabaseValue::Null => Box::new(None::<i64>),
             DatabaseValue::Bool(v) => Box::new(*v),
@@ -94,11 +259,14 @@ impl Database for SqliteDatabase {
             *connection = None;
         }
 
-        // Reset transaction flag to handle failed/aborted attempts
-        {
-            let mut in_transaction = self.in_transaction.lock().await;
-            *in_transaction = false;
-        }
+        // Reset transaction depth to handle failed/aborted attempts
+        let closed_levels = {
+            let mut transaction_depth = self.transaction_depth.lock().await;
+            *transaction_depth = 0;
+            self.abandoned.take();
+            self.callbacks.truncate(1)
+        };
+        run_rolled_back(closed_levels);
 
         let connection_string = connection_string.to_string();
         let connection_arc = Arc::clone(&self.connection);
@@ -138,11 +306,14 @@ impl Database for SqliteDatabase {
     }
 
     async fn disconnect(&self) -> Result<()> {
-        // Clear transaction flag to prevent stale state after reconnect
-        {
-            let mut in_transaction = self.in_transaction.lock().await;
-            *in_transaction = false;
-        }
+        // Clear transaction depth to prevent stale state after reconnect
+        let closed_levels = {
+            let mut transaction_depth = self.transaction_depth.lock().await;
+            *transaction_depth = 0;
+            self.abandoned.take();
+            self.callbacks.truncate(1)
+        };
+        run_rolled_back(closed_levels);
 
         let mut connection = self.connection.lock().await;
         *connection = None;
@@ -152,10 +323,13 @@ impl Database for SqliteDatabase {
     async fn execute(&self, query: &str) -> Result<u64> {
         let query = query.to_string();
         let connection_arc = Arc::clone(&self.connection);
+        let transaction_depth_arc = Arc::clone(&self.transaction_depth);
+        let abandoned = Arc::clone(&self.abandoned);
 
         // Offload blocking database operations to blocking thread pool with timeout
         let mut task = tokio::task::spawn_blocking(move || -> Result<u64> {
-            let connection = connection_arc.blocking_lock();
+            let connection =
+                Self::lock_connection(&connection_arc, &transaction_depth_arc, &abandoned)?;
             let conn = connection
                 .as_ref()
                 .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
@@ -176,26 +350,22 @@ impl Database for SqliteDatabase {
         }
     }
 
-    async fn query(&self, query: &str) -> Result<DatabaseResult> {
-        let query = query.to_string();
+    async fn execute_batch(&self, script: &str) -> Result<()> {
+        let script = script.to_string();
         let connection_arc = Arc::clone(&self.connection);
+        let transaction_depth_arc = Arc::clone(&self.transaction_depth);
+        let abandoned = Arc::clone(&self.abandoned);
 
         // Offload blocking database operations to blocking thread pool with timeout
-        let mut task = tokio::task::spawn_blocking(move || -> Result<DatabaseResult> {
-            let connection = connection_arc.blocking_lock();
+        let mut task = tokio::task::spawn_blocking(move || -> Result<()> {
+            let connection =
+                Self::lock_connection(&connection_arc, &transaction_depth_arc, &abandoned)?;
             let conn = connection
                 .as_ref()
                 .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
 
-            let mut stmt = conn.prepare(&query)?;
-            let rows = stmt.query_map([], Self::row_to_database_row)?;
-
-            let mut results = Vec::new();
-            for row_result in rows {
-                results.push(row_result?);
-            }
-
-            Ok(results)
+            conn.execute_batch(&script)?;
+            Ok(())
         });
 
         // Use select! to abort task on timeout, preventing resource leaks
@@ -210,38 +380,46 @@ impl Database for SqliteDatabase {
         }
     }
 
+    async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, &[])
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
     async fn query_with_params(
         &self,
         query: &str,
         params: &[DatabaseValue],
     ) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, params)
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.query_rows_with_params(query, &[]).await
+    }
+
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
         let query = query.to_string();
         let params = params.to_vec();
         let connection_arc = Arc::clone(&self.connection);
+        let transaction_depth_arc = Arc::clone(&self.transaction_depth);
+        let abandoned = Arc::clone(&self.abandoned);
 
         // Offload blocking database operations to blocking thread pool with timeout
-        let mut task = tokio::task::spawn_blocking(move || -> Result<DatabaseResult> {
-            let connection = connection_arc.blocking_lock();
+        let mut task = tokio::task::spawn_blocking(move || -> Result<ResultSet> {
+            let connection =
+                Self::lock_connection(&connection_arc, &transaction_depth_arc, &abandoned)?;
             let conn = connection
                 .as_ref()
                 .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
 
-            // Convert DatabaseValue to rusqlite parameters
-            let rusqlite_params: Vec<Box<dyn rusqlite::ToSql>> =
-                params.iter().map(Self::value_to_param).collect();
-
-            let mut stmt = conn.prepare(&query)?;
-            let rows = stmt.query_map(
-                params_from_iter(rusqlite_params.iter()),
-                Self::row_to_database_row,
-            )?;
-
-            let mut results = Vec::new();
-            for row_result in rows {
-                results.push(row_result?);
-            }
-
-            Ok(results)
+            Ok(Self::query_result_set(conn, &query, &params)?)
         });
 
         // Use select! to abort task on timeout, preventing resource leaks
@@ -256,14 +434,53 @@ impl Database for SqliteDatabase {
         }
     }
 
+    async fn query_stream_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<RowStream> {
+        let query = query.to_string();
+        let params = params.to_vec();
+        let connection_arc = Arc::clone(&self.connection);
+        let transaction_depth_arc = Arc::clone(&self.transaction_depth);
+        let abandoned = Arc::clone(&self.abandoned);
+        let (prepared_tx, prepared_rx) = oneshot::channel();
+        let (sender, stream) = row_channel();
+
+        // The blocking task holds the connection until the stream is finished or dropped
+        tokio::task::spawn_blocking(move || {
+            let connection =
+                match Self::lock_connection(&connection_arc, &transaction_depth_arc, &abandoned) {
+                    Ok(connection) => connection,
+                    Err(e) => {
+                        let _ = prepared_tx.send(Err(e));
+                        return;
+                    }
+                };
+            match connection.as_ref() {
+                Some(conn) => Self::send_rows(conn, &query, &params, prepared_tx, sender),
+                None => {
+                    let _ = prepared_tx
+                        .send(Err(DatabaseError::connection("Not connected to database")));
+                }
+            }
+        });
+
+        Self::await_prepared(prepared_rx, DEFAULT_OPERATION_TIMEOUT).await?;
+        Ok(stream)
+    }
+
     async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
         let query = query.to_string();
         let params = params.to_vec();
         let connection_arc = Arc::clone(&self.connection);
+        let transaction_depth_arc = Arc::clone(&self.transaction_depth);
+        let abandoned = Arc::clone(&self.abandoned);
 
         // Offload blocking database operations to blocking thread pool with timeout
         let mut task = tokio::task::spawn_blocking(move || -> Result<u64> {
-            let connection = connection_arc.blocking_lock();
+            let connection =
+                Self::lock_connection(&connection_arc, &transaction_depth_arc, &abandoned)?;
             let conn = connection
                 .as_ref()
                 .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
@@ -291,31 +508,43 @@ impl Database for SqliteDatabase {
     }
 
     async fn begin_transaction(&self) -> Result<()> {
+        self.begin_transaction_with_options(TransactionOptions::default())
+            .await
+    }
+
+    async fn begin_transaction_with_options(&self, options: TransactionOptions) -> Result<()> {
+        self.begin_transaction_level(options).await.map(|_| ())
+    }
+
+    async fn begin_transaction_level(&self, options: TransactionOptions) -> Result<usize> {
         let connection_arc = Arc::clone(&self.connection);
-        let in_transaction_arc = Arc::clone(&self.in_transaction);
+        let transaction_depth_arc = Arc::clone(&self.transaction_depth);
+        let abandoned = Arc::clone(&self.abandoned);
+        let callbacks = Arc::clone(&self.callbacks);
 
         // Offload blocking database operations to blocking thread pool with timeout
-        let mut task = tokio::task::spawn_blocking(move || -> Result<()> {
+        let mut task = tokio::task::spawn_blocking(move || -> Result<usize> {
             // Acquire both locks atomically to prevent race conditions
-            let mut in_transaction = in_transaction_arc.blocking_lock();
+            let mut transaction_depth = transaction_depth_arc.blocking_lock();
             let connection = connection_arc.blocking_lock();
 
             let conn = connection
                 .as_ref()
                 .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
-
-            // Check if already in transaction
-            if *in_transaction {
-                return Err(DatabaseError::transaction(
-                    "Already in a transaction".to_string(),
-                ));
+            Self::roll_back_abandoned(conn, &mut transaction_depth, &abandoned)?;
+
+            // Nested transactions are backed by savepoints
+            // Execute SQL first, only update depth on success
+            if *transaction_depth == 0 {
+                conn.execute_batch(&options.begin_statements(Dialect::Sqlite)?.join("; "))?;
+            } else {
+                options.ensure_nestable()?;
+                conn.execute_batch(&savepoint_sql(*transaction_depth))?;
             }
+            *transaction_depth += 1;
+            callbacks.push();
 
-            // Execute SQL first, only set flag on success
-            conn.execute("BEGIN TRANSACTION", [])?;
-            *in_transaction = true;
-
-            Ok(())
+            Ok(*transaction_depth)
         });
 
         // Use select! to abort task on timeout, preventing resource leaks
@@ -332,89 +561,135 @@ impl Database for SqliteDatabase {
 
     async fn commit(&self) -> Result<()> {
         let connection_arc = Arc::clone(&self.connection);
-        let in_transaction_arc = Arc::clone(&self.in_transaction);
+        let transaction_depth_arc = Arc::clone(&self.transaction_depth);
+        let abandoned = Arc::clone(&self.abandoned);
+        let callbacks = Arc::clone(&self.callbacks);
 
         // Offload blocking database operations to blocking thread pool with timeout
-        let mut task = tokio::task::spawn_blocking(move || -> Result<()> {
-            // Acquire both locks atomically to prevent race conditions
-            let mut in_transaction = in_transaction_arc.blocking_lock();
-            let connection = connection_arc.blocking_lock();
-
-            let conn = connection
-                .as_ref()
-                .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
-
-            // Check if in transaction
-            if !*in_transaction {
-                return Err(DatabaseError::transaction(
-                    "Not in a transaction".to_string(),
-                ));
-            }
+        let mut task =
+            tokio::task::spawn_blocking(move || -> Result<Option<Arc<TransactionCallbacks>>> {
+                // Acquire both locks atomically to prevent race conditions
+                let mut transaction_depth = transaction_depth_arc.blocking_lock();
+                let connection = connection_arc.blocking_lock();
+
+                let conn = connection
+                    .as_ref()
+                    .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+                Self::roll_back_abandoned(conn, &mut transaction_depth, &abandoned)?;
+
+                // Check if in transaction
+                if *transaction_depth == 0 {
+                    return Err(DatabaseError::transaction(
+                        "Not in a transaction".to_string(),
+                    ));
+                }
 
-            // Execute SQL first, only clear flag on success
-            conn.execute("COMMIT", [])?;
-            *in_transaction = false;
+                // Execute SQL first, only update depth on success
+                if *transaction_depth == 1 {
+                    conn.execute("COMMIT", [])?;
+                } else {
+                    conn.execute_batch(&release_savepoint_sql(*transaction_depth - 1))?;
+                }
+                *transaction_depth -= 1;
 
-            Ok(())
-        });
+                Ok(callbacks.commit())
+            });
 
         // Use select! to abort task on timeout, preventing resource leaks
-        tokio::select! {
+        let finished = tokio::select! {
             result = &mut task => {
-                result.map_err(|e| DatabaseError::other(format!("Task join error: {}", e)))?
+                result.map_err(|e| DatabaseError::other(format!("Task join error: {}", e)))??
             }
             _ = tokio::time::sleep(DEFAULT_OPERATION_TIMEOUT) => {
                 task.abort();
                 return Err(DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64));
             }
+        };
+
+        // Callbacks run once the connection is unlocked
+        if let Some(callbacks) = finished {
+            callbacks.committed();
         }
+        Ok(())
     }
 
     async fn rollback(&self) -> Result<()> {
         let connection_arc = Arc::clone(&self.connection);
-        let in_transaction_arc = Arc::clone(&self.in_transaction);
+        let transaction_depth_arc = Arc::clone(&self.transaction_depth);
+        let abandoned = Arc::clone(&self.abandoned);
+        let callbacks = Arc::clone(&self.callbacks);
 
         // Offload blocking database operations to blocking thread pool with timeout
-        let mut task = tokio::task::spawn_blocking(move || -> Result<()> {
-            // Acquire both locks atomically to prevent race conditions
-            let mut in_transaction = in_transaction_arc.blocking_lock();
-            let connection = connection_arc.blocking_lock();
-
-            let conn = connection
-                .as_ref()
-                .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
-
-            // Check if in transaction
-            if !*in_transaction {
-                return Err(DatabaseError::transaction(
-                    "Not in a transaction".to_string(),
-                ));
-            }
+        let mut task =
+            tokio::task::spawn_blocking(move || -> Result<Option<Arc<TransactionCallbacks>>> {
+                // Acquire both locks atomically to prevent race conditions
+                let mut transaction_depth = transaction_depth_arc.blocking_lock();
+                let connection = connection_arc.blocking_lock();
+
+                let conn = connection
+                    .as_ref()
+                    .ok_or_else(|| DatabaseError::connection("Not connected to database"))?;
+                Self::roll_back_abandoned(conn, &mut transaction_depth, &abandoned)?;
+
+                // Check if in transaction
+                if *transaction_depth == 0 {
+                    return Err(DatabaseError::transaction(
+                        "Not in a transaction".to_string(),
+                    ));
+                }
 
-            // Execute SQL first, only clear flag on success
-            conn.execute("ROLLBACK", [])?;
-            *in_transaction = false;
+                // Execute SQL first, only update depth on success
+                if *transaction_depth == 1 {
+                    conn.execute("ROLLBACK", [])?;
+                } else {
+                    for statement in rollback_to_savepoint_sql(*transaction_depth - 1) {
+                        conn.execute_batch(&statement)?;
+                    }
+                }
+                *transaction_depth -= 1;
 
-            Ok(())
-        });
+                Ok(callbacks.rollback())
+            });
 
         // Use select! to abort task on timeout, preventing resource leaks
-        tokio::select! {
+        let finished = tokio::select! {
             result = &mut task => {
-                result.map_err(|e| DatabaseError::other(format!("Task join error: {}", e)))?
+                result.map_err(|e| DatabaseError::other(format!("Task join error: {}", e)))??
             }
             _ = tokio::time::sleep(DEFAULT_OPERATION_TIMEOUT) => {
                 task.abort();
                 return Err(DatabaseError::query_timeout(DEFAULT_OPERATION_TIMEOUT.as_millis() as u64));
             }
+        };
+
+        // Callbacks run once the connection is unlocked
+        if let Some(callbacks) = finished {
+            callbacks.rolled_back();
         }
+        Ok(())
     }
 
     fn in_transaction(&self) -> bool {
-        self.in_transaction
+        self.transaction_depth() > 0
+    }
+
+    fn transaction_depth(&self) -> usize {
+        let depth = self
+            .transaction_depth
             .try_lock()
             .map(|guard| *guard)
-            .unwrap_or(false)
+            .unwrap_or(0);
+        self.abandoned.effective_depth(depth)
+    }
+
+    fn defer_rollback(&self, depth: usize) -> bool {
+        self.abandoned.mark(depth);
+        run_rolled_back(self.callbacks.truncate(depth));
+        true
+    }
+
+    fn transaction_callbacks(&self, depth: usize) -> Option<Arc<TransactionCallbacks>> {
+        self.callbacks.level(depth)
     }
 }
 
@@ -423,8 +698,8 @@ impl Drop for SqliteDatabase {
     fn drop(&mut self) {
         // Attempt to rollback any open transaction
         // Note: This is best-effort cleanup since Drop cannot be async
-        if let Ok(in_trans) = self.in_transaction.try_lock() {
-            if *in_trans {
+        if let Ok(transaction_depth) = self.transaction_depth.try_lock() {
+            if *transaction_depth > 0 {
                 if let Ok(connection) = self.connection.try_lock() {
                     if let Some(conn) = connection.as_ref() {
                         let _ = conn.execute("ROLLBACK", []);
@@ -526,4 +801,72 @@ mod tests {
 
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_sqlite_transaction_closure() -> Result<()> {
+        let db = SqliteDatabase::new();
+        db.connect(":memory:").await?;
+        db.execute("CREATE TABLE test (id INTEGER PRIMARY KEY, name TEXT)")
+            .await?;
+
+        // transaction() hands the closure the database itself
+        let inserted = db
+            .transaction(|db| {
+                Box::pin(
+                    async move { db.execute("INSERT INTO test (name) VALUES ('Alice')").await },
+                )
+            })
+            .await?;
+        assert_eq!(inserted, 1);
+
+        let failed: Result<u64> = db
+            .transaction(|db| {
+                Box::pin(async move {
+                    db.execute("INSERT INTO test (name) VALUES ('Bob')").await?;
+                    Err(DatabaseError::QueryError("abort".to_string()))
+                })
+            })
+            .await;
+        assert!(failed.is_err());
+        assert!(!db.in_transaction());
+        assert_eq!(db.query("SELECT * FROM test").await?.len(), 1);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_begin_transaction_level() -> Result<()> {
+        let db = SqliteDatabase::new();
+        db.connect(":memory:").await?;
+
+        assert_eq!(
+            db.begin_transaction_level(TransactionOptions::default())
+                .await?,
+            1
+        );
+        assert_eq!(
+            db.begin_transaction_level(TransactionOptions::default())
+                .await?,
+            2
+        );
+
+        // transaction_depth() cannot wait for the lock and reads 0 while it is held
+        {
+            let _held = db.transaction_depth.lock().await;
+            assert_eq!(db.transaction_depth(), 0);
+        }
+        assert_eq!(db.transaction_depth(), 2);
+
+        db.commit().await?;
+        assert_eq!(
+            db.begin_transaction_level(TransactionOptions::default())
+                .await?,
+            2
+        );
+        db.rollback().await?;
+        db.rollback().await?;
+        assert_eq!(db.transaction_depth(), 0);
+
+        Ok(())
+    }
 }
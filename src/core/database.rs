@@ -3,9 +3,18 @@
 //! This module defines the core database trait that all database backends must implement.
 
 use super::database_types::DatabaseType;
-use super::error::Result;
+use super::dialect::Dialect;
+use super::error::{DatabaseError, Result};
+use super::result_set::{ResultSet, RowStream};
+use super::retry::{Retried, RetryPolicy};
+use super::row::FromRow;
+use super::script::split_statements;
+use super::transaction::{
+    SharedTransaction, Transaction, TransactionCallbacks, TransactionOptions,
+};
 use super::value::{DatabaseResult, DatabaseValue};
 use async_trait::async_trait;
+use std::sync::Arc;
 
 /// Core database trait that all database backends must implement
 #[async_trait]
@@ -105,20 +114,249 @@ pub trait Database: Send + Sync {
     /// Safe to call concurrently from multiple threads. Operations are serialized internally.
     async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64>;
 
+    /// Execute a script of several statements separated by semicolons
+    ///
+    /// SQLite and PostgreSQL hand the whole script to the driver; the default
+    /// splits it with [`split_statements`] and executes the statements in order,
+    /// stopping at the first error. Like `execute()`, this runs raw SQL.
+    ///
+    /// Statements before a failing one stay applied unless the script runs
+    /// inside a transaction.
+    async fn execute_batch(&self, script: &str) -> Result<()> {
+        let dialect = Dialect::from_database_type(self.database_type()).unwrap_or_default();
+        for statement in split_statements(script, dialect) {
+            self.execute(statement).await?;
+        }
+        Ok(())
+    }
+
+    /// Execute a SELECT query and return ordered rows with column metadata
+    ///
+    /// Backends with ordered results override this; the default converts the
+    /// result of `query()`. Like `query()`, this executes raw SQL.
+    async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.query(query).await.map(ResultSet::from_database_result)
+    }
+
+    /// Execute a parameterized SELECT query and return ordered rows with column metadata
+    ///
+    /// Backends with ordered results override this; the default converts the
+    /// result of `query_with_params()`.
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        self.query_with_params(query, params)
+            .await
+            .map(ResultSet::from_database_result)
+    }
+
+    /// Execute a SELECT query and stream its rows
+    ///
+    /// Like `query()`, this executes raw SQL; use `query_stream_with_params()` for
+    /// user input.
+    async fn query_stream(&self, query: &str) -> Result<RowStream> {
+        self.query_stream_with_params(query, &[]).await
+    }
+
+    /// Execute a parameterized SELECT query and stream its rows
+    ///
+    /// Rows are fetched as the stream is polled instead of being collected up
+    /// front, so arbitrarily large results can be processed in bounded memory.
+    /// SQL errors are returned from this call; errors while reading rows end the
+    /// stream with an `Err` item.
+    ///
+    /// The default buffers the whole result of `query_rows_with_params()`;
+    /// SQLite and PostgreSQL backends stream natively.
+    ///
+    /// # Connection Use
+    ///
+    /// The connection stays busy until the stream is exhausted or dropped, so
+    /// other operations on the same database wait for it. Finish or drop the
+    /// stream before issuing further queries on this connection.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// use futures::TryStreamExt;
+    ///
+    /// let mut rows = db.query_stream_with_params("SELECT * FROM events WHERE kind = ?", &["click".into()]).await?;
+    /// while let Some(row) = rows.try_next().await? {
+    ///     let id: i64 = row.try_get("id")?;
+    /// }
+    /// ```
+    async fn query_stream_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<RowStream> {
+        let rows = self.query_rows_with_params(query, params).await?;
+        Ok(Box::pin(futures::stream::iter(rows.into_iter().map(Ok))))
+    }
+
+    /// Execute a SELECT query and decode every row into `T`
+    ///
+    /// # Security Warning
+    ///
+    /// Like `query()`, this executes raw SQL; use `query_as_with_params()` for user input.
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ColumnNotFound` or `TypeMismatch` if a row does not match `T`.
+    async fn query_as<T>(&self, query: &str) -> Result<Vec<T>>
+    where
+        T: FromRow + Send,
+    {
+        let rows = self.query(query).await?;
+        rows.iter().map(T::from_row).collect()
+    }
+
+    /// Execute a parameterized SELECT query and decode every row into `T`
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// #[derive(FromRow)]
+    /// struct User {
+    ///     id: i64,
+    ///     name: String,
+    /// }
+    ///
+    /// let users: Vec<User> = db
+    ///     .query_as_with_params("SELECT id, name FROM users WHERE age > ?", &[18.into()])
+    ///     .await?;
+    /// ```
+    ///
+    /// # Errors
+    ///
+    /// Fails with `ColumnNotFound` or `TypeMismatch` if a row does not match `T`.
+    async fn query_as_with_params<T>(&self, query: &str, params: &[DatabaseValue]) -> Result<Vec<T>>
+    where
+        T: FromRow + Send,
+    {
+        let rows = self.query_with_params(query, params).await?;
+        rows.iter().map(T::from_row).collect()
+    }
+
     /// Begin a transaction
     ///
+    /// On SQL backends, calling this while a transaction is active opens a nested
+    /// transaction backed by a SAVEPOINT; `commit()` and `rollback()` then apply to
+    /// the innermost level.
+    ///
     /// # Thread Safety
-    /// Safe to call concurrently. Only one transaction can be active at a time per connection.
+    /// Safe to call concurrently. Nesting levels are tracked per connection, not per task.
     async fn begin_transaction(&self) -> Result<()>;
 
+    /// Begin a transaction with isolation level, access mode or locking options
+    ///
+    /// With default options this is the same as [`begin_transaction()`](Self::begin_transaction).
+    /// The default implementation rejects any other options; SQL backends override
+    /// it and render them into their BEGIN statement.
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedOperation` if the backend cannot honour the options, or
+    /// if options are given for a nested transaction
+    async fn begin_transaction_with_options(&self, options: TransactionOptions) -> Result<()> {
+        if !options.is_default() {
+            return Err(DatabaseError::unsupported(format!(
+                "Transaction options are not supported by {}",
+                self.database_type().to_str()
+            )));
+        }
+        self.begin_transaction().await
+    }
+
+    /// Begin a transaction level and return its depth (1 for the outermost
+    /// transaction)
+    ///
+    /// Like [`begin_transaction_with_options()`](Self::begin_transaction_with_options),
+    /// but the depth is read while BEGIN or SAVEPOINT still holds the backend's
+    /// transaction state. [`transaction_depth()`](Self::transaction_depth) cannot
+    /// wait for that state and may be stale while another operation holds it, so
+    /// code that must know which level it opened uses this instead. SQL backends
+    /// override it; the default reads `transaction_depth()` after beginning.
+    async fn begin_transaction_level(&self, options: TransactionOptions) -> Result<usize> {
+        self.begin_transaction_with_options(options).await?;
+        Ok(self.transaction_depth().max(1))
+    }
+
+    /// Begin a transaction bound to one connection
+    ///
+    /// Unlike [`begin_transaction()`](Self::begin_transaction), the returned
+    /// [`Transaction`] runs every statement on the connection it started on, so
+    /// generic code behaves the same on single-connection and pooled backends.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the transaction cannot be started, or
+    /// `UnsupportedOperation` if the backend has no connection-bound transactions
+    async fn begin<'a>(&'a self) -> Result<Box<dyn Transaction + 'a>>
+    where
+        Self: Sized,
+    {
+        self.begin_with_options(TransactionOptions::default()).await
+    }
+
+    /// Begin a transaction bound to one connection with isolation level, access
+    /// mode or locking options
+    ///
+    /// The default implementation opens a level with
+    /// [`begin_transaction_with_options()`](Self::begin_transaction_with_options)
+    /// on the backend's own connection and commits or rolls it back through
+    /// [`commit()`](Self::commit) and [`rollback()`](Self::rollback); dropping
+    /// the transaction unfinished hands its level to
+    /// [`defer_rollback()`](Self::defer_rollback). Pooled backends override it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`begin()`](Self::begin) would, or `UnsupportedOperation`
+    /// if the backend cannot honour the options
+    async fn begin_with_options<'a>(
+        &'a self,
+        options: TransactionOptions,
+    ) -> Result<Box<dyn Transaction + 'a>>
+    where
+        Self: Sized,
+    {
+        Ok(Box::new(SharedTransaction::begin(self, options).await?))
+    }
+
+    /// Begin a connection-bound transaction that does not borrow the database
+    ///
+    /// Pooled backends keep no transaction state on the database, so a
+    /// [`TransactionGuard`](super::transaction::TransactionGuard) runs its
+    /// statements on the returned transaction instead. The default returns
+    /// `None`, and the guard uses [`begin_transaction()`](Self::begin_transaction),
+    /// [`commit()`](Self::commit) and [`rollback()`](Self::rollback).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`begin_with_options()`](Self::begin_with_options) would
+    async fn begin_owned(
+        &self,
+        options: TransactionOptions,
+    ) -> Result<Option<Box<dyn Transaction>>> {
+        let _ = options;
+        Ok(None)
+    }
+
     /// Commit the current transaction
     ///
+    /// Inside a nested transaction this releases its savepoint; the work becomes
+    /// part of the enclosing transaction.
+    ///
     /// # Thread Safety
     /// Safe to call concurrently, though only meaningful if a transaction is active.
     async fn commit(&self) -> Result<()>;
 
     /// Rollback the current transaction
     ///
+    /// Inside a nested transaction this rolls back to its savepoint, leaving the
+    /// enclosing transaction active.
+    ///
     /// # Thread Safety
     /// Safe to call concurrently, though only meaningful if a transaction is active.
     async fn rollback(&self) -> Result<()>;
@@ -126,6 +364,42 @@ pub trait Database: Send + Sync {
     /// Check if currently in a transaction
     fn in_transaction(&self) -> bool;
 
+    /// Number of open transaction levels (0 outside a transaction)
+    ///
+    /// Backends that cannot nest transactions report at most 1.
+    fn transaction_depth(&self) -> usize {
+        usize::from(self.in_transaction())
+    }
+
+    /// Roll back transaction level `depth` before the next operation
+    ///
+    /// Called by a [`TransactionGuard`](super::transaction::TransactionGuard)
+    /// dropped without commit or rollback, where no async code can run. Backends
+    /// that support it record `depth` (1 for the outermost transaction) and roll
+    /// that level and every level inside it back at the start of their next
+    /// operation; [`transaction_depth()`](Self::transaction_depth) reflects the
+    /// rollback at once.
+    ///
+    /// Returns `false` if the backend cannot defer a rollback, which is the
+    /// default.
+    fn defer_rollback(&self, depth: usize) -> bool {
+        let _ = depth;
+        false
+    }
+
+    /// Callbacks of open transaction level `depth` (1 for the outermost
+    /// transaction)
+    ///
+    /// Backends that nest transactions keep callbacks per level and run them
+    /// once the outcome of the outermost transaction is known: committing a
+    /// savepoint hands its callbacks to the enclosing level. The default
+    /// returns `None`, and [`TransactionGuard`](super::transaction::TransactionGuard)
+    /// runs its callbacks itself.
+    fn transaction_callbacks(&self, depth: usize) -> Option<Arc<TransactionCallbacks>> {
+        let _ = depth;
+        None
+    }
+
     /// Execute multiple queries in a transaction
     ///
     /// # Note
@@ -133,8 +407,9 @@ pub trait Database: Send + Sync {
     /// Due to the generic nature of this method, it makes the Database trait not object-safe,
     /// preventing use in trait objects (e.g., `Box<dyn Database>`).
     ///
-    /// For applications needing connection pooling or trait objects, use the manual
-    /// begin_transaction/commit/rollback methods instead.
+    /// For applications needing connection pooling, use
+    /// [`bound_transaction()`](Self::bound_transaction); for trait objects, use the
+    /// manual begin_transaction/commit/rollback methods instead.
     ///
     /// # Thread Safety
     /// Safe to call concurrently. Each invocation gets its own transaction scope.
@@ -160,6 +435,171 @@ pub trait Database: Send + Sync {
             }
         }
     }
+
+    /// Execute multiple queries in a connection-bound transaction
+    ///
+    /// Like [`transaction()`](Self::transaction), but `f` receives the
+    /// [`Transaction`] from [`begin()`](Self::begin) instead of the database, so
+    /// this works the same on single-connection and pooled backends. The
+    /// transaction commits if `f` succeeds and rolls back if it fails.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let updated = pool
+    ///     .bound_transaction(|tx| {
+    ///         Box::pin(async move { tx.execute("UPDATE counters SET n = n + 1").await })
+    ///     })
+    ///     .await?;
+    /// ```
+    async fn bound_transaction<F, T>(&self, f: F) -> Result<T>
+    where
+        F: for<'a> FnOnce(
+                &'a dyn Transaction,
+            ) -> std::pin::Pin<
+                Box<dyn std::future::Future<Output = Result<T>> + Send + 'a>,
+            > + Send,
+        T: Send,
+        Self: Sized,
+    {
+        let tx = self.begin().await?;
+
+        match f(&*tx).await {
+            Ok(result) => {
+                tx.commit().await?;
+                Ok(result)
+            }
+            Err(e) => {
+                let _ = tx.rollback().await;
+                Err(e)
+            }
+        }
+    }
+
+    /// Execute a transaction, running it again from scratch on retryable errors
+    ///
+    /// Each attempt begins a transaction with [`begin()`](Self::begin), runs `f`
+    /// and commits; if BEGIN, `f` or the commit fails, the attempt is rolled
+    /// back and `policy` decides whether to wait and try again. The returned
+    /// [`Retried`] reports how many attempts were made.
+    ///
+    /// A conflict aborts a whole transaction, so retrying a savepoint cannot
+    /// help: inside an enclosing transaction `f` runs once and errors are
+    /// returned unchanged for the outermost caller to retry.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error `policy` does not retry, or `RetriesExhausted`
+    /// wrapping the last error once every attempt failed
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let outcome = db
+    ///     .transaction_with_retry(&RetryPolicy::new(), |tx| {
+    ///         Box::pin(async move {
+    ///             tx.execute("UPDATE accounts SET balance = balance - 10 WHERE id = 1")
+    ///                 .await
+    ///         })
+    ///     })
+    ///     .await?;
+    /// println!("updated {} rows in {} attempts", outcome.value, outcome.attempts);
+    /// ```
+    async fn transaction_with_retry<F, T>(&self, policy: &RetryPolicy, f: F) -> Result<Retried<T>>
+    where
+        F: for<'a> FnMut(
+                &'a dyn Transaction,
+            ) -> std::pin::Pin<
+                Box<dyn std::future::Future<Output = Result<T>> + Send + 'a>,
+            > + Send,
+        T: Send,
+        Self: Sized,
+    {
+        self.transaction_with_retry_options(policy, TransactionOptions::default(), f)
+            .await
+    }
+
+    /// Execute a transaction started with `options`, running it again from
+    /// scratch on retryable errors
+    ///
+    /// Like [`transaction_with_retry()`](Self::transaction_with_retry). On SQLite,
+    /// [`LockingMode::Immediate`](super::transaction::LockingMode::Immediate) takes
+    /// the write lock at BEGIN, so a busy database fails the BEGIN, where a
+    /// retry is cheap, instead of the COMMIT after all the work was done.
+    ///
+    /// # Errors
+    ///
+    /// Returns what [`transaction_with_retry()`](Self::transaction_with_retry)
+    /// would, or `UnsupportedOperation` if the backend cannot honour the
+    /// options or they are given inside an enclosing transaction
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let options = TransactionOptions::new().with_locking_mode(LockingMode::Immediate);
+    /// db.transaction_with_retry_options(&RetryPolicy::new(), options, |tx| {
+    ///     Box::pin(async move { tx.execute("UPDATE counters SET n = n + 1").await })
+    /// })
+    /// .await?;
+    /// ```
+    async fn transaction_with_retry_options<F, T>(
+        &self,
+        policy: &RetryPolicy,
+        options: TransactionOptions,
+        mut f: F,
+    ) -> Result<Retried<T>>
+    where
+        F: for<'a> FnMut(
+                &'a dyn Transaction,
+            ) -> std::pin::Pin<
+                Box<dyn std::future::Future<Output = Result<T>> + Send + 'a>,
+            > + Send,
+        T: Send,
+        Self: Sized,
+    {
+        if self.in_transaction() {
+            let tx = self.begin_with_options(options).await?;
+            return match f(&*tx).await {
+                Ok(value) => {
+                    tx.commit().await?;
+                    Ok(Retried { value, attempts: 1 })
+                }
+                Err(e) => {
+                    let _ = tx.rollback().await;
+                    Err(e)
+                }
+            };
+        }
+
+        let mut attempt = 1;
+        loop {
+            // A busy database often fails the BEGIN itself, so it is retried too
+            let result = match self.begin_with_options(options).await {
+                Ok(tx) => match f(&*tx).await {
+                    Ok(value) => tx.commit().await.map(|()| value),
+                    Err(e) => {
+                        let _ = tx.rollback().await;
+                        Err(e)
+                    }
+                },
+                Err(e) => Err(e),
+            };
+
+            match result {
+                Ok(value) => {
+                    return Ok(Retried {
+                        value,
+                        attempts: attempt,
+                    })
+                }
+                Err(e) => {
+                    let delay = policy.on_failure(attempt, e)?;
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
 }
 
 /// Object-safe version of the Database trait
@@ -204,9 +644,53 @@ pub trait DatabaseObject: Send + Sync {
     /// Execute a query with parameters that doesn't return results
     async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64>;
 
+    /// Execute a script of several statements separated by semicolons
+    async fn execute_batch(&self, script: &str) -> Result<()>;
+
+    /// Execute a SELECT query and return ordered rows with column metadata
+    async fn query_rows(&self, query: &str) -> Result<ResultSet>;
+
+    /// Execute a parameterized SELECT query and return ordered rows with column metadata
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet>;
+
+    /// Execute a SELECT query and stream its rows
+    async fn query_stream(&self, query: &str) -> Result<RowStream>;
+
+    /// Execute a parameterized SELECT query and stream its rows
+    async fn query_stream_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<RowStream>;
+
     /// Begin a transaction
     async fn begin_transaction(&self) -> Result<()>;
 
+    /// Begin a transaction with isolation level, access mode or locking options
+    async fn begin_transaction_with_options(&self, options: TransactionOptions) -> Result<()>;
+
+    /// Begin a transaction level and return its depth
+    async fn begin_transaction_level(&self, options: TransactionOptions) -> Result<usize>;
+
+    /// Begin a transaction bound to one connection
+    async fn begin<'a>(&'a self) -> Result<Box<dyn Transaction + 'a>>;
+
+    /// Begin a transaction bound to one connection with options
+    async fn begin_with_options<'a>(
+        &'a self,
+        options: TransactionOptions,
+    ) -> Result<Box<dyn Transaction + 'a>>;
+
+    /// Begin a connection-bound transaction that does not borrow the database
+    async fn begin_owned(
+        &self,
+        options: TransactionOptions,
+    ) -> Result<Option<Box<dyn Transaction>>>;
+
     /// Commit the current transaction
     async fn commit(&self) -> Result<()>;
 
@@ -215,6 +699,15 @@ pub trait DatabaseObject: Send + Sync {
 
     /// Check if currently in a transaction
     fn in_transaction(&self) -> bool;
+
+    /// Number of open transaction levels (0 outside a transaction)
+    fn transaction_depth(&self) -> usize;
+
+    /// Roll back transaction level `depth` before the next operation
+    fn defer_rollback(&self, depth: usize) -> bool;
+
+    /// Callbacks of open transaction level `depth`
+    fn transaction_callbacks(&self, depth: usize) -> Option<Arc<TransactionCallbacks>>;
 }
 
 /// Blanket implementation of DatabaseObject for all types implementing Database
@@ -258,10 +751,64 @@ impl<T: Database> DatabaseObject for T {
         Database::execute_with_params(self, query, params).await
     }
 
+    async fn execute_batch(&self, script: &str) -> Result<()> {
+        Database::execute_batch(self, script).await
+    }
+
+    async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        Database::query_rows(self, query).await
+    }
+
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        Database::query_rows_with_params(self, query, params).await
+    }
+
+    async fn query_stream(&self, query: &str) -> Result<RowStream> {
+        Database::query_stream(self, query).await
+    }
+
+    async fn query_stream_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<RowStream> {
+        Database::query_stream_with_params(self, query, params).await
+    }
+
     async fn begin_transaction(&self) -> Result<()> {
         Database::begin_transaction(self).await
     }
 
+    async fn begin_transaction_with_options(&self, options: TransactionOptions) -> Result<()> {
+        Database::begin_transaction_with_options(self, options).await
+    }
+
+    async fn begin_transaction_level(&self, options: TransactionOptions) -> Result<usize> {
+        Database::begin_transaction_level(self, options).await
+    }
+
+    async fn begin<'a>(&'a self) -> Result<Box<dyn Transaction + 'a>> {
+        Database::begin(self).await
+    }
+
+    async fn begin_with_options<'a>(
+        &'a self,
+        options: TransactionOptions,
+    ) -> Result<Box<dyn Transaction + 'a>> {
+        Database::begin_with_options(self, options).await
+    }
+
+    async fn begin_owned(
+        &self,
+        options: TransactionOptions,
+    ) -> Result<Option<Box<dyn Transaction>>> {
+        Database::begin_owned(self, options).await
+    }
+
     async fn commit(&self) -> Result<()> {
         Database::commit(self).await
     }
@@ -273,6 +820,40 @@ impl<T: Database> DatabaseObject for T {
     fn in_transaction(&self) -> bool {
         Database::in_transaction(self)
     }
+
+    fn transaction_depth(&self) -> usize {
+        Database::transaction_depth(self)
+    }
+
+    fn defer_rollback(&self, depth: usize) -> bool {
+        Database::defer_rollback(self, depth)
+    }
+
+    fn transaction_callbacks(&self, depth: usize) -> Option<Arc<TransactionCallbacks>> {
+        Database::transaction_callbacks(self, depth)
+    }
+}
+
+impl dyn DatabaseObject + '_ {
+    /// Execute a SELECT query and decode every row into `T`
+    ///
+    /// Trait-object counterpart of [`Database::query_as`].
+    pub async fn query_as<T: FromRow>(&self, query: &str) -> Result<Vec<T>> {
+        let rows = self.query(query).await?;
+        rows.iter().map(T::from_row).collect()
+    }
+
+    /// Execute a parameterized SELECT query and decode every row into `T`
+    ///
+    /// Trait-object counterpart of [`Database::query_as_with_params`].
+    pub async fn query_as_with_params<T: FromRow>(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<Vec<T>> {
+        let rows = self.query_with_params(query, params).await?;
+        rows.iter().map(T::from_row).collect()
+    }
 }
 
 /// Trait for database connection pooling
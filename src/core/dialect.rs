@@ -0,0 +1,253 @@
+//! SQL dialects
+//!
+//! The query builders render the same expression differently depending on the
+//! target database: placeholder style, identifier quoting, LIMIT/OFFSET syntax and
+//! boolean literals all vary between SQLite, PostgreSQL and MySQL.
+
+use super::database_types::DatabaseType;
+
+/// Words that must be quoted when used as identifiers in any supported dialect
+const RESERVED_WORDS: &[&str] = &[
+    "ALL",
+    "AND",
+    "AS",
+    "ASC",
+    "BETWEEN",
+    "BY",
+    "CASE",
+    "CHECK",
+    "COLUMN",
+    "CONSTRAINT",
+    "CREATE",
+    "CROSS",
+    "DEFAULT",
+    "DELETE",
+    "DESC",
+    "DISTINCT",
+    "DROP",
+    "ELSE",
+    "END",
+    "EXCEPT",
+    "EXISTS",
+    "FALSE",
+    "FETCH",
+    "FOR",
+    "FOREIGN",
+    "FROM",
+    "FULL",
+    "GROUP",
+    "HAVING",
+    "IN",
+    "INDEX",
+    "INNER",
+    "INSERT",
+    "INTERSECT",
+    "INTO",
+    "IS",
+    "JOIN",
+    "KEY",
+    "LEFT",
+    "LIKE",
+    "LIMIT",
+    "NATURAL",
+    "NOT",
+    "NULL",
+    "OFFSET",
+    "ON",
+    "OR",
+    "ORDER",
+    "OUTER",
+    "PRIMARY",
+    "REFERENCES",
+    "RIGHT",
+    "SELECT",
+    "SET",
+    "TABLE",
+    "THEN",
+    "TO",
+    "TRUE",
+    "UNION",
+    "UNIQUE",
+    "UPDATE",
+    "USER",
+    "USING",
+    "VALUES",
+    "WHEN",
+    "WHERE",
+    "WITH",
+];
+
+/// Largest row count MySQL accepts in a LIMIT clause, used for OFFSET without LIMIT
+const MYSQL_MAX_LIMIT: &str = "18446744073709551615";
+
+/// SQL dialect the query builders render against
+///
+/// # Example
+///
+/// ```
+/// use rust_database_system::core::dialect::Dialect;
+/// use rust_database_system::core::query_builder::SelectBuilder;
+///
+/// let query = SelectBuilder::new("users").where_eq("id", 42).limit(10);
+///
+/// assert_eq!(
+///     query.build_for(Dialect::Postgres),
+///     "SELECT * FROM users WHERE id = $1 LIMIT 10"
+/// );
+/// assert_eq!(
+///     query.build_for(Dialect::Mysql),
+///     "SELECT * FROM users WHERE id = ? LIMIT 10"
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum Dialect {
+    /// SQLite: `?` placeholders, `"double quoted"` identifiers
+    #[default]
+    Sqlite,
+    /// PostgreSQL: `$1, $2, ...` placeholders, `"double quoted"` identifiers
+    Postgres,
+    /// MySQL/MariaDB: `?` placeholders, `` `backtick quoted` `` identifiers
+    Mysql,
+}
+
+impl Dialect {
+    /// Get the dialect for a database type
+    ///
+    /// Returns `None` for databases that do not speak SQL through the builders.
+    pub fn from_database_type(database_type: DatabaseType) -> Option<Self> {
+        match database_type {
+            DatabaseType::Sqlite => Some(Dialect::Sqlite),
+            DatabaseType::Postgres => Some(Dialect::Postgres),
+            DatabaseType::Mysql => Some(Dialect::Mysql),
+            _ => None,
+        }
+    }
+
+    /// Render the placeholder for the `index`-th parameter (1-based)
+    pub fn placeholder(&self, index: usize) -> String {
+        match self {
+            Dialect::Postgres => format!("${}", index),
+            Dialect::Sqlite | Dialect::Mysql => "?".to_string(),
+        }
+    }
+
+    /// Character used to quote identifiers
+    fn quote_char(&self) -> char {
+        match self {
+            Dialect::Mysql => '`',
+            Dialect::Sqlite | Dialect::Postgres => '"',
+        }
+    }
+
+    /// Quote an identifier unconditionally, escaping embedded quote characters
+    pub fn quote_identifier(&self, name: &str) -> String {
+        let quote = self.quote_char();
+        let escaped = name.replace(quote, &format!("{}{}", quote, quote));
+        format!("{}{}{}", quote, escaped, quote)
+    }
+
+    /// Check whether a name is an identifier that can be written without quotes
+    ///
+    /// Reserved words always need quoting. PostgreSQL folds unquoted names to
+    /// lowercase, so names containing uppercase letters need quoting there too.
+    pub fn is_bare_identifier(&self, name: &str) -> bool {
+        let mut chars = name.chars();
+        let starts_ok = chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '_');
+        if !starts_ok || !chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+            return false;
+        }
+
+        if *self == Dialect::Postgres && name.chars().any(|c| c.is_ascii_uppercase()) {
+            return false;
+        }
+
+        !RESERVED_WORDS
+            .iter()
+            .any(|word| word.eq_ignore_ascii_case(name))
+    }
+
+    /// Render the LIMIT/OFFSET clause (with a leading space), or an empty string
+    ///
+    /// SQLite and MySQL cannot express OFFSET without LIMIT, so an unbounded
+    /// limit is emitted in that case.
+    pub fn limit_offset(&self, limit: Option<usize>, offset: Option<usize>) -> String {
+        match (limit, offset) {
+            (None, None) => String::new(),
+            (Some(limit), None) => format!(" LIMIT {}", limit),
+            (Some(limit), Some(offset)) => format!(" LIMIT {} OFFSET {}", limit, offset),
+            (None, Some(offset)) => match self {
+                Dialect::Postgres => format!(" OFFSET {}", offset),
+                Dialect::Sqlite => format!(" LIMIT -1 OFFSET {}", offset),
+                Dialect::Mysql => format!(" LIMIT {} OFFSET {}", MYSQL_MAX_LIMIT, offset),
+            },
+        }
+    }
+
+    /// Render a boolean literal
+    ///
+    /// SQLite has no boolean type and stores booleans as 1/0.
+    pub fn boolean_literal(&self, value: bool) -> &'static str {
+        match (self, value) {
+            (Dialect::Sqlite, true) => "1",
+            (Dialect::Sqlite, false) => "0",
+            (_, true) => "TRUE",
+            (_, false) => "FALSE",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_placeholder() {
+        assert_eq!(Dialect::Sqlite.placeholder(3), "?");
+        assert_eq!(Dialect::Mysql.placeholder(3), "?");
+        assert_eq!(Dialect::Postgres.placeholder(3), "$3");
+    }
+
+    #[test]
+    fn test_quote_identifier() {
+        assert_eq!(Dialect::Sqlite.quote_identifier("order"), "\"order\"");
+        assert_eq!(Dialect::Mysql.quote_identifier("order"), "`order`");
+        assert_eq!(Dialect::Postgres.quote_identifier("a\"b"), "\"a\"\"b\"");
+        assert_eq!(Dialect::Mysql.quote_identifier("a`b"), "`a``b`");
+    }
+
+    #[test]
+    fn test_limit_offset() {
+        assert_eq!(Dialect::Sqlite.limit_offset(None, None), "");
+        assert_eq!(
+            Dialect::Postgres.limit_offset(Some(10), Some(5)),
+            " LIMIT 10 OFFSET 5"
+        );
+        assert_eq!(Dialect::Postgres.limit_offset(None, Some(5)), " OFFSET 5");
+        assert_eq!(
+            Dialect::Sqlite.limit_offset(None, Some(5)),
+            " LIMIT -1 OFFSET 5"
+        );
+        assert_eq!(
+            Dialect::Mysql.limit_offset(None, Some(5)),
+            " LIMIT 18446744073709551615 OFFSET 5"
+        );
+    }
+
+    #[test]
+    fn test_boolean_literal() {
+        assert_eq!(Dialect::Sqlite.boolean_literal(true), "1");
+        assert_eq!(Dialect::Postgres.boolean_literal(false), "FALSE");
+        assert_eq!(Dialect::Mysql.boolean_literal(true), "TRUE");
+    }
+
+    #[test]
+    fn test_from_database_type() {
+        assert_eq!(
+            Dialect::from_database_type(DatabaseType::Postgres),
+            Some(Dialect::Postgres)
+        );
+        assert_eq!(Dialect::from_database_type(DatabaseType::Redis), None);
+    }
+}
@@ -58,10 +58,31 @@ pub enum DatabaseError {
     #[error("Column not found: {0}")]
     ColumnNotFound(String),
 
+    /// Invalid SQL identifier
+    #[error("Invalid identifier: {0}")]
+    InvalidIdentifier(String),
+
     /// Transaction error
     #[error("Transaction error: {0}")]
     TransactionError(String),
 
+    /// Transaction aborted by a conflict with a concurrent transaction
+    /// (serialization failure, deadlock, lock wait timeout); retrying may succeed
+    #[error("Transaction conflict: {0}")]
+    TransactionConflict(String),
+
+    /// Insert or update violated a unique or primary key constraint
+    #[error("Unique constraint violated: {0}")]
+    UniqueViolation(String),
+
+    /// Retryable error that persisted through every attempt
+    #[error("Gave up after {attempts} attempts: {last_error}")]
+    RetriesExhausted {
+        attempts: u32,
+        #[source]
+        last_error: Box<DatabaseError>,
+    },
+
     /// Migration error
     #[error("Migration error: {0}")]
     Migration(String),
@@ -167,11 +188,26 @@ impl DatabaseError {
         }
     }
 
+    /// Create an invalid identifier error
+    pub fn invalid_identifier<S: Into<String>>(msg: S) -> Self {
+        DatabaseError::InvalidIdentifier(msg.into())
+    }
+
     /// Create a new transaction error
     pub fn transaction<S: Into<String>>(msg: S) -> Self {
         DatabaseError::TransactionError(msg.into())
     }
 
+    /// Create a transaction conflict error
+    pub fn transaction_conflict<S: Into<String>>(msg: S) -> Self {
+        DatabaseError::TransactionConflict(msg.into())
+    }
+
+    /// Create a unique constraint violation error
+    pub fn unique_violation<S: Into<String>>(msg: S) -> Self {
+        DatabaseError::UniqueViolation(msg.into())
+    }
+
     /// Create a new migration error
     pub fn migration<S: Into<String>>(msg: S) -> Self {
         DatabaseError::Migration(msg.into())
@@ -186,6 +222,41 @@ impl DatabaseError {
     pub fn other<S: Into<String>>(msg: S) -> Self {
         DatabaseError::Other(msg.into())
     }
+
+    /// Check whether the operation may succeed if run again from scratch
+    ///
+    /// True for transaction conflicts (PostgreSQL `40001` / `40P01`, MySQL
+    /// deadlocks and lock wait timeouts) and for SQLite `SQLITE_BUSY` /
+    /// `SQLITE_LOCKED` ("database is locked").
+    pub fn is_retryable(&self) -> bool {
+        match self {
+            DatabaseError::TransactionConflict(_) => true,
+            #[cfg(feature = "sqlite")]
+            DatabaseError::SqliteError(rusqlite::Error::SqliteFailure(error, _)) => matches!(
+                error.code,
+                rusqlite::ErrorCode::DatabaseBusy | rusqlite::ErrorCode::DatabaseLocked
+            ),
+            _ => false,
+        }
+    }
+
+    /// Check whether a write failed because a row with the same unique or
+    /// primary key already exists
+    ///
+    /// True for PostgreSQL `23505`, MySQL duplicate entries (1062) and SQLite
+    /// `SQLITE_CONSTRAINT_UNIQUE` / `SQLITE_CONSTRAINT_PRIMARYKEY`.
+    pub fn is_unique_violation(&self) -> bool {
+        match self {
+            DatabaseError::UniqueViolation(_) => true,
+            #[cfg(feature = "sqlite")]
+            DatabaseError::SqliteError(rusqlite::Error::SqliteFailure(error, _)) => matches!(
+                error.extended_code,
+                rusqlite::ffi::SQLITE_CONSTRAINT_UNIQUE
+                    | rusqlite::ffi::SQLITE_CONSTRAINT_PRIMARYKEY
+            ),
+            _ => false,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -211,5 +282,50 @@ mod tests {
 
         let err = DatabaseError::type_mismatch("i64", "f64");
         assert_eq!(err.to_string(), "Type mismatch: expected i64, got f64");
+
+        let err = DatabaseError::RetriesExhausted {
+            attempts: 3,
+            last_error: Box::new(DatabaseError::transaction_conflict("deadlock")),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Gave up after 3 attempts: Transaction conflict: deadlock"
+        );
+    }
+
+    #[test]
+    fn test_error_retryable() {
+        assert!(DatabaseError::transaction_conflict("could not serialize access").is_retryable());
+        assert!(!DatabaseError::query("syntax error").is_retryable());
+
+        #[cfg(feature = "sqlite")]
+        {
+            let busy = DatabaseError::from(rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+                Some("database is locked".to_string()),
+            ));
+            assert!(busy.is_retryable());
+        }
+    }
+
+    #[test]
+    fn test_error_unique_violation() {
+        assert!(DatabaseError::unique_violation("duplicate key value").is_unique_violation());
+        assert!(!DatabaseError::query("syntax error").is_unique_violation());
+
+        #[cfg(feature = "sqlite")]
+        {
+            let duplicate = DatabaseError::from(rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT_PRIMARYKEY),
+                Some("UNIQUE constraint failed: locks.id".to_string()),
+            ));
+            assert!(duplicate.is_unique_violation());
+
+            let not_null = DatabaseError::from(rusqlite::Error::SqliteFailure(
+                rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_CONSTRAINT_NOTNULL),
+                Some("NOT NULL constraint failed: locks.owner".to_string()),
+            ));
+            assert!(!not_null.is_unique_violation());
+        }
     }
 }
@@ -0,0 +1,222 @@
+//! SQL identifiers
+//!
+//! Table and column names cannot be bound as parameters, so the query builders
+//! splice them into SQL. `Identifier` makes that safe: names are quoted per
+//! dialect whenever they are not plain bare identifiers, embedded quote characters
+//! are escaped, and [`Identifier::new`] validates untrusted names up front. Raw SQL
+//! fragments must be opted into explicitly with [`Identifier::raw`].
+
+use super::dialect::Dialect;
+use super::error::{DatabaseError, Result};
+
+/// Maximum identifier length accepted by [`Identifier::new`] (MySQL's limit)
+const MAX_IDENTIFIER_LENGTH: usize = 64;
+
+/// A table or column reference, or an explicit raw SQL fragment
+///
+/// Converting from a string (`"users.name".into()`) splits the name on `.` and
+/// quotes every segment that is not a plain identifier, so even hostile input can
+/// only ever name a (probably nonexistent) column. Use [`Identifier::new`] to
+/// reject such input instead, and [`Identifier::raw`] for expressions such as
+/// `COUNT(*) AS total`.
+///
+/// # Example
+///
+/// ```
+/// use rust_database_system::core::dialect::Dialect;
+/// use rust_database_system::core::identifier::Identifier;
+///
+/// let column = Identifier::new("orders.order").unwrap();
+/// assert_eq!(column.render(Dialect::Postgres), "orders.\"order\"");
+/// assert_eq!(column.render(Dialect::Mysql), "orders.`order`");
+///
+/// // Untrusted input is rejected rather than spliced into SQL
+/// assert!(Identifier::new("name; DROP TABLE users").is_err());
+///
+/// // Expressions need the explicit escape hatch
+/// let total = Identifier::raw("COUNT(*) AS total");
+/// assert_eq!(total.render(Dialect::Sqlite), "COUNT(*) AS total");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Identifier {
+    kind: IdentifierKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum IdentifierKind {
+    /// Dotted name path; the last segment may be `*`
+    Path(Vec<String>),
+    /// Raw SQL emitted verbatim
+    Raw(String),
+}
+
+impl Identifier {
+    /// Parse and validate a (possibly dotted) identifier
+    ///
+    /// Every segment must start with a letter or underscore, contain only ASCII
+    /// letters, digits, `_` or `$`, and be at most 64 characters long. The last
+    /// segment may be `*`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `InvalidIdentifier` if the name does not satisfy these rules
+    pub fn new(name: &str) -> Result<Self> {
+        let segments: Vec<&str> = name.split('.').collect();
+        let last = segments.len() - 1;
+
+        for (i, segment) in segments.iter().enumerate() {
+            if *segment == "*" && i == last {
+                continue;
+            }
+            Self::validate_segment(segment).map_err(|reason| {
+                DatabaseError::invalid_identifier(format!("{:?}: {}", name, reason))
+            })?;
+        }
+
+        Ok(Self::path(segments))
+    }
+
+    /// Wrap raw SQL that is emitted verbatim
+    ///
+    /// This bypasses all quoting and validation; never pass untrusted input.
+    pub fn raw(sql: impl Into<String>) -> Self {
+        Self {
+            kind: IdentifierKind::Raw(sql.into()),
+        }
+    }
+
+    /// The `*` wildcard
+    pub fn wildcard() -> Self {
+        Self::path(vec!["*"])
+    }
+
+    /// Check whether this is a raw SQL fragment
+    pub fn is_raw(&self) -> bool {
+        matches!(self.kind, IdentifierKind::Raw(_))
+    }
+
+    /// Render the identifier for a dialect
+    ///
+    /// Segments are quoted only when they are reserved words, need case
+    /// preservation or contain characters outside a plain identifier.
+    pub fn render(&self, dialect: Dialect) -> String {
+        match &self.kind {
+            IdentifierKind::Raw(sql) => sql.clone(),
+            IdentifierKind::Path(segments) => {
+                let last = segments.len() - 1;
+                segments
+                    .iter()
+                    .enumerate()
+                    .map(|(i, segment)| {
+                        if (segment == "*" && i == last) || dialect.is_bare_identifier(segment) {
+                            segment.clone()
+                        } else {
+                            dialect.quote_identifier(segment)
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(".")
+            }
+        }
+    }
+
+    fn path(segments: Vec<&str>) -> Self {
+        Self {
+            kind: IdentifierKind::Path(segments.into_iter().map(str::to_string).collect()),
+        }
+    }
+
+    fn validate_segment(segment: &str) -> std::result::Result<(), &'static str> {
+        let mut chars = segment.chars();
+        match chars.next() {
+            None => return Err("empty name segment"),
+            Some(c) if !(c.is_ascii_alphabetic() || c == '_') => {
+                return Err("must start with a letter or underscore")
+            }
+            _ => {}
+        }
+        if !chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$') {
+            return Err("may only contain letters, digits, '_' and '$'");
+        }
+        if segment.len() > MAX_IDENTIFIER_LENGTH {
+            return Err("longer than 64 characters");
+        }
+        Ok(())
+    }
+}
+
+impl From<&str> for Identifier {
+    fn from(name: &str) -> Self {
+        Self::path(name.split('.').collect())
+    }
+}
+
+impl From<String> for Identifier {
+    fn from(name: String) -> Self {
+        Self::from(name.as_str())
+    }
+}
+
+impl From<&String> for Identifier {
+    fn from(name: &String) -> Self {
+        Self::from(name.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_quotes_only_when_needed() {
+        let render = |name: &str, dialect| Identifier::from(name).render(dialect);
+
+        assert_eq!(render("name", Dialect::Sqlite), "name");
+        assert_eq!(render("users.*", Dialect::Sqlite), "users.*");
+        assert_eq!(render("order", Dialect::Sqlite), "\"order\"");
+        assert_eq!(render("orders.group", Dialect::Mysql), "orders.`group`");
+        assert_eq!(render("userName", Dialect::Sqlite), "userName");
+        assert_eq!(render("userName", Dialect::Postgres), "\"userName\"");
+    }
+
+    #[test]
+    fn test_hostile_names_are_quoted() {
+        assert_eq!(
+            Identifier::from("name; DROP TABLE users").render(Dialect::Sqlite),
+            "\"name; DROP TABLE users\""
+        );
+        assert_eq!(
+            Identifier::from("x\" OR 1=1 --").render(Dialect::Postgres),
+            "\"x\"\" OR 1=1 --\""
+        );
+        assert_eq!(
+            Identifier::from("a` OR 1=1").render(Dialect::Mysql),
+            "`a`` OR 1=1`"
+        );
+    }
+
+    #[test]
+    fn test_validation() {
+        assert!(Identifier::new("users").is_ok());
+        assert!(Identifier::new("public.users.*").is_ok());
+        assert!(Identifier::new("price$usd").is_ok());
+
+        for invalid in ["", "users.", "1abc", "a b", "a;b", "*.id", "a\"b"] {
+            let err = Identifier::new(invalid).unwrap_err();
+            assert!(
+                matches!(err, DatabaseError::InvalidIdentifier(_)),
+                "{}",
+                invalid
+            );
+        }
+        assert!(Identifier::new(&"a".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn test_raw() {
+        let raw = Identifier::raw("COUNT(*) AS total");
+        assert!(raw.is_raw());
+        assert_eq!(raw.render(Dialect::Postgres), "COUNT(*) AS total");
+        assert_eq!(Identifier::wildcard().render(Dialect::Mysql), "*");
+    }
+}
@@ -2,6 +2,17 @@
 //!
 //! Provides tools for managing database schema versions with up/down migrations.
 //!
+//! On backends with transactional DDL (SQLite and PostgreSQL) each migration and
+//! the update of the tracking table run in one transaction, so a failing
+//! migration leaves neither partial schema changes nor a tracking row behind.
+//! MySQL commits DDL implicitly, so there the statements run one by one.
+//!
+//! `up_sql` and `down_sql` may hold several statements separated by semicolons;
+//! see [`split_statements`] for how scripts are split. Changes SQL cannot
+//! express, such as data backfills, can be written in Rust with
+//! [`Migration::from_fn`] or [`Migration::from_code`] and mixed with SQL
+//! migrations in the same version sequence.
+//!
 //! # Example
 //!
 //! ```rust
@@ -34,12 +45,30 @@
 //! ```
 
 use super::database::Database;
+use super::dialect::Dialect;
 use super::error::{DatabaseError, Result};
-use super::value::DatabaseValue;
+use super::result_set::ResultSet;
+use super::row::FromRow;
+use super::script::split_statements;
+use super::transaction::{Transaction, TransactionOptions};
+use super::value::{DatabaseResult, DatabaseValue};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::hash_map::RandomState;
 use std::collections::BTreeMap;
+use std::fmt;
+use std::hash::{BuildHasher, Hasher};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+
+#[cfg(feature = "macros")]
+pub use rust_database_system_macros::embed_migrations;
 
 /// Represents a single database migration
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 pub struct Migration {
     /// Migration version number (must be unique and sequential)
     version: i64,
@@ -49,6 +78,26 @@ pub struct Migration {
     up_sql: String,
     /// SQL to revert this migration (backward)
     down_sql: String,
+    /// Whether to run this migration inside a transaction
+    transactional: bool,
+    /// Whether rollbacks must refuse to revert this migration
+    irreversible: bool,
+    /// Rust code run instead of the SQL
+    code: Option<Arc<dyn MigrationCode>>,
+}
+
+impl fmt::Debug for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Migration")
+            .field("version", &self.version)
+            .field("name", &self.name)
+            .field("up_sql", &self.up_sql)
+            .field("down_sql", &self.down_sql)
+            .field("transactional", &self.transactional)
+            .field("irreversible", &self.irreversible)
+            .field("kind", &self.kind())
+            .finish()
+    }
 }
 
 impl Migration {
@@ -84,9 +133,134 @@ impl Migration {
             name: name.into(),
             up_sql: up_sql.into(),
             down_sql: down_sql.into(),
+            transactional: true,
+            irreversible: false,
+            code: None,
+        }
+    }
+
+    /// Create a migration written in Rust
+    ///
+    /// `code` runs through a [`MigrationContext`]: inside the migration's
+    /// transaction by default, or directly on the database after
+    /// [`Migration::without_transaction`]. The migration is recorded in the
+    /// tracking table like a SQL migration.
+    pub fn from_code(
+        version: i64,
+        name: impl Into<String>,
+        code: impl MigrationCode + 'static,
+    ) -> Self {
+        Self {
+            code: Some(Arc::new(code)),
+            ..Self::new(version, name, "", "")
         }
     }
 
+    /// Create a migration from async closures for the up and down direction
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_database_system::core::{DatabaseValue, Migration};
+    ///
+    /// let migration = Migration::from_fn(
+    ///     3,
+    ///     "lowercase_emails",
+    ///     |ctx| {
+    ///         Box::pin(async move {
+    ///             let rows = ctx.query("SELECT id, email FROM users").await?;
+    ///             for row in rows {
+    ///                 let email = row.get("email").and_then(|v| v.as_str()).unwrap_or_default();
+    ///                 ctx.execute_with_params(
+    ///                     "UPDATE users SET email = ? WHERE id = ?",
+    ///                     &[
+    ///                         DatabaseValue::String(email.to_lowercase()),
+    ///                         row.get("id").cloned().unwrap_or(DatabaseValue::Null),
+    ///                     ],
+    ///                 )
+    ///                 .await?;
+    ///             }
+    ///             Ok(())
+    ///         })
+    ///     },
+    ///     |_ctx| Box::pin(async { Ok(()) }),
+    /// );
+    /// assert!(migration.is_code());
+    /// ```
+    pub fn from_fn<U, D>(version: i64, name: impl Into<String>, up: U, down: D) -> Self
+    where
+        U: for<'c> Fn(&'c MigrationContext<'c>) -> BoxFuture<'c, Result<()>>
+            + Send
+            + Sync
+            + 'static,
+        D: for<'c> Fn(&'c MigrationContext<'c>) -> BoxFuture<'c, Result<()>>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self::from_code(version, name, FnMigration { up, down })
+    }
+
+    /// Run this migration outside a transaction
+    ///
+    /// Needed for statements that cannot run inside a transaction block, such as
+    /// PostgreSQL's `CREATE INDEX CONCURRENTLY`. A failure then leaves whatever
+    /// the statements already did in place and the migration unrecorded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_database_system::core::Migration;
+    ///
+    /// let migration = Migration::new(
+    ///     7,
+    ///     "index_users_email",
+    ///     "CREATE INDEX CONCURRENTLY idx_users_email ON users (email)",
+    ///     "DROP INDEX CONCURRENTLY idx_users_email",
+    /// )
+    /// .without_transaction();
+    /// assert!(!migration.is_transactional());
+    /// ```
+    #[must_use]
+    pub fn without_transaction(mut self) -> Self {
+        self.transactional = false;
+        self
+    }
+
+    /// Mark this migration as impossible to revert
+    ///
+    /// `rollback`, `migrate_to` and `reset` then refuse with a `Migration`
+    /// error before reverting anything, instead of running the down SQL or
+    /// code. SQL migrations with empty down SQL are irreversible without this.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_database_system::core::Migration;
+    ///
+    /// let migration = Migration::new(
+    ///     8,
+    ///     "drop_legacy_orders",
+    ///     "DROP TABLE legacy_orders",
+    ///     "",
+    /// );
+    /// assert!(migration.is_irreversible());
+    ///
+    /// let migration = Migration::new(
+    ///     9,
+    ///     "hash_passwords",
+    ///     "UPDATE users SET password = sha256(password)",
+    ///     "SELECT 1",
+    /// )
+    /// .irreversible();
+    /// assert!(migration.is_irreversible());
+    /// ```
+    #[must_use]
+    pub fn irreversible(mut self) -> Self {
+        self.irreversible = true;
+        self
+    }
+
     /// Get the migration version
     pub fn version(&self) -> i64 {
         self.version
@@ -106,6 +280,462 @@ impl Migration {
     pub fn down_sql(&self) -> &str {
         &self.down_sql
     }
+
+    /// Check whether this migration runs inside a transaction
+    pub fn is_transactional(&self) -> bool {
+        self.transactional
+    }
+
+    /// SQL run in `direction`
+    fn sql(&self, direction: MigrationDirection) -> &str {
+        match direction {
+            MigrationDirection::Up => &self.up_sql,
+            MigrationDirection::Down => &self.down_sql,
+        }
+    }
+
+    /// Check whether this migration cannot be reverted
+    ///
+    /// True when marked with [`Migration::irreversible`], or for a SQL
+    /// migration whose down SQL is empty.
+    pub fn is_irreversible(&self) -> bool {
+        self.irreversible || (!self.is_code() && self.down_sql.trim().is_empty())
+    }
+
+    /// Check whether this migration is written in Rust rather than SQL
+    pub fn is_code(&self) -> bool {
+        self.code.is_some()
+    }
+
+    /// Whether this migration is SQL or Rust code
+    pub fn kind(&self) -> MigrationKind {
+        if self.is_code() {
+            MigrationKind::Code
+        } else {
+            MigrationKind::Sql
+        }
+    }
+
+    /// SHA-256 of the up SQL as lowercase hex
+    ///
+    /// Recorded when the migration is applied to detect later edits. Line
+    /// endings are normalized first, so checking the file out with CRLF line
+    /// endings does not change the checksum. Code migrations have no up SQL,
+    /// so edits to their code are not detected.
+    pub fn checksum(&self) -> String {
+        let normalized = self.up_sql.replace("\r\n", "\n");
+        Sha256::digest(normalized.as_bytes())
+            .iter()
+            .map(|byte| format!("{:02x}", byte))
+            .collect()
+    }
+}
+
+/// Whether a [`Migration`] is SQL or Rust code
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationKind {
+    /// Runs `up_sql` / `down_sql`
+    Sql,
+    /// Runs a [`MigrationCode`]
+    Code,
+}
+
+/// Migration written in Rust
+///
+/// For changes SQL cannot express, such as re-hashing or reshaping stored
+/// data. Register it with [`Migration::from_code`]; for closures use
+/// [`Migration::from_fn`].
+///
+/// # Example
+///
+/// ```rust
+/// use async_trait::async_trait;
+/// use rust_database_system::core::error::Result;
+/// use rust_database_system::core::migration::{MigrationCode, MigrationContext};
+/// use rust_database_system::core::Migration;
+///
+/// struct SeedRoles;
+///
+/// #[async_trait]
+/// impl MigrationCode for SeedRoles {
+///     async fn up(&self, ctx: &MigrationContext<'_>) -> Result<()> {
+///         for role in ["admin", "member"] {
+///             ctx.execute(&format!("INSERT INTO roles (name) VALUES ('{}')", role))
+///                 .await?;
+///         }
+///         Ok(())
+///     }
+///
+///     async fn down(&self, ctx: &MigrationContext<'_>) -> Result<()> {
+///         ctx.execute("DELETE FROM roles").await?;
+///         Ok(())
+///     }
+/// }
+///
+/// let migration = Migration::from_code(4, "seed_roles", SeedRoles);
+/// ```
+#[async_trait]
+pub trait MigrationCode: Send + Sync {
+    /// Apply the migration
+    async fn up(&self, ctx: &MigrationContext<'_>) -> Result<()>;
+
+    /// Revert the migration
+    async fn down(&self, ctx: &MigrationContext<'_>) -> Result<()>;
+}
+
+/// [`MigrationCode`] made of two closures, built by [`Migration::from_fn`]
+struct FnMigration<U, D> {
+    up: U,
+    down: D,
+}
+
+#[async_trait]
+impl<U, D> MigrationCode for FnMigration<U, D>
+where
+    U: for<'c> Fn(&'c MigrationContext<'c>) -> BoxFuture<'c, Result<()>> + Send + Sync,
+    D: for<'c> Fn(&'c MigrationContext<'c>) -> BoxFuture<'c, Result<()>> + Send + Sync,
+{
+    async fn up(&self, ctx: &MigrationContext<'_>) -> Result<()> {
+        (self.up)(ctx).await
+    }
+
+    async fn down(&self, ctx: &MigrationContext<'_>) -> Result<()> {
+        (self.down)(ctx).await
+    }
+}
+
+/// Connection a [`MigrationCode`] runs on
+///
+/// The migration's transaction when it runs inside one, otherwise the
+/// database itself.
+pub struct MigrationContext<'a> {
+    connection: Connection<'a>,
+    dialect: Dialect,
+}
+
+enum Connection<'a> {
+    Transaction(&'a (dyn Transaction + 'a)),
+    Database(&'a dyn super::database::DatabaseObject),
+}
+
+impl<'a> MigrationContext<'a> {
+    /// Context running inside `tx`
+    pub(crate) fn transaction(tx: &'a (dyn Transaction + 'a), dialect: Dialect) -> Self {
+        Self {
+            connection: Connection::Transaction(tx),
+            dialect,
+        }
+    }
+
+    /// Context running directly on `db`
+    pub(crate) fn database(db: &'a dyn super::database::DatabaseObject, dialect: Dialect) -> Self {
+        Self {
+            connection: Connection::Database(db),
+            dialect,
+        }
+    }
+
+    /// SQL dialect of the database, e.g. for [`Dialect::placeholder`]
+    pub fn dialect(&self) -> Dialect {
+        self.dialect
+    }
+
+    /// The migration's transaction, if it runs inside one
+    pub fn as_transaction(&self) -> Option<&(dyn Transaction + 'a)> {
+        match self.connection {
+            Connection::Transaction(tx) => Some(tx),
+            Connection::Database(_) => None,
+        }
+    }
+
+    /// The database, if the migration runs outside a transaction
+    pub fn as_database(&self) -> Option<&'a dyn super::database::DatabaseObject> {
+        match self.connection {
+            Connection::Transaction(_) => None,
+            Connection::Database(db) => Some(db),
+        }
+    }
+
+    /// Execute a query that doesn't return results
+    pub async fn execute(&self, query: &str) -> Result<u64> {
+        match self.connection {
+            Connection::Transaction(tx) => tx.execute(query).await,
+            Connection::Database(db) => db.execute(query).await,
+        }
+    }
+
+    /// Execute a parameterized query that doesn't return results
+    pub async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        match self.connection {
+            Connection::Transaction(tx) => tx.execute_with_params(query, params).await,
+            Connection::Database(db) => db.execute_with_params(query, params).await,
+        }
+    }
+
+    /// Execute a script of several statements separated by semicolons
+    pub async fn execute_batch(&self, script: &str) -> Result<()> {
+        match self.connection {
+            Connection::Transaction(tx) => tx.execute_batch(script).await,
+            Connection::Database(db) => db.execute_batch(script).await,
+        }
+    }
+
+    /// Execute a SELECT query and return results
+    pub async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        match self.connection {
+            Connection::Transaction(tx) => tx.query(query).await,
+            Connection::Database(db) => db.query(query).await,
+        }
+    }
+
+    /// Execute a parameterized SELECT query
+    pub async fn query_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<DatabaseResult> {
+        match self.connection {
+            Connection::Transaction(tx) => tx.query_with_params(query, params).await,
+            Connection::Database(db) => db.query_with_params(query, params).await,
+        }
+    }
+
+    /// Execute a SELECT query and return ordered rows with column metadata
+    pub async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        match self.connection {
+            Connection::Transaction(tx) => tx.query_rows(query).await,
+            Connection::Database(db) => db.query_rows(query).await,
+        }
+    }
+
+    /// Execute a parameterized SELECT query and return ordered rows with column metadata
+    pub async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        match self.connection {
+            Connection::Transaction(tx) => tx.query_rows_with_params(query, params).await,
+            Connection::Database(db) => db.query_rows_with_params(query, params).await,
+        }
+    }
+
+    /// Execute a SELECT query and decode every row into `T`
+    pub async fn query_as<T: FromRow>(&self, query: &str) -> Result<Vec<T>> {
+        match self.connection {
+            Connection::Transaction(tx) => tx.query_as(query).await,
+            Connection::Database(db) => db.query_as(query).await,
+        }
+    }
+
+    /// Execute a parameterized SELECT query and decode every row into `T`
+    pub async fn query_as_with_params<T: FromRow>(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<Vec<T>> {
+        match self.connection {
+            Connection::Transaction(tx) => tx.query_as_with_params(query, params).await,
+            Connection::Database(db) => db.query_as_with_params(query, params).await,
+        }
+    }
+}
+
+/// Direction of a migration SQL file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum FileKind {
+    Up,
+    Down,
+}
+
+/// Split a migration file name like `0001_create_users.up.sql` into its parts
+fn parse_file_name(file_name: &str) -> Result<(i64, &str, FileKind)> {
+    let invalid = || {
+        DatabaseError::migration(format!(
+            "Invalid migration file name '{}': expected <version>_<name>.up.sql or <version>_<name>.down.sql",
+            file_name
+        ))
+    };
+
+    let (stem, kind) = if let Some(stem) = file_name.strip_suffix(".up.sql") {
+        (stem, FileKind::Up)
+    } else if let Some(stem) = file_name.strip_suffix(".down.sql") {
+        (stem, FileKind::Down)
+    } else {
+        return Err(invalid());
+    };
+
+    let (version, name) = stem.split_once('_').ok_or_else(invalid)?;
+    if version.is_empty() || !version.bytes().all(|b| b.is_ascii_digit()) || name.is_empty() {
+        return Err(invalid());
+    }
+    let version = version.parse().map_err(|_| invalid())?;
+    Ok((version, name, kind))
+}
+
+impl Migration {
+    /// Build migrations from SQL files named `<version>_<name>.up.sql` and
+    /// `<version>_<name>.down.sql`
+    ///
+    /// `files` yields `(file name, contents)` pairs. Every version needs an up
+    /// file; a missing down file gives an empty down script, which makes the
+    /// migration irreversible. The version is the leading number, so
+    /// `0001_create_users.up.sql` is version 1.
+    ///
+    /// # Errors
+    ///
+    /// Returns `Migration` if a file name does not follow the convention, a
+    /// version has two different names or two files of the same direction, or a
+    /// down file has no up file.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_database_system::core::Migration;
+    ///
+    /// let migrations = Migration::from_sql_files([
+    ///     ("0001_create_users.up.sql", "CREATE TABLE users (id INTEGER PRIMARY KEY)"),
+    ///     ("0001_create_users.down.sql", "DROP TABLE users"),
+    ///     ("0002_add_email.up.sql", "ALTER TABLE users ADD COLUMN email TEXT"),
+    /// ])
+    /// .unwrap();
+    ///
+    /// assert_eq!(migrations.len(), 2);
+    /// assert_eq!(migrations[0].name(), "create_users");
+    /// assert_eq!(migrations[1].down_sql(), "");
+    /// ```
+    pub fn from_sql_files<'a, I>(files: I) -> Result<Vec<Migration>>
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        struct Files<'a> {
+            name: &'a str,
+            up: Option<&'a str>,
+            down: Option<&'a str>,
+        }
+
+        let mut by_version: BTreeMap<i64, Files<'a>> = BTreeMap::new();
+        for (file_name, contents) in files {
+            let (version, name, kind) = parse_file_name(file_name)?;
+            let entry = by_version.entry(version).or_insert(Files {
+                name,
+                up: None,
+                down: None,
+            });
+            if entry.name != name {
+                return Err(DatabaseError::migration(format!(
+                    "Migration {} has two names: '{}' and '{}'",
+                    version, entry.name, name
+                )));
+            }
+
+            let slot = match kind {
+                FileKind::Up => &mut entry.up,
+                FileKind::Down => &mut entry.down,
+            };
+            if slot.replace(contents).is_some() {
+                return Err(DatabaseError::migration(format!(
+                    "Duplicate migration file '{}'",
+                    file_name
+                )));
+            }
+        }
+
+        by_version
+            .into_iter()
+            .map(|(version, files)| {
+                let up = files.up.ok_or_else(|| {
+                    DatabaseError::migration(format!(
+                        "Migration {} ({}) has no .up.sql file",
+                        version, files.name
+                    ))
+                })?;
+                Ok(Migration::new(
+                    version,
+                    files.name,
+                    up,
+                    files.down.unwrap_or_default(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Load migrations from the `.sql` files in a directory
+    ///
+    /// Files must be named as described in [`Migration::from_sql_files`]; other
+    /// files and subdirectories are ignored. To ship the files inside the
+    /// binary instead, use [`embed_migrations!`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `IoError` if the directory or a file cannot be read, and
+    /// `Migration` if the files do not form a valid set of migrations.
+    pub fn load_dir(dir: impl AsRef<Path>) -> Result<Vec<Migration>> {
+        let mut files = Vec::new();
+        for entry in std::fs::read_dir(dir.as_ref())? {
+            let path = entry?.path();
+            if !path.is_file() || path.extension().is_none_or(|ext| ext != "sql") {
+                continue;
+            }
+            let file_name = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .ok_or_else(|| {
+                    DatabaseError::migration(format!(
+                        "Migration file name is not valid UTF-8: {}",
+                        path.display()
+                    ))
+                })?
+                .to_string();
+            let contents = std::fs::read_to_string(&path)?;
+            files.push((file_name, contents));
+        }
+
+        Self::from_sql_files(
+            files
+                .iter()
+                .map(|(name, contents)| (name.as_str(), contents.as_str())),
+        )
+    }
+}
+
+/// Migration SQL files embedded into the binary by [`embed_migrations!`]
+///
+/// # Example
+///
+/// ```rust,ignore
+/// use rust_database_system::core::migration::{embed_migrations, EmbeddedMigrations};
+///
+/// // Path relative to the crate's Cargo.toml
+/// static MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations");
+///
+/// manager.add_migrations(MIGRATIONS.migrations()?);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct EmbeddedMigrations {
+    files: &'static [(&'static str, &'static str)],
+}
+
+impl EmbeddedMigrations {
+    /// Wrap `(file name, contents)` pairs; normally called by [`embed_migrations!`]
+    pub const fn new(files: &'static [(&'static str, &'static str)]) -> Self {
+        Self { files }
+    }
+
+    /// Embedded `(file name, contents)` pairs
+    pub fn files(&self) -> &'static [(&'static str, &'static str)] {
+        self.files
+    }
+
+    /// Build the migrations as [`Migration::from_sql_files`] does
+    ///
+    /// # Errors
+    ///
+    /// Returns `Migration` if the files do not form a valid set of migrations
+    pub fn migrations(&self) -> Result<Vec<Migration>> {
+        Migration::from_sql_files(self.files.iter().copied())
+    }
 }
 
 /// Migration status for a specific version
@@ -115,13 +745,304 @@ pub enum MigrationStatus {
     Applied,
     /// Migration is pending
     Pending,
+    /// Migration has been applied, but its up SQL changed since
+    Modified,
+    /// Version is recorded as applied, but no migration is registered for it
+    Missing,
+}
+
+/// Difference between the applied migrations and the registered ones
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Drift {
+    /// An applied migration whose up SQL no longer matches the recorded checksum
+    Modified {
+        version: i64,
+        name: String,
+        /// Checksum recorded when the migration was applied
+        recorded: String,
+        /// Checksum of the registered migration
+        current: String,
+    },
+    /// A version recorded as applied with no registered migration
+    Missing { version: i64, name: String },
+}
+
+impl fmt::Display for Drift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Drift::Modified {
+                version,
+                name,
+                recorded,
+                current,
+            } => write!(
+                f,
+                "migration {} ({}) was modified after it was applied (checksum {} recorded, {} now)",
+                version, name, recorded, current
+            ),
+            Drift::Missing { version, name } => write!(
+                f,
+                "migration {} ({}) is applied but not registered",
+                version, name
+            ),
+        }
+    }
+}
+
+/// What [`MigrationManager`] does when it finds [`Drift`] before a run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DriftPolicy {
+    /// Refuse to run with a `Migration` error listing the drift
+    #[default]
+    Fail,
+    /// Report the drift to the hook set with
+    /// [`MigrationManager::with_drift_hook`] and run anyway
+    Warn,
+    /// Run without checking
+    Ignore,
+}
+
+/// Callback receiving the [`Drift`] found under [`DriftPolicy::Warn`]
+pub type DriftHook = Arc<dyn Fn(&Drift) + Send + Sync>;
+
+/// What [`MigrationManager`] does with pending migrations older than the
+/// current version
+///
+/// Such migrations usually come from a branch merged after newer migrations
+/// were deployed; applying them may depend on schema they predate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutOfOrderPolicy {
+    /// Refuse to run with a `Migration` error listing them
+    Fail,
+    /// Apply them along with the newer pending migrations
+    #[default]
+    Apply,
+    /// Leave them pending and apply only the newer migrations
+    Skip,
+}
+
+/// Direction of a migration step
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MigrationDirection {
+    /// Apply the migration with its up SQL
+    Up,
+    /// Revert the migration with its down SQL
+    Down,
+}
+
+/// One step of a [`MigrationPlan`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationStep {
+    /// Migration version
+    pub version: i64,
+    /// Migration name
+    pub name: String,
+    /// Whether the step applies or reverts the migration
+    pub direction: MigrationDirection,
+    /// Whether the step runs SQL or Rust code
+    pub kind: MigrationKind,
+    /// SQL the step runs, empty for code migrations
+    pub sql: String,
+    /// Whether the step runs inside a transaction
+    pub transactional: bool,
+    /// Whether the migration cannot be reverted
+    pub irreversible: bool,
+}
+
+/// Ordered steps a [`MigrationManager`] run would take
+///
+/// Returned by the `plan_*` methods, which read the tracking table but change
+/// nothing. Drift is not checked; use [`MigrationManager::check_drift`] for that.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MigrationPlan {
+    /// Steps in execution order
+    pub steps: Vec<MigrationStep>,
+}
+
+impl MigrationPlan {
+    /// Check whether the run would do nothing
+    pub fn is_empty(&self) -> bool {
+        self.steps.is_empty()
+    }
+
+    /// Versions the run would apply or revert, in order
+    pub fn versions(&self) -> Vec<i64> {
+        self.steps.iter().map(|step| step.version).collect()
+    }
+
+    /// Render the plan as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).expect("migration plans always serialize")
+    }
+
+    /// Render the plan for a dry run
+    pub fn render(&self, format: PlanFormat) -> String {
+        match format {
+            PlanFormat::Text => self.to_string(),
+            PlanFormat::Json => self.to_json(),
+        }
+    }
+}
+
+impl fmt::Display for MigrationPlan {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.steps.is_empty() {
+            return writeln!(f, "Nothing to migrate");
+        }
+
+        for (index, step) in self.steps.iter().enumerate() {
+            let direction = match step.direction {
+                MigrationDirection::Up => "UP",
+                MigrationDirection::Down => "DOWN",
+            };
+            write!(
+                f,
+                "{}. {} {} {}",
+                index + 1,
+                direction,
+                step.version,
+                step.name
+            )?;
+            let mut notes = Vec::new();
+            if step.kind == MigrationKind::Code {
+                notes.push("Rust code");
+            }
+            if step.irreversible {
+                notes.push("irreversible");
+            }
+            if !step.transactional {
+                notes.push("no transaction");
+            }
+            if !notes.is_empty() {
+                write!(f, " ({})", notes.join(", "))?;
+            }
+            writeln!(f)?;
+            for line in step.sql.trim().lines() {
+                writeln!(f, "   {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Output format of [`MigrationPlan::render`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PlanFormat {
+    /// Numbered steps with their SQL, for people
+    #[default]
+    Text,
+    /// Pretty-printed JSON, for tools
+    Json,
+}
+
+/// Where a [`MigrationManager`] run is heading
+#[derive(Debug, Clone, Copy)]
+enum Target {
+    /// Apply every pending migration
+    Latest,
+    /// Migrate up or down to a version
+    Version(i64),
+    /// Revert the last N applied migrations
+    Steps(usize),
+    /// Revert every applied migration
+    All,
+}
+
+/// How [`MigrationManager`] keeps concurrent runs from racing on the same versions
+///
+/// `migrate`, `migrate_to`, `rollback` and `reset` hold the lock for the whole
+/// run; a second run waits for it up to the lock timeout.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum MigrationLock {
+    /// Lease row in a `<table>_lock` table, renewed while migrations run
+    ///
+    /// Works on every SQL backend, including connection pools. A crashed
+    /// runner's lease expires after the lease duration.
+    #[default]
+    Table,
+    /// PostgreSQL session advisory lock or MySQL `GET_LOCK`
+    ///
+    /// Released by the server when the connection closes. The lock belongs to a
+    /// connection, so it is only available on single-connection backends
+    /// (`PostgresDatabase`, `MysqlDatabase`); pooled backends and SQLite return
+    /// `UnsupportedOperation`.
+    Advisory,
+    /// No locking; for deployments that run migrations from a single place
+    None,
+}
+
+/// Lock held by a running [`MigrationManager`]
+#[derive(Debug)]
+enum LockLease {
+    None,
+    Table { owner: String },
+    Advisory,
+}
+
+/// Default time to wait for the migration lock
+const DEFAULT_LOCK_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Default duration of a lock table lease
+const DEFAULT_LOCK_LEASE: Duration = Duration::from_secs(300);
+
+/// How often a lock table lease is renewed while a migration runs
+const LOCK_RENEWALS_PER_LEASE: u32 = 3;
+
+/// First delay between attempts to take the migration lock
+const LOCK_POLL_INITIAL: Duration = Duration::from_millis(20);
+
+/// Longest delay between attempts to take the migration lock
+const LOCK_POLL_MAX: Duration = Duration::from_secs(1);
+
+/// Milliseconds since the Unix epoch
+fn now_millis() -> i64 {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    duration_millis(now)
+}
+
+fn duration_millis(duration: Duration) -> i64 {
+    i64::try_from(duration.as_millis()).unwrap_or(i64::MAX)
+}
+
+/// Whether `column` of the first row is true (`TRUE` on PostgreSQL, `1` on MySQL)
+fn is_true(rows: &DatabaseResult, column: &str) -> bool {
+    rows.first()
+        .and_then(|row| row.get(column))
+        .is_some_and(|value| value.as_bool() == Some(true) || value.as_long() == Some(1))
+}
+
+/// Token identifying this run as the lock owner
+fn lock_owner_token() -> String {
+    let random = RandomState::new().build_hasher().finish();
+    format!("pid {} ({:016x})", std::process::id(), random)
+}
+
+/// Row of the tracking table
+#[derive(Debug)]
+struct AppliedMigration {
+    name: String,
+    /// `None` for migrations applied before checksums were recorded
+    checksum: Option<String>,
 }
 
 /// Manages database migrations
+///
+/// Runs that change the schema take a database-level lock first (see
+/// [`MigrationLock`]), so several processes starting at once apply each
+/// migration exactly once.
 pub struct MigrationManager<D: Database> {
     db: D,
     migrations: BTreeMap<i64, Migration>,
     table_name: String,
+    drift_policy: DriftPolicy,
+    drift_hook: Option<DriftHook>,
+    out_of_order_policy: OutOfOrderPolicy,
+    lock: MigrationLock,
+    lock_timeout: Duration,
+    lock_lease: Duration,
 }
 
 impl<D: Database> MigrationManager<D> {
@@ -138,6 +1059,12 @@ impl<D: Database> MigrationManager<D> {
             db,
             migrations: BTreeMap::new(),
             table_name: Self::DEFAULT_TABLE_NAME.to_string(),
+            drift_policy: DriftPolicy::default(),
+            drift_hook: None,
+            out_of_order_policy: OutOfOrderPolicy::default(),
+            lock: MigrationLock::default(),
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+            lock_lease: DEFAULT_LOCK_LEASE,
         }
     }
 
@@ -147,9 +1074,74 @@ impl<D: Database> MigrationManager<D> {
             db,
             migrations: BTreeMap::new(),
             table_name: table_name.into(),
+            drift_policy: DriftPolicy::default(),
+            drift_hook: None,
+            out_of_order_policy: OutOfOrderPolicy::default(),
+            lock: MigrationLock::default(),
+            lock_timeout: DEFAULT_LOCK_TIMEOUT,
+            lock_lease: DEFAULT_LOCK_LEASE,
         }
     }
 
+    /// Set what happens when applied migrations differ from the registered ones
+    ///
+    /// Checked at the start of `migrate`, `migrate_to`, `rollback` and `reset`.
+    /// Defaults to [`DriftPolicy::Fail`].
+    #[must_use]
+    pub fn with_drift_policy(mut self, policy: DriftPolicy) -> Self {
+        self.drift_policy = policy;
+        self
+    }
+
+    /// Call `hook` for each [`Drift`] found before a run under
+    /// [`DriftPolicy::Warn`]
+    #[must_use]
+    pub fn with_drift_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&Drift) + Send + Sync + 'static,
+    {
+        self.drift_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Set what happens to pending migrations older than the current version
+    ///
+    /// Applies to `migrate` and to `migrate_to` a newer version, and to their
+    /// plans. Defaults to [`OutOfOrderPolicy::Apply`], which runs every pending
+    /// migration in version order.
+    #[must_use]
+    pub fn with_out_of_order_policy(mut self, policy: OutOfOrderPolicy) -> Self {
+        self.out_of_order_policy = policy;
+        self
+    }
+
+    /// Set how concurrent runs are kept apart
+    ///
+    /// Defaults to [`MigrationLock::Table`].
+    #[must_use]
+    pub fn with_lock(mut self, lock: MigrationLock) -> Self {
+        self.lock = lock;
+        self
+    }
+
+    /// Set how long a run waits for another run to release the lock (default 60s)
+    #[must_use]
+    pub fn with_lock_timeout(mut self, timeout: Duration) -> Self {
+        self.lock_timeout = timeout;
+        self
+    }
+
+    /// Set how long a lock table lease lasts without renewal (default 5 minutes)
+    ///
+    /// The lease is renewed every third of its duration while migrations run,
+    /// and a run that finds its lease taken over stops. A runner that crashes
+    /// blocks others for at most this long.
+    #[must_use]
+    pub fn with_lock_lease(mut self, lease: Duration) -> Self {
+        self.lock_lease = lease;
+        self
+    }
+
     /// Add a migration to the manager
     ///
     /// # Example
@@ -173,36 +1165,343 @@ impl<D: Database> MigrationManager<D> {
         self.migrations.insert(migration.version, migration);
     }
 
-    /// Ensure the migrations tracking table exists
+    /// Add several migrations, e.g. from [`Migration::load_dir`] or
+    /// [`EmbeddedMigrations::migrations`]
+    pub fn add_migrations(&mut self, migrations: impl IntoIterator<Item = Migration>) {
+        for migration in migrations {
+            self.add_migration(migration);
+        }
+    }
+
+    /// Ensure the migrations tracking table exists
+    ///
+    /// Tables created before checksums were recorded get a `checksum` column.
     async fn ensure_migrations_table(&self) -> Result<()> {
         let create_table_sql = format!(
             "CREATE TABLE IF NOT EXISTS {} (
                 version INTEGER PRIMARY KEY NOT NULL,
                 name TEXT NOT NULL,
-                applied_at INTEGER NOT NULL
+                applied_at INTEGER NOT NULL,
+                checksum TEXT
             )",
             self.table_name
         );
 
         self.db.execute(&create_table_sql).await?;
+
+        // Tables created before checksums were recorded lack the column
+        if !self.has_checksum_column().await? {
+            let alter_sql = format!("ALTER TABLE {} ADD COLUMN checksum TEXT", self.table_name);
+            self.db.execute(&alter_sql).await?;
+        }
         Ok(())
     }
 
-    /// Get all applied migration versions
-    async fn get_applied_versions(&self) -> Result<Vec<i64>> {
+    /// Schema (if the name is qualified) and table of the tracking table, for
+    /// catalog lookups
+    fn tracking_table_parts(&self) -> (Option<DatabaseValue>, DatabaseValue) {
+        let (schema, table) = match self.table_name.rsplit_once('.') {
+            Some((schema, table)) => (Some(schema), table),
+            None => (None, self.table_name.as_str()),
+        };
+        (
+            schema.map(|schema| DatabaseValue::String(schema.to_string())),
+            DatabaseValue::String(table.to_string()),
+        )
+    }
+
+    /// Look up the tracking table's `checksum` column in the catalog
+    async fn has_checksum_column(&self) -> Result<bool> {
+        let (schema, table) = self.tracking_table_parts();
+        let (query, params) = match self.dialect() {
+            Dialect::Sqlite => (
+                "SELECT name FROM pragma_table_info(?1, ?2) WHERE name = 'checksum'",
+                vec![
+                    table,
+                    schema.unwrap_or(DatabaseValue::String("main".to_string())),
+                ],
+            ),
+            Dialect::Postgres => (
+                // Unquoted names are stored in lower case
+                "SELECT column_name FROM information_schema.columns
+                 WHERE table_schema = COALESCE(lower($2), current_schema())
+                   AND table_name = lower($1) AND column_name = 'checksum'",
+                vec![table, schema.unwrap_or(DatabaseValue::Null)],
+            ),
+            Dialect::Mysql => (
+                "SELECT column_name FROM information_schema.columns
+                 WHERE table_schema = COALESCE(?, DATABASE())
+                   AND table_name = ? AND column_name = 'checksum'",
+                vec![schema.unwrap_or(DatabaseValue::Null), table],
+            ),
+        };
+        Ok(!self.db.query_with_params(query, &params).await?.is_empty())
+    }
+
+    /// Get the rows of the tracking table by version
+    async fn applied_migrations(&self) -> Result<BTreeMap<i64, AppliedMigration>> {
         self.ensure_migrations_table().await?;
 
-        let query = format!("SELECT version FROM {} ORDER BY version", self.table_name);
+        let query = format!(
+            "SELECT version, name, checksum FROM {} ORDER BY version",
+            self.table_name
+        );
         let result = self.db.query(&query).await?;
 
-        let mut versions = Vec::new();
+        let mut applied = BTreeMap::new();
         for row in &result {
-            if let Some(DatabaseValue::Long(version)) = row.get("version") {
-                versions.push(*version);
+            if let Some(version) = row.get("version").and_then(DatabaseValue::as_long) {
+                let name = row.get("name").map(DatabaseValue::as_string);
+                let checksum = row
+                    .get("checksum")
+                    .filter(|value| !value.is_null())
+                    .map(DatabaseValue::as_string);
+                applied.insert(
+                    version,
+                    AppliedMigration {
+                        name: name.unwrap_or_default(),
+                        checksum,
+                    },
+                );
+            }
+        }
+
+        Ok(applied)
+    }
+
+    /// Get all applied migration versions
+    async fn get_applied_versions(&self) -> Result<Vec<i64>> {
+        Ok(self.applied_migrations().await?.into_keys().collect())
+    }
+
+    /// Compare the applied migrations with the registered ones
+    ///
+    /// Reports applied migrations whose up SQL changed since they were applied
+    /// and applied versions with no registered migration. Migrations applied
+    /// before checksums were recorded are not reported.
+    pub async fn check_drift(&self) -> Result<Vec<Drift>> {
+        let applied = self.applied_migrations().await?;
+        Ok(self.drift_in(&applied))
+    }
+
+    fn drift_in(&self, applied: &BTreeMap<i64, AppliedMigration>) -> Vec<Drift> {
+        applied
+            .iter()
+            .filter_map(|(version, record)| match self.migrations.get(version) {
+                None => Some(Drift::Missing {
+                    version: *version,
+                    name: record.name.clone(),
+                }),
+                Some(migration) => {
+                    let recorded = record.checksum.as_ref()?;
+                    let current = migration.checksum();
+                    (*recorded != current).then(|| Drift::Modified {
+                        version: *version,
+                        name: migration.name().to_string(),
+                        recorded: recorded.clone(),
+                        current,
+                    })
+                }
+            })
+            .collect()
+    }
+
+    /// Apply the drift policy before a run
+    ///
+    /// Also records checksums for migrations applied before checksums existed.
+    async fn enforce_drift_policy(&self) -> Result<()> {
+        let applied = self.applied_migrations().await?;
+        self.record_missing_checksums(&applied).await?;
+
+        if self.drift_policy == DriftPolicy::Ignore {
+            return Ok(());
+        }
+        let drift = self.drift_in(&applied);
+        if drift.is_empty() {
+            return Ok(());
+        }
+
+        match self.drift_policy {
+            DriftPolicy::Fail => {
+                let details: Vec<String> = drift.iter().map(ToString::to_string).collect();
+                Err(DatabaseError::migration(format!(
+                    "Applied migrations differ from the registered ones: {}",
+                    details.join("; ")
+                )))
+            }
+            DriftPolicy::Warn => {
+                if let Some(hook) = &self.drift_hook {
+                    for found in &drift {
+                        hook(found);
+                    }
+                }
+                Ok(())
             }
+            DriftPolicy::Ignore => Ok(()),
         }
+    }
 
-        Ok(versions)
+    /// Store the current checksum for applied migrations that have none
+    async fn record_missing_checksums(
+        &self,
+        applied: &BTreeMap<i64, AppliedMigration>,
+    ) -> Result<()> {
+        let dialect = self.dialect();
+        let update_sql = format!(
+            "UPDATE {} SET checksum = {} WHERE version = {} AND checksum IS NULL",
+            self.table_name,
+            dialect.placeholder(1),
+            dialect.placeholder(2)
+        );
+
+        for (version, record) in applied {
+            if record.checksum.is_some() {
+                continue;
+            }
+            if let Some(migration) = self.migrations.get(version) {
+                self.db
+                    .execute_with_params(
+                        &update_sql,
+                        &[
+                            DatabaseValue::String(migration.checksum()),
+                            DatabaseValue::Long(*version),
+                        ],
+                    )
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// SQL dialect of the managed database
+    fn dialect(&self) -> Dialect {
+        Dialect::from_database_type(self.db.database_type()).unwrap_or_default()
+    }
+
+    /// Check whether `migration` runs inside a transaction
+    ///
+    /// MySQL commits DDL implicitly, so a transaction there would not make the
+    /// migration atomic.
+    fn use_transaction(&self, migration: &Migration) -> bool {
+        migration.is_transactional() && self.dialect() != Dialect::Mysql
+    }
+
+    /// Execute the statements of a script one by one
+    ///
+    /// Used outside transactions: PostgreSQL runs a multi-statement script sent
+    /// in one call as a single transaction, which statements like
+    /// `CREATE INDEX CONCURRENTLY` refuse.
+    async fn execute_statements(&self, script: &str) -> Result<()> {
+        for statement in split_statements(script, self.dialect()) {
+            self.db.execute(statement).await?;
+        }
+        Ok(())
+    }
+
+    /// Statement recording a migration in the tracking table
+    fn insert_sql(&self) -> String {
+        let dialect = self.dialect();
+        format!(
+            "INSERT INTO {} (version, name, applied_at, checksum) VALUES ({}, {}, {}, {})",
+            self.table_name,
+            dialect.placeholder(1),
+            dialect.placeholder(2),
+            dialect.placeholder(3),
+            dialect.placeholder(4)
+        )
+    }
+
+    /// Parameters of [`Self::insert_sql`] for `migration`
+    fn tracking_params(migration: &Migration) -> [DatabaseValue; 4] {
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        [
+            DatabaseValue::Long(migration.version()),
+            DatabaseValue::String(migration.name().to_string()),
+            DatabaseValue::Long(now),
+            DatabaseValue::String(migration.checksum()),
+        ]
+    }
+
+    /// Run the up SQL of `migration` and record it as applied
+    async fn apply(&self, migration: &Migration) -> Result<()> {
+        let insert_sql = self.insert_sql();
+        let params = Self::tracking_params(migration);
+
+        if self.use_transaction(migration) {
+            // Dropping the transaction on error rolls the migration back
+            let tx = self.db.begin().await?;
+            self.run_in_transaction(migration, MigrationDirection::Up, tx.as_ref())
+                .await?;
+            tx.execute_with_params(&insert_sql, &params).await?;
+            tx.commit().await
+        } else {
+            self.run_directly(migration, MigrationDirection::Up).await?;
+            self.db.execute_with_params(&insert_sql, &params).await?;
+            Ok(())
+        }
+    }
+
+    /// Run the down SQL of `migration` and remove its record
+    async fn revert(&self, migration: &Migration) -> Result<()> {
+        let delete_sql = format!(
+            "DELETE FROM {} WHERE version = {}",
+            self.table_name,
+            self.dialect().placeholder(1)
+        );
+        let params = [DatabaseValue::Long(migration.version())];
+
+        if self.use_transaction(migration) {
+            let tx = self.db.begin().await?;
+            self.run_in_transaction(migration, MigrationDirection::Down, tx.as_ref())
+                .await?;
+            tx.execute_with_params(&delete_sql, &params).await?;
+            tx.commit().await
+        } else {
+            self.run_directly(migration, MigrationDirection::Down)
+                .await?;
+            self.db.execute_with_params(&delete_sql, &params).await?;
+            Ok(())
+        }
+    }
+
+    /// Run one direction of `migration` inside `tx`
+    async fn run_in_transaction(
+        &self,
+        migration: &Migration,
+        direction: MigrationDirection,
+        tx: &(dyn Transaction + '_),
+    ) -> Result<()> {
+        match &migration.code {
+            Some(code) => {
+                let ctx = MigrationContext::transaction(tx, self.dialect());
+                match direction {
+                    MigrationDirection::Up => code.up(&ctx).await,
+                    MigrationDirection::Down => code.down(&ctx).await,
+                }
+            }
+            None => tx.execute_batch(migration.sql(direction)).await,
+        }
+    }
+
+    /// Run one direction of `migration` outside a transaction
+    async fn run_directly(
+        &self,
+        migration: &Migration,
+        direction: MigrationDirection,
+    ) -> Result<()> {
+        match &migration.code {
+            Some(code) => {
+                let ctx = MigrationContext::database(&self.db, self.dialect());
+                match direction {
+                    MigrationDirection::Up => code.up(&ctx).await,
+                    MigrationDirection::Down => code.down(&ctx).await,
+                }
+            }
+            None => self.execute_statements(migration.sql(direction)).await,
+        }
     }
 
     /// Check if a specific migration version has been applied
@@ -218,17 +1517,28 @@ impl<D: Database> MigrationManager<D> {
     }
 
     /// Get the status of all migrations
+    ///
+    /// Covers every registered migration and every applied version, so
+    /// `Modified` and `Missing` entries reveal drift without running anything.
     pub async fn migration_status(&self) -> Result<BTreeMap<i64, MigrationStatus>> {
-        let applied = self.get_applied_versions().await?;
+        let applied = self.applied_migrations().await?;
         let mut status = BTreeMap::new();
 
         for version in self.migrations.keys() {
-            let migration_status = if applied.contains(version) {
-                MigrationStatus::Applied
-            } else {
-                MigrationStatus::Pending
+            if !applied.contains_key(version) {
+                status.insert(*version, MigrationStatus::Pending);
+            }
+        }
+        for version in applied.keys() {
+            status.insert(*version, MigrationStatus::Applied);
+        }
+        for drift in self.drift_in(&applied) {
+            match drift {
+                Drift::Modified { version, .. } => {
+                    status.insert(version, MigrationStatus::Modified)
+                }
+                Drift::Missing { version, .. } => status.insert(version, MigrationStatus::Missing),
             };
-            status.insert(*version, migration_status);
         }
 
         Ok(status)
@@ -255,303 +1565,1424 @@ impl<D: Database> MigrationManager<D> {
     /// # }
     /// ```
     pub async fn migrate(&self) -> Result<Vec<i64>> {
-        self.ensure_migrations_table().await?;
+        self.run(Target::Latest).await
+    }
+
+    /// Migrate to a specific version
+    ///
+    /// If the target version is lower than current, migrations will be rolled back.
+    /// If higher, pending migrations will be applied.
+    pub async fn migrate_to(&self, target_version: i64) -> Result<Vec<i64>> {
+        self.run(Target::Version(target_version)).await
+    }
+
+    /// Rollback the last N migrations
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_database_system::core::{Database, MigrationManager};
+    ///
+    /// // FIXED: Added generic parameter <D: Database> to match struct signature
+    /// # async fn example<D: Database>(manager: MigrationManager<D>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Rollback the last 2 migrations
+    /// manager.rollback(2).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn rollback(&self, steps: usize) -> Result<Vec<i64>> {
+        self.run(Target::Steps(steps)).await
+    }
+
+    /// Run the steps towards `target` under the migration lock
+    async fn run(&self, target: Target) -> Result<Vec<i64>> {
+        let lease = self.acquire_lock().await?;
+        let result = self.run_locked(target, &lease).await;
+        self.release_lock(lease, result).await
+    }
+
+    async fn run_locked(&self, target: Target, lease: &LockLease) -> Result<Vec<i64>> {
+        self.enforce_drift_policy().await?;
         let applied = self.get_applied_versions().await?;
-        let mut migrated = Vec::new();
+        let mut done = Vec::new();
+
+        for (direction, migration) in self.steps_for(&applied, target)? {
+            self.renew_lock(lease).await?;
+            let step = async {
+                match direction {
+                    MigrationDirection::Up => self.apply(migration).await,
+                    MigrationDirection::Down => self.revert(migration).await,
+                }
+            };
+            self.while_renewing(lease, step).await?;
+            done.push(migration.version());
+        }
+
+        Ok(done)
+    }
 
-        for (version, migration) in &self.migrations {
-            if applied.contains(version) {
-                continue; // Already applied
+    /// Work out the ordered steps that reach `target` from the applied versions
+    fn steps_for(
+        &self,
+        applied: &[i64],
+        target: Target,
+    ) -> Result<Vec<(MigrationDirection, &Migration)>> {
+        let current = applied.last().copied().unwrap_or(0);
+        let (direction, versions): (MigrationDirection, Vec<i64>) = match target {
+            Target::Latest => (
+                MigrationDirection::Up,
+                self.pending_up_to(applied, i64::MAX)?,
+            ),
+            Target::Version(version) if version > current => (
+                MigrationDirection::Up,
+                self.pending_up_to(applied, version)?,
+            ),
+            Target::Version(version) => (
+                MigrationDirection::Down,
+                applied
+                    .iter()
+                    .rev()
+                    .take_while(|applied| **applied > version)
+                    .copied()
+                    .collect(),
+            ),
+            Target::Steps(steps) => (
+                MigrationDirection::Down,
+                applied.iter().rev().take(steps).copied().collect(),
+            ),
+            Target::All => (
+                MigrationDirection::Down,
+                applied.iter().rev().copied().collect(),
+            ),
+        };
+
+        let steps = versions
+            .into_iter()
+            .map(|version| {
+                let migration = self.migrations.get(&version).ok_or_else(|| {
+                    DatabaseError::Migration(format!("Migration {} not found", version))
+                })?;
+                Ok((direction, migration))
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        // Refuse before reverting anything rather than stopping halfway
+        if direction == MigrationDirection::Down {
+            if let Some((_, migration)) = steps.iter().find(|(_, m)| m.is_irreversible()) {
+                return Err(DatabaseError::migration(format!(
+                    "Migration {} ({}) is irreversible and cannot be rolled back",
+                    migration.version(),
+                    migration.name()
+                )));
             }
+        }
+        Ok(steps)
+    }
 
-            // Execute the up migration
-            self.db.execute(migration.up_sql()).await?;
+    /// Registered versions up to `target_version` that are not applied,
+    /// following the out-of-order policy
+    fn pending_up_to(&self, applied: &[i64], target_version: i64) -> Result<Vec<i64>> {
+        let current = applied.last().copied().unwrap_or(i64::MIN);
+        let (older, newer): (Vec<i64>, Vec<i64>) = self
+            .migrations
+            .range(..=target_version)
+            .map(|(version, _)| *version)
+            .filter(|version| !applied.contains(version))
+            .partition(|version| *version < current);
+
+        match self.out_of_order_policy {
+            OutOfOrderPolicy::Fail if !older.is_empty() => {
+                let listed: Vec<String> = older.iter().map(|v| v.to_string()).collect();
+                Err(DatabaseError::migration(format!(
+                    "Pending migrations older than current version {}: {}; \
+                     apply them with OutOfOrderPolicy::Apply or skip them with OutOfOrderPolicy::Skip",
+                    current,
+                    listed.join(", ")
+                )))
+            }
+            OutOfOrderPolicy::Skip => Ok(newer),
+            _ => Ok(older.into_iter().chain(newer).collect()),
+        }
+    }
 
-            // Record the migration
-            let insert_sql = format!(
-                "INSERT INTO {} (version, name, applied_at) VALUES (?, ?, ?)",
-                self.table_name
-            );
+    /// Plan what `migrate` would do, without changing the database
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_database_system::core::{Database, MigrationManager};
+    ///
+    /// # async fn example<D: Database>(manager: MigrationManager<D>) -> Result<(), Box<dyn std::error::Error>> {
+    /// let plan = manager.plan_migrate().await?;
+    /// println!("{}", plan); // or plan.to_json() for the release pipeline
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn plan_migrate(&self) -> Result<MigrationPlan> {
+        self.plan(Target::Latest).await
+    }
 
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs() as i64;
+    /// Plan what `migrate_to` would do, without changing the database
+    pub async fn plan_migrate_to(&self, target_version: i64) -> Result<MigrationPlan> {
+        self.plan(Target::Version(target_version)).await
+    }
 
+    /// Plan what `rollback` would do, without changing the database
+    pub async fn plan_rollback(&self, steps: usize) -> Result<MigrationPlan> {
+        self.plan(Target::Steps(steps)).await
+    }
+
+    /// Plan what `reset` would do, without changing the database
+    pub async fn plan_reset(&self) -> Result<MigrationPlan> {
+        self.plan(Target::All).await
+    }
+
+    async fn plan(&self, target: Target) -> Result<MigrationPlan> {
+        // Planning must not create the tracking table
+        let applied = if self.tracking_table_exists().await? {
+            let query = format!("SELECT version FROM {} ORDER BY version", self.table_name);
             self.db
-                .execute_with_params(
-                    &insert_sql,
-                    &[
-                        DatabaseValue::Long(*version),
-                        DatabaseValue::String(migration.name().to_string()),
-                        DatabaseValue::Long(now),
-                    ],
-                )
+                .query(&query)
+                .await?
+                .iter()
+                .filter_map(|row| row.get("version").and_then(DatabaseValue::as_long))
+                .collect()
+        } else {
+            Vec::new()
+        };
+
+        let steps = self
+            .steps_for(&applied, target)?
+            .into_iter()
+            .map(|(direction, migration)| MigrationStep {
+                version: migration.version(),
+                name: migration.name().to_string(),
+                direction,
+                kind: migration.kind(),
+                sql: migration.sql(direction).to_string(),
+                transactional: self.use_transaction(migration),
+                irreversible: migration.is_irreversible(),
+            })
+            .collect();
+        Ok(MigrationPlan { steps })
+    }
+
+    /// Check whether the tracking table exists, without creating it
+    async fn tracking_table_exists(&self) -> Result<bool> {
+        let (schema, table) = self.tracking_table_parts();
+        let (query, params) = match self.dialect() {
+            Dialect::Sqlite => (
+                // Every table has at least one column
+                "SELECT name FROM pragma_table_info(?1, ?2) LIMIT 1",
+                vec![
+                    table,
+                    schema.unwrap_or(DatabaseValue::String("main".to_string())),
+                ],
+            ),
+            Dialect::Postgres => (
+                "SELECT table_name FROM information_schema.tables
+                 WHERE table_schema = COALESCE(lower($2), current_schema())
+                   AND table_name = lower($1)",
+                vec![table, schema.unwrap_or(DatabaseValue::Null)],
+            ),
+            Dialect::Mysql => (
+                "SELECT table_name FROM information_schema.tables
+                 WHERE table_schema = COALESCE(?, DATABASE()) AND table_name = ?",
+                vec![schema.unwrap_or(DatabaseValue::Null), table],
+            ),
+        };
+        Ok(!self.db.query_with_params(query, &params).await?.is_empty())
+    }
+
+    /// Get the list of pending migrations
+    pub async fn pending_migrations(&self) -> Result<Vec<&Migration>> {
+        let applied = self.get_applied_versions().await?;
+        let pending: Vec<&Migration> = self
+            .migrations
+            .values()
+            .filter(|m| !applied.contains(&m.version))
+            .collect();
+
+        Ok(pending)
+    }
+
+    /// Get the pending migrations older than the current version
+    ///
+    /// What [`OutOfOrderPolicy`] decides about before a run.
+    pub async fn out_of_order_migrations(&self) -> Result<Vec<&Migration>> {
+        let applied = self.get_applied_versions().await?;
+        let Some(current) = applied.last().copied() else {
+            return Ok(Vec::new());
+        };
+        Ok(self
+            .migrations
+            .range(..current)
+            .filter(|(version, _)| !applied.contains(version))
+            .map(|(_, migration)| migration)
+            .collect())
+    }
+
+    /// Record the migrations up to `version` as applied without running them
+    ///
+    /// Adopts a database whose schema already matches those migrations, e.g.
+    /// an existing production database: only later migrations will run.
+    /// Versions already applied are left alone. Returns the versions recorded.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rust_database_system::core::{Database, MigrationManager};
+    ///
+    /// # async fn example<D: Database>(manager: MigrationManager<D>) -> Result<(), Box<dyn std::error::Error>> {
+    /// // Schema of migrations 1-12 already exists
+    /// manager.baseline(12).await?;
+    /// manager.migrate().await?; // runs 13 onwards
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn baseline(&self, version: i64) -> Result<Vec<i64>> {
+        let lease = self.acquire_lock().await?;
+        let result = self.baseline_locked(version).await;
+        self.release_lock(lease, result).await
+    }
+
+    async fn baseline_locked(&self, version: i64) -> Result<Vec<i64>> {
+        let applied = self.get_applied_versions().await?;
+        let migrations: Vec<&Migration> = self
+            .migrations
+            .range(..=version)
+            .filter(|(version, _)| !applied.contains(version))
+            .map(|(_, migration)| migration)
+            .collect();
+        if migrations.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let insert_sql = self.insert_sql();
+        let tx = self.db.begin().await?;
+        for migration in &migrations {
+            tx.execute_with_params(&insert_sql, &Self::tracking_params(migration))
                 .await?;
+        }
+        tx.commit().await?;
+        Ok(migrations
+            .iter()
+            .map(|migration| migration.version())
+            .collect())
+    }
+
+    /// Reset the database by rolling back all migrations
+    pub async fn reset(&self) -> Result<Vec<i64>> {
+        self.run(Target::All).await
+    }
+
+    /// Name of the table holding the migration lock
+    fn lock_table_name(&self) -> String {
+        format!("{}_lock", self.table_name)
+    }
+
+    /// Take the migration lock, waiting up to the lock timeout
+    async fn acquire_lock(&self) -> Result<LockLease> {
+        if self.lock == MigrationLock::None {
+            return Ok(LockLease::None);
+        }
+        if self.lock == MigrationLock::Advisory {
+            self.ensure_single_connection().await?;
+            if self.dialect() == Dialect::Sqlite {
+                return Err(DatabaseError::unsupported(
+                    "SQLite has no advisory locks; use MigrationLock::Table",
+                ));
+            }
+        }
+        if self.lock == MigrationLock::Table {
+            self.ensure_lock_table().await?;
+        }
+
+        let owner = lock_owner_token();
+        let deadline = tokio::time::Instant::now() + self.lock_timeout;
+        let mut delay = LOCK_POLL_INITIAL;
+        loop {
+            let acquired = match self.lock {
+                MigrationLock::Table => self.try_lock_table(&owner).await?,
+                _ => self.try_advisory_lock().await?,
+            };
+            if acquired {
+                return Ok(match self.lock {
+                    MigrationLock::Table => LockLease::Table { owner },
+                    _ => LockLease::Advisory,
+                });
+            }
 
-            migrated.push(*version);
+            let now = tokio::time::Instant::now();
+            if now >= deadline {
+                return Err(DatabaseError::migration(format!(
+                    "Timed out after {}ms waiting for the migration lock{}",
+                    self.lock_timeout.as_millis(),
+                    self.lock_holder().await
+                )));
+            }
+            tokio::time::sleep(delay.min(deadline - now)).await;
+            delay = (delay * 2).min(LOCK_POLL_MAX);
         }
+    }
 
-        Ok(migrated)
+    /// Describe who holds the lock table lease, for timeout errors
+    async fn lock_holder(&self) -> String {
+        if self.lock != MigrationLock::Table {
+            return String::new();
+        }
+        let query = format!("SELECT owner FROM {} WHERE id = 1", self.lock_table_name());
+        match self.db.query(&query).await {
+            Ok(rows) => rows
+                .first()
+                .and_then(|row| row.get("owner"))
+                .map(|owner| format!(" held by {}", owner.as_string()))
+                .unwrap_or_default(),
+            Err(_) => String::new(),
+        }
     }
 
-    /// Migrate to a specific version
+    /// Release the migration lock and pass on the result of the locked run
     ///
-    /// If the target version is lower than current, migrations will be rolled back.
-    /// If higher, pending migrations will be applied.
-    pub async fn migrate_to(&self, target_version: i64) -> Result<Vec<i64>> {
-        let current = self.current_version().await?.unwrap_or(0);
-
-        if target_version > current {
-            // Migrate up
-            self.migrate_up_to(target_version).await
-        } else if target_version < current {
-            // Migrate down
-            self.rollback_to(target_version).await
-        } else {
-            Ok(Vec::new()) // Already at target version
+    /// A failure to release is reported only if the run itself succeeded.
+    async fn release_lock<T>(&self, lease: LockLease, result: Result<T>) -> Result<T> {
+        let released = match &lease {
+            LockLease::None => Ok(()),
+            LockLease::Table { owner } => {
+                let delete_sql = format!(
+                    "DELETE FROM {} WHERE id = 1 AND owner = {}",
+                    self.lock_table_name(),
+                    self.dialect().placeholder(1)
+                );
+                self.db
+                    .execute_with_params(&delete_sql, &[DatabaseValue::String(owner.clone())])
+                    .await
+                    .map(|_| ())
+            }
+            LockLease::Advisory => {
+                let unlock_sql = match self.dialect() {
+                    Dialect::Postgres => "SELECT pg_advisory_unlock($1) AS unlocked",
+                    _ => "SELECT RELEASE_LOCK(?) AS unlocked",
+                };
+                match self
+                    .db
+                    .query_with_params(unlock_sql, &[self.advisory_key()])
+                    .await
+                {
+                    Ok(rows) if is_true(&rows, "unlocked") => Ok(()),
+                    // The unlock ran on a connection that did not hold the lock
+                    Ok(_) => Err(DatabaseError::migration(
+                        "Migration advisory lock was not held by this connection",
+                    )),
+                    Err(e) => Err(e),
+                }
+            }
+        };
+
+        let value = result?;
+        released?;
+        Ok(value)
+    }
+
+    /// Extend the lock table lease before the next step
+    ///
+    /// Fails if the lease expired and another runner took the lock over.
+    async fn renew_lock(&self, lease: &LockLease) -> Result<()> {
+        let LockLease::Table { owner } = lease else {
+            return Ok(());
+        };
+
+        let dialect = self.dialect();
+        let update_sql = format!(
+            "UPDATE {} SET expires_at = {} WHERE id = 1 AND owner = {}",
+            self.lock_table_name(),
+            dialect.placeholder(1),
+            dialect.placeholder(2)
+        );
+        let renewed = self
+            .db
+            .execute_with_params(
+                &update_sql,
+                &[
+                    DatabaseValue::Long(now_millis() + duration_millis(self.lock_lease)),
+                    DatabaseValue::String(owner.clone()),
+                ],
+            )
+            .await?;
+        if renewed == 0 {
+            return Err(DatabaseError::migration(
+                "Migration lock lease expired and was taken over by another runner",
+            ));
         }
+        Ok(())
     }
 
-    /// Apply pending migrations up to a specific version
-    async fn migrate_up_to(&self, target_version: i64) -> Result<Vec<i64>> {
-        self.ensure_migrations_table().await?;
-        let applied = self.get_applied_versions().await?;
-        let mut migrated = Vec::new();
+    /// Run `step` while renewing the lock table lease every third of the lease
+    ///
+    /// If a renewal finds the lease lost, `step` is dropped, which rolls back
+    /// its transaction, and the run fails. Renewals that fail because the step
+    /// keeps the database busy are retried at the next interval.
+    async fn while_renewing<T>(
+        &self,
+        lease: &LockLease,
+        step: impl std::future::Future<Output = Result<T>>,
+    ) -> Result<T> {
+        if !matches!(lease, LockLease::Table { .. }) {
+            return step.await;
+        }
 
-        for (version, migration) in &self.migrations {
-            if *version > target_version {
-                break;
+        let heartbeat = async {
+            loop {
+                tokio::time::sleep(self.lock_lease / LOCK_RENEWALS_PER_LEASE).await;
+                match self.renew_lock(lease).await {
+                    Ok(()) => {}
+                    Err(e) if e.is_retryable() => {}
+                    Err(e) => return e,
+                }
             }
+        };
+        tokio::select! {
+            result = step => result,
+            error = heartbeat => Err(error),
+        }
+    }
 
-            if applied.contains(version) {
-                continue;
+    /// Ensure the lock table exists
+    async fn ensure_lock_table(&self) -> Result<()> {
+        let create_table_sql = format!(
+            "CREATE TABLE IF NOT EXISTS {} (
+                id INTEGER PRIMARY KEY NOT NULL,
+                owner TEXT NOT NULL,
+                expires_at BIGINT NOT NULL
+            )",
+            self.lock_table_name()
+        );
+        self.db.execute(&create_table_sql).await?;
+        Ok(())
+    }
+
+    /// Try once to take the lock table lease
+    ///
+    /// An expired lease is cleared first; the primary key lets only one runner
+    /// insert the lock row.
+    async fn try_lock_table(&self, owner: &str) -> Result<bool> {
+        let dialect = self.dialect();
+        let now = now_millis();
+
+        let clear_sql = format!(
+            "DELETE FROM {} WHERE id = 1 AND expires_at < {}",
+            self.lock_table_name(),
+            dialect.placeholder(1)
+        );
+        self.db
+            .execute_with_params(&clear_sql, &[DatabaseValue::Long(now)])
+            .await?;
+
+        let insert_sql = format!(
+            "INSERT INTO {} (id, owner, expires_at) VALUES (1, {}, {})",
+            self.lock_table_name(),
+            dialect.placeholder(1),
+            dialect.placeholder(2)
+        );
+        let inserted = self
+            .db
+            .execute_with_params(
+                &insert_sql,
+                &[
+                    DatabaseValue::String(owner.to_string()),
+                    DatabaseValue::Long(now + duration_millis(self.lock_lease)),
+                ],
+            )
+            .await;
+        match inserted {
+            Ok(_) => Ok(true),
+            // The row already exists while another runner holds the lock
+            Err(e) if e.is_unique_violation() => Ok(false),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Try once to take the PostgreSQL or MySQL advisory lock
+    async fn try_advisory_lock(&self) -> Result<bool> {
+        let lock_sql = match self.dialect() {
+            Dialect::Postgres => "SELECT pg_try_advisory_lock($1) AS locked",
+            _ => "SELECT GET_LOCK(?, 0) AS locked",
+        };
+        let rows = self
+            .db
+            .query_with_params(lock_sql, &[self.advisory_key()])
+            .await?;
+        Ok(is_true(&rows, "locked"))
+    }
+
+    /// Fail unless lock and unlock are sure to run on the same connection
+    ///
+    /// Advisory locks belong to a connection, and a pooled backend may run each
+    /// statement on a different one.
+    async fn ensure_single_connection(&self) -> Result<()> {
+        if let Some(tx) = self.db.begin_owned(TransactionOptions::default()).await? {
+            tx.rollback().await?;
+            return Err(DatabaseError::unsupported(
+                "MigrationLock::Advisory needs a single-connection backend; use MigrationLock::Table with connection pools",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Advisory lock key: a 64-bit number on PostgreSQL, a name on MySQL
+    fn advisory_key(&self) -> DatabaseValue {
+        match self.dialect() {
+            Dialect::Postgres => {
+                let digest = Sha256::digest(self.table_name.as_bytes());
+                let mut key = [0u8; 8];
+                key.copy_from_slice(&digest[..8]);
+                DatabaseValue::Long(i64::from_be_bytes(key))
             }
+            _ => DatabaseValue::String(self.table_name.clone()),
+        }
+    }
+}
 
-            // Execute the up migration
-            self.db.execute(migration.up_sql()).await?;
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::sqlite::SqliteDatabase;
 
-            // Record the migration
-            let insert_sql = format!(
-                "INSERT INTO {} (version, name, applied_at) VALUES (?, ?, ?)",
-                self.table_name
+    async fn create_test_db() -> SqliteDatabase {
+        let db = SqliteDatabase::new();
+        db.connect(":memory:").await.unwrap();
+        db
+    }
+
+    #[tokio::test]
+    async fn test_migration_creation() {
+        let migration = Migration::new(
+            1,
+            "create_users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY)",
+            "DROP TABLE users",
+        );
+
+        assert_eq!(migration.version(), 1);
+        assert_eq!(migration.name(), "create_users");
+        assert_eq!(
+            migration.up_sql(),
+            "CREATE TABLE users (id INTEGER PRIMARY KEY)"
+        );
+        assert_eq!(migration.down_sql(), "DROP TABLE users");
+    }
+
+    #[test]
+    fn test_from_sql_files() {
+        let migrations = Migration::from_sql_files([
+            ("0010_add_index.up.sql", "CREATE INDEX idx ON users (name)"),
+            ("0002_create_users.down.sql", "DROP TABLE users"),
+            (
+                "0002_create_users.up.sql",
+                "CREATE TABLE users (id INTEGER)",
+            ),
+        ])
+        .unwrap();
+        assert_eq!(migrations.len(), 2);
+        assert_eq!(migrations[0].version(), 2);
+        assert_eq!(migrations[0].name(), "create_users");
+        assert_eq!(migrations[0].down_sql(), "DROP TABLE users");
+        assert_eq!(migrations[1].version(), 10);
+        assert_eq!(migrations[1].down_sql(), "");
+
+        for files in [
+            vec![("create_users.up.sql", "")],
+            vec![("0001_.up.sql", "")],
+            vec![("0001_users.sql", "")],
+            vec![("0001_a.up.sql", ""), ("0001_b.up.sql", "")],
+            vec![("0001_a.up.sql", ""), ("01_a.up.sql", "")],
+            vec![("0001_a.down.sql", "")],
+        ] {
+            assert!(
+                matches!(
+                    Migration::from_sql_files(files.clone()),
+                    Err(DatabaseError::Migration(_))
+                ),
+                "{:?} should be rejected",
+                files
             );
+        }
+    }
 
-            let now = std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_secs() as i64;
+    #[tokio::test]
+    async fn test_load_dir() {
+        let dir = concat!(env!("CARGO_MANIFEST_DIR"), "/tests/fixtures/migrations");
+        let migrations = Migration::load_dir(dir).unwrap();
+        let versions: Vec<i64> = migrations.iter().map(Migration::version).collect();
+        assert_eq!(versions, vec![1, 2]);
 
-            self.db
-                .execute_with_params(
-                    &insert_sql,
-                    &[
-                        DatabaseValue::Long(*version),
-                        DatabaseValue::String(migration.name().to_string()),
-                        DatabaseValue::Long(now),
-                    ],
-                )
-                .await?;
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
+        manager.add_migrations(migrations);
+        assert_eq!(manager.migrate().await.unwrap(), vec![1, 2]);
+        assert!(table_exists(&manager, "audit").await);
+
+        assert!(matches!(
+            Migration::load_dir("/nonexistent/migrations"),
+            Err(DatabaseError::IoError(_))
+        ));
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_embed_migrations() {
+        static MIGRATIONS: EmbeddedMigrations = embed_migrations!("tests/fixtures/migrations");
 
-            migrated.push(*version);
+        let names: Vec<&str> = MIGRATIONS.files().iter().map(|(name, _)| *name).collect();
+        assert_eq!(
+            names,
+            vec![
+                "0001_create_users.down.sql",
+                "0001_create_users.up.sql",
+                "0002_add_email.down.sql",
+                "0002_add_email.up.sql"
+            ]
+        );
+
+        let embedded = MIGRATIONS.migrations().unwrap();
+        let loaded = Migration::load_dir(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/tests/fixtures/migrations"
+        ))
+        .unwrap();
+        assert_eq!(embedded.len(), loaded.len());
+        for (embedded, loaded) in embedded.iter().zip(&loaded) {
+            assert_eq!(embedded.version(), loaded.version());
+            assert_eq!(embedded.up_sql(), loaded.up_sql());
+            assert_eq!(embedded.down_sql(), loaded.down_sql());
         }
+    }
+
+    #[tokio::test]
+    async fn test_ensure_migrations_table() {
+        let db = create_test_db().await;
+        let manager = MigrationManager::new(db);
+
+        manager.ensure_migrations_table().await.unwrap();
+
+        // Verify table was created
+        let result = manager
+            .db
+            .query("SELECT name FROM sqlite_master WHERE type='table' AND name='schema_migrations'")
+            .await
+            .unwrap();
 
-        Ok(migrated)
+        assert_eq!(result.len(), 1);
     }
 
-    /// Rollback migrations to a specific version
-    async fn rollback_to(&self, target_version: i64) -> Result<Vec<i64>> {
-        let applied = self.get_applied_versions().await?;
-        let mut rolled_back = Vec::new();
+    #[tokio::test]
+    async fn test_migrate_single() {
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
 
-        // Process in reverse order
-        for version in applied.iter().rev() {
-            if *version <= target_version {
-                break;
-            }
+        let migration = Migration::new(
+            1,
+            "create_users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
+            "DROP TABLE users",
+        );
 
-            let migration = self.migrations.get(version).ok_or_else(|| {
-                DatabaseError::Migration(format!("Migration {} not found", version))
-            })?;
+        manager.add_migration(migration);
 
-            // Execute the down migration
-            self.db.execute(migration.down_sql()).await?;
+        let migrated = manager.migrate().await.unwrap();
+        assert_eq!(migrated, vec![1]);
 
-            // Remove the migration record
-            let delete_sql = format!("DELETE FROM {} WHERE version = ?", self.table_name);
-            self.db
-                .execute_with_params(&delete_sql, &[DatabaseValue::Long(*version)])
-                .await?;
+        let current_version = manager.current_version().await.unwrap();
+        assert_eq!(current_version, Some(1));
 
-            rolled_back.push(*version);
-        }
+        // Verify table was created
+        let result = manager
+            .db
+            .query("SELECT name FROM sqlite_master WHERE type='table' AND name='users'")
+            .await
+            .unwrap();
 
-        Ok(rolled_back)
+        assert_eq!(result.len(), 1);
     }
 
-    /// Rollback the last N migrations
-    ///
-    /// # Example
-    ///
-    /// ```rust
-    /// use rust_database_system::core::{Database, MigrationManager};
-    ///
-    /// // FIXED: Added generic parameter <D: Database> to match struct signature
-    /// # async fn example<D: Database>(manager: MigrationManager<D>) -> Result<(), Box<dyn std::error::Error>> {
-    /// // Rollback the last 2 migrations
-    /// manager.rollback(2).await?;
-    /// # Ok(())
-    /// # }
-    /// ```
-    pub async fn rollback(&self, steps: usize) -> Result<Vec<i64>> {
-        let applied = self.get_applied_versions().await?;
+    #[tokio::test]
+    async fn test_migrate_multiple() {
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
+
+        manager.add_migration(Migration::new(
+            1,
+            "create_users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
+            "DROP TABLE users",
+        ));
+
+        manager.add_migration(Migration::new(
+            2,
+            "create_posts",
+            "CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT NOT NULL)",
+            "DROP TABLE posts",
+        ));
+
+        let migrated = manager.migrate().await.unwrap();
+        assert_eq!(migrated, vec![1, 2]);
+
+        let current_version = manager.current_version().await.unwrap();
+        assert_eq!(current_version, Some(2));
+    }
+
+    /// Make recording version `version` in the tracking table fail
+    async fn fail_recording(manager: &MigrationManager<SqliteDatabase>, version: i64) {
+        manager.ensure_migrations_table().await.unwrap();
+        manager
+            .db
+            .execute(&format!(
+                "CREATE TRIGGER fail_recording BEFORE INSERT ON schema_migrations \
+                 WHEN NEW.version = {} BEGIN SELECT RAISE(ABORT, 'boom'); END",
+                version
+            ))
+            .await
+            .unwrap();
+    }
+
+    async fn table_exists(manager: &MigrationManager<SqliteDatabase>, table: &str) -> bool {
+        let result = manager
+            .db
+            .query_with_params(
+                "SELECT name FROM sqlite_master WHERE type='table' AND name = ?",
+                &[DatabaseValue::String(table.to_string())],
+            )
+            .await
+            .unwrap();
+        !result.is_empty()
+    }
+
+    #[tokio::test]
+    async fn test_failed_migration_is_atomic() {
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
+
+        manager.add_migration(Migration::new(
+            1,
+            "create_users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY)",
+            "DROP TABLE users",
+        ));
+        manager.add_migration(Migration::new(
+            2,
+            "create_posts",
+            "CREATE TABLE posts (id INTEGER PRIMARY KEY)",
+            "DROP TABLE posts",
+        ));
+        fail_recording(&manager, 2).await;
+
+        assert!(manager.migrate().await.is_err());
+        assert!(!manager.db.in_transaction());
+        assert_eq!(manager.current_version().await.unwrap(), Some(1));
+        // The DDL of the failed migration was rolled back with its record
+        assert!(table_exists(&manager, "users").await);
+        assert!(!table_exists(&manager, "posts").await);
+    }
+
+    #[tokio::test]
+    async fn test_migration_without_transaction() {
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
+
+        let migration = Migration::new(
+            1,
+            "create_users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY)",
+            "DROP TABLE users",
+        )
+        .without_transaction();
+        assert!(!migration.is_transactional());
+        manager.add_migration(migration);
+        fail_recording(&manager, 1).await;
+
+        // Without a transaction the DDL stays even though recording it failed
+        assert!(manager.migrate().await.is_err());
+        assert_eq!(manager.current_version().await.unwrap(), None);
+        assert!(table_exists(&manager, "users").await);
+    }
+
+    #[tokio::test]
+    async fn test_multi_statement_migration() {
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
 
-        if steps == 0 || applied.is_empty() {
-            return Ok(Vec::new());
-        }
+        let up = "
+            CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+            CREATE TABLE audit (user_id INTEGER, note TEXT);
+            -- Record every new user; the trigger body holds its own semicolons
+            CREATE TRIGGER users_audit AFTER INSERT ON users BEGIN
+                INSERT INTO audit (user_id, note) VALUES (NEW.id, 'created; ok');
+            END;
+            INSERT INTO users (name) VALUES ('alice');
+        ";
+        let down = "DROP TRIGGER users_audit; DROP TABLE audit; DROP TABLE users;";
+        manager.add_migration(Migration::new(1, "create_users", up, down));
+        manager.add_migration(
+            Migration::new(2, "seed", "INSERT INTO users (name) VALUES ('bob'); INSERT INTO users (name) VALUES ('carol')", "DELETE FROM users WHERE name IN ('bob', 'carol')")
+                .without_transaction(),
+        );
+
+        manager.migrate().await.unwrap();
+        let audit = manager.db.query("SELECT note FROM audit").await.unwrap();
+        assert_eq!(audit.len(), 3);
+        assert_eq!(audit[0].get("note").unwrap().as_string(), "created; ok");
 
-        let rollback_count = steps.min(applied.len());
-        let mut rolled_back = Vec::new();
+        manager.reset().await.unwrap();
+        assert!(!table_exists(&manager, "users").await);
+        assert!(!table_exists(&manager, "audit").await);
+    }
 
-        for version in applied.iter().rev().take(rollback_count) {
-            let migration = self.migrations.get(version).ok_or_else(|| {
-                DatabaseError::Migration(format!("Migration {} not found", version))
-            })?;
+    /// Code migration inserting a user, e.g. a data backfill
+    fn seed_user(version: i64) -> Migration {
+        Migration::from_fn(
+            version,
+            "seed_user",
+            |ctx| {
+                Box::pin(async move {
+                    let sql = format!(
+                        "INSERT INTO users (id) VALUES ({})",
+                        ctx.dialect().placeholder(1)
+                    );
+                    ctx.execute_with_params(&sql, &[DatabaseValue::Long(7)])
+                        .await?;
+                    Ok(())
+                })
+            },
+            |ctx| {
+                Box::pin(async move {
+                    ctx.execute("DELETE FROM users WHERE id = 7").await?;
+                    Ok(())
+                })
+            },
+        )
+    }
 
-            // Execute the down migration
-            self.db.execute(migration.down_sql()).await?;
+    async fn user_count(manager: &MigrationManager<SqliteDatabase>) -> i64 {
+        let rows = manager
+            .db
+            .query("SELECT COUNT(*) AS n FROM users")
+            .await
+            .unwrap();
+        rows[0].get("n").and_then(DatabaseValue::as_long).unwrap()
+    }
 
-            // Remove the migration record
-            let delete_sql = format!("DELETE FROM {} WHERE version = ?", self.table_name);
-            self.db
-                .execute_with_params(&delete_sql, &[DatabaseValue::Long(*version)])
-                .await?;
+    #[tokio::test]
+    async fn test_code_migration() {
+        struct AddActive;
+
+        #[async_trait]
+        impl MigrationCode for AddActive {
+            async fn up(&self, ctx: &MigrationContext<'_>) -> Result<()> {
+                assert!(ctx.as_database().is_some());
+                ctx.execute_batch(
+                    "ALTER TABLE users ADD COLUMN active INTEGER; UPDATE users SET active = 1;",
+                )
+                .await
+            }
 
-            rolled_back.push(*version);
+            async fn down(&self, ctx: &MigrationContext<'_>) -> Result<()> {
+                assert!(ctx.as_database().is_some());
+                ctx.execute("ALTER TABLE users DROP COLUMN active").await?;
+                Ok(())
+            }
         }
 
-        Ok(rolled_back)
-    }
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
+        manager.add_migrations(users_and_posts());
+        manager.add_migration(seed_user(3));
+        manager
+            .add_migration(Migration::from_code(4, "add_active", AddActive).without_transaction());
+
+        let plan = manager.plan_migrate().await.unwrap();
+        assert_eq!(plan.steps[2].kind, MigrationKind::Code);
+        assert!(!plan.steps[2].irreversible);
+
+        // SQL and code migrations share one version sequence and tracking table
+        assert_eq!(manager.migrate().await.unwrap(), vec![1, 2, 3, 4]);
+        assert_eq!(user_count(&manager).await, 1);
+        let active = manager
+            .db
+            .query("SELECT active FROM users WHERE id = 7")
+            .await
+            .unwrap();
+        assert_eq!(
+            active[0].get("active").and_then(DatabaseValue::as_long),
+            Some(1)
+        );
 
-    /// Get the list of pending migrations
-    pub async fn pending_migrations(&self) -> Result<Vec<&Migration>> {
-        let applied = self.get_applied_versions().await?;
-        let pending: Vec<&Migration> = self
-            .migrations
-            .values()
-            .filter(|m| !applied.contains(&m.version))
-            .collect();
+        let status = manager.migration_status().await.unwrap();
+        assert_eq!(status.get(&3), Some(&MigrationStatus::Applied));
+        assert_eq!(status.get(&4), Some(&MigrationStatus::Applied));
 
-        Ok(pending)
+        assert_eq!(manager.rollback(2).await.unwrap(), vec![4, 3]);
+        assert_eq!(user_count(&manager).await, 0);
+        assert_eq!(manager.current_version().await.unwrap(), Some(2));
     }
 
-    /// Reset the database by rolling back all migrations
-    pub async fn reset(&self) -> Result<Vec<i64>> {
-        let applied = self.get_applied_versions().await?;
-        self.rollback(applied.len()).await
-    }
-}
+    #[tokio::test]
+    async fn test_failed_code_migration_is_atomic() {
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
+        manager.add_migrations(users_and_posts());
+        manager.add_migration(Migration::from_fn(
+            3,
+            "broken_backfill",
+            |ctx| {
+                Box::pin(async move {
+                    assert!(ctx.as_transaction().is_some());
+                    ctx.execute("INSERT INTO users (id) VALUES (1)").await?;
+                    Err(DatabaseError::migration("backfill failed"))
+                })
+            },
+            |_ctx| Box::pin(async { Ok(()) }),
+        ));
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::backends::sqlite::SqliteDatabase;
+        assert!(manager.migrate().await.is_err());
+        assert_eq!(user_count(&manager).await, 0);
+        assert_eq!(manager.current_version().await.unwrap(), Some(2));
+    }
 
-    async fn create_test_db() -> SqliteDatabase {
-        let db = SqliteDatabase::new();
-        db.connect(":memory:").await.unwrap();
-        db
+    #[test]
+    fn test_checksum() {
+        let migration = Migration::new(1, "a", "CREATE TABLE a (id INTEGER);\n", "");
+        assert_eq!(migration.checksum().len(), 64);
+        assert_eq!(
+            Migration::new(1, "a", "", "").checksum(),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+        // Only the up SQL counts, and line endings are normalized
+        let crlf = Migration::new(1, "b", "CREATE TABLE a (id INTEGER);\r\n", "DROP TABLE a");
+        assert_eq!(migration.checksum(), crlf.checksum());
+        let edited = Migration::new(1, "a", "CREATE TABLE a (id BIGINT);\n", "");
+        assert_ne!(migration.checksum(), edited.checksum());
     }
 
     #[tokio::test]
-    async fn test_migration_creation() {
-        let migration = Migration::new(
+    async fn test_drift_detection() {
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
+
+        manager.add_migration(Migration::new(
             1,
             "create_users",
             "CREATE TABLE users (id INTEGER PRIMARY KEY)",
             "DROP TABLE users",
-        );
+        ));
+        manager.add_migration(Migration::new(
+            2,
+            "create_posts",
+            "CREATE TABLE posts (id INTEGER PRIMARY KEY)",
+            "DROP TABLE posts",
+        ));
+        manager.migrate().await.unwrap();
+        assert!(manager.check_drift().await.unwrap().is_empty());
 
-        assert_eq!(migration.version(), 1);
-        assert_eq!(migration.name(), "create_users");
+        // Edit an applied migration and forget another one
+        manager.add_migration(Migration::new(
+            1,
+            "create_users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT)",
+            "DROP TABLE users",
+        ));
+        manager.migrations.remove(&2);
+        manager.add_migration(Migration::new(
+            3,
+            "create_tags",
+            "CREATE TABLE tags (id INTEGER PRIMARY KEY)",
+            "DROP TABLE tags",
+        ));
+
+        let status = manager.migration_status().await.unwrap();
+        assert_eq!(status.get(&1), Some(&MigrationStatus::Modified));
+        assert_eq!(status.get(&2), Some(&MigrationStatus::Missing));
+        assert_eq!(status.get(&3), Some(&MigrationStatus::Pending));
+
+        let drift = manager.check_drift().await.unwrap();
+        assert_eq!(drift.len(), 2);
+        assert!(matches!(&drift[0], Drift::Modified { version: 1, .. }));
         assert_eq!(
-            migration.up_sql(),
-            "CREATE TABLE users (id INTEGER PRIMARY KEY)"
+            drift[1],
+            Drift::Missing {
+                version: 2,
+                name: "create_posts".to_string()
+            }
         );
-        assert_eq!(migration.down_sql(), "DROP TABLE users");
+
+        // The default policy refuses to run
+        match manager.migrate().await {
+            Err(DatabaseError::Migration(message)) => {
+                assert!(message.contains("migration 1 (create_users) was modified"));
+                assert!(
+                    message.contains("migration 2 (create_posts) is applied but not registered")
+                );
+            }
+            other => panic!("expected a drift error, got {:?}", other),
+        }
+        assert!(!table_exists(&manager, "tags").await);
+
+        let reported = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let manager = manager
+            .with_drift_policy(DriftPolicy::Warn)
+            .with_drift_hook({
+                let reported = Arc::clone(&reported);
+                move |drift| reported.lock().unwrap().push(drift.clone())
+            });
+        assert_eq!(manager.migrate().await.unwrap(), vec![3]);
+        assert_eq!(*reported.lock().unwrap(), drift);
     }
 
     #[tokio::test]
-    async fn test_ensure_migrations_table() {
+    async fn test_checksum_added_to_existing_table() {
         let db = create_test_db().await;
-        let manager = MigrationManager::new(db);
+        db.execute(
+            "CREATE TABLE schema_migrations (
+                version INTEGER PRIMARY KEY NOT NULL,
+                name TEXT NOT NULL,
+                applied_at INTEGER NOT NULL
+            )",
+        )
+        .await
+        .unwrap();
+        db.execute("CREATE TABLE users (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
+        db.execute("INSERT INTO schema_migrations VALUES (1, 'create_users', 0)")
+            .await
+            .unwrap();
 
-        manager.ensure_migrations_table().await.unwrap();
+        let mut manager = MigrationManager::new(db);
+        let migration = Migration::new(
+            1,
+            "create_users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY)",
+            "DROP TABLE users",
+        );
+        let checksum = migration.checksum();
+        manager.add_migration(migration);
 
-        // Verify table was created
+        assert!(manager.migrate().await.unwrap().is_empty());
         let result = manager
             .db
-            .query("SELECT name FROM sqlite_master WHERE type='table' AND name='schema_migrations'")
+            .query("SELECT checksum FROM schema_migrations WHERE version = 1")
             .await
             .unwrap();
+        assert_eq!(result[0].get("checksum").unwrap().as_string(), checksum);
+    }
 
-        assert_eq!(result.len(), 1);
+    /// Open a connection to a database file shared between managers
+    async fn open_shared_db(path: &std::path::Path) -> SqliteDatabase {
+        let db = SqliteDatabase::new();
+        db.connect(path.to_str().unwrap()).await.unwrap();
+        db.query("PRAGMA busy_timeout = 5000").await.unwrap();
+        db
+    }
+
+    fn users_and_posts() -> Vec<Migration> {
+        vec![
+            Migration::new(
+                1,
+                "create_users",
+                "CREATE TABLE users (id INTEGER PRIMARY KEY)",
+                "DROP TABLE users",
+            ),
+            Migration::new(
+                2,
+                "create_posts",
+                "CREATE TABLE posts (id INTEGER PRIMARY KEY)",
+                "DROP TABLE posts",
+            ),
+        ]
     }
 
     #[tokio::test]
-    async fn test_migrate_single() {
+    async fn test_migration_lock() {
+        let path = std::env::temp_dir().join(format!("rds_lock_test_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let mut holder = MigrationManager::new(open_shared_db(&path).await);
+        holder.add_migrations(users_and_posts());
+        let mut waiter = MigrationManager::new(open_shared_db(&path).await)
+            .with_lock_timeout(Duration::from_millis(100));
+        waiter.add_migrations(users_and_posts());
+
+        // A second run gives up while the lock is held
+        let lease = holder.acquire_lock().await.unwrap();
+        match waiter.migrate().await {
+            Err(DatabaseError::Migration(message)) => {
+                assert!(message.contains("Timed out after 100ms"), "{}", message);
+                assert!(message.contains("held by pid"), "{}", message);
+            }
+            other => panic!("expected a lock timeout, got {:?}", other),
+        }
+        holder.release_lock(lease, Ok(())).await.unwrap();
+        assert_eq!(waiter.migrate().await.unwrap(), vec![1, 2]);
+
+        // An expired lease is taken over
+        holder
+            .db
+            .execute("INSERT INTO schema_migrations_lock VALUES (1, 'crashed', 0)")
+            .await
+            .unwrap();
+        assert_eq!(waiter.rollback(1).await.unwrap(), vec![2]);
+
+        // Concurrent runs apply every migration exactly once
+        let (first, second) = tokio::join!(holder.migrate(), waiter.migrate());
+        let mut migrated = first.unwrap();
+        migrated.extend(second.unwrap());
+        assert_eq!(migrated, vec![2]);
+        let lock_rows = holder
+            .db
+            .query("SELECT * FROM schema_migrations_lock")
+            .await
+            .unwrap();
+        assert!(lock_rows.is_empty());
+
+        drop(holder);
+        drop(waiter);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_migration_lock_errors_are_not_contention() {
         let db = create_test_db().await;
-        let mut manager = MigrationManager::new(db);
+        // Every insert into this lock table fails, but not because the lock is held
+        db.execute(
+            "CREATE TABLE schema_migrations_lock (
+                id INTEGER PRIMARY KEY NOT NULL,
+                owner TEXT NOT NULL,
+                expires_at BIGINT NOT NULL,
+                host TEXT NOT NULL
+            )",
+        )
+        .await
+        .unwrap();
+        let mut manager = MigrationManager::new(db).with_lock_timeout(Duration::from_secs(60));
+        manager.add_migrations(users_and_posts());
+
+        match manager.migrate().await {
+            Err(DatabaseError::SqliteError(e)) => {
+                assert!(e.to_string().contains("NOT NULL"), "{}", e)
+            }
+            other => panic!("expected the insert error, got {:?}", other),
+        }
+    }
 
-        let migration = Migration::new(
+    #[tokio::test]
+    async fn test_migration_lock_renewed_during_step() {
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db).with_lock_lease(Duration::from_millis(150));
+        manager.add_migration(Migration::from_fn(
             1,
-            "create_users",
-            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
-            "DROP TABLE users",
-        );
+            "slow",
+            |ctx| {
+                Box::pin(async move {
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                    // Without renewal the lease would have expired by now
+                    let rows = ctx
+                        .query("SELECT expires_at FROM schema_migrations_lock")
+                        .await?;
+                    let expires_at = rows[0].get("expires_at").and_then(DatabaseValue::as_long);
+                    assert!(expires_at > Some(now_millis()));
+                    Ok(())
+                })
+            },
+            |_ctx| Box::pin(async { Ok(()) }),
+        ));
+        assert_eq!(manager.migrate().await.unwrap(), vec![1]);
 
-        manager.add_migration(migration);
+        // A lease taken over while a step runs stops the run and rolls the step back
+        manager.add_migration(Migration::from_fn(
+            2,
+            "taken_over",
+            |ctx| {
+                Box::pin(async move {
+                    ctx.execute("CREATE TABLE half_done (id INTEGER)").await?;
+                    ctx.execute("UPDATE schema_migrations_lock SET owner = 'other'")
+                        .await?;
+                    tokio::time::sleep(Duration::from_millis(500)).await;
+                    Ok(())
+                })
+            },
+            |_ctx| Box::pin(async { Ok(()) }),
+        ));
+        match manager.migrate().await {
+            Err(DatabaseError::Migration(message)) => {
+                assert!(message.contains("taken over"), "{}", message)
+            }
+            other => panic!("expected a lost lease, got {:?}", other),
+        }
+        assert_eq!(manager.current_version().await.unwrap(), Some(1));
+        assert!(!table_exists(&manager, "half_done").await);
+    }
 
-        let migrated = manager.migrate().await.unwrap();
-        assert_eq!(migrated, vec![1]);
+    #[tokio::test]
+    async fn test_advisory_lock_unsupported_on_sqlite() {
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db).with_lock(MigrationLock::Advisory);
+        manager.add_migrations(users_and_posts());
+        assert!(matches!(
+            manager.migrate().await,
+            Err(DatabaseError::UnsupportedOperation(_))
+        ));
 
-        let current_version = manager.current_version().await.unwrap();
-        assert_eq!(current_version, Some(1));
+        let mut manager = MigrationManager::new(manager.db).with_lock(MigrationLock::None);
+        manager.add_migrations(users_and_posts());
+        assert_eq!(manager.migrate().await.unwrap(), vec![1, 2]);
+        assert!(!table_exists(&manager, "schema_migrations_lock").await);
+    }
 
-        // Verify table was created
-        let result = manager
-            .db
-            .query("SELECT name FROM sqlite_master WHERE type='table' AND name='users'")
+    #[tokio::test]
+    async fn test_advisory_lock_unsupported_on_pools() {
+        use crate::backends::PooledSqliteDatabase;
+
+        let path =
+            std::env::temp_dir().join(format!("rds_advisory_pool_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let db = PooledSqliteDatabase::new(path.to_str().unwrap())
             .await
             .unwrap();
+        let mut manager = MigrationManager::new(db).with_lock(MigrationLock::Advisory);
+        manager.add_migrations(users_and_posts());
+        match manager.migrate().await {
+            Err(DatabaseError::UnsupportedOperation(message)) => {
+                assert!(message.contains("connection pools"), "{}", message)
+            }
+            other => panic!("expected advisory locks to be refused, got {:?}", other),
+        }
 
-        assert_eq!(result.len(), 1);
+        drop(manager);
+        let _ = std::fs::remove_file(&path);
     }
 
     #[tokio::test]
-    async fn test_migrate_multiple() {
+    async fn test_irreversible_migration() {
         let db = create_test_db().await;
         let mut manager = MigrationManager::new(db);
+        manager.add_migrations(users_and_posts());
+        manager.add_migration(
+            Migration::new(
+                3,
+                "create_comments",
+                "CREATE TABLE comments (id INTEGER PRIMARY KEY)",
+                "DROP TABLE comments",
+            )
+            .irreversible(),
+        );
+        manager.migrate().await.unwrap();
 
+        // Nothing is reverted, not even the reversible migrations after it
+        let error = manager.rollback(2).await.unwrap_err();
+        assert!(matches!(error, DatabaseError::Migration(_)));
+        assert!(error
+            .to_string()
+            .contains("Migration 3 (create_comments) is irreversible"));
+        assert!(manager.reset().await.is_err());
+        assert!(manager.migrate_to(1).await.is_err());
+        assert!(manager.plan_rollback(1).await.is_err());
+        assert_eq!(manager.current_version().await.unwrap(), Some(3));
+        assert!(table_exists(&manager, "comments").await);
+
+        // Empty down SQL is irreversible as well
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
         manager.add_migration(Migration::new(
             1,
             "create_users",
-            "CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)",
-            "DROP TABLE users",
+            "CREATE TABLE users (id INTEGER PRIMARY KEY)",
+            " ",
         ));
+        assert!(manager.plan_migrate().await.unwrap().steps[0].irreversible);
+        manager.migrate().await.unwrap();
+        assert!(manager.rollback(1).await.is_err());
+        assert!(table_exists(&manager, "users").await);
+    }
+
+    #[tokio::test]
+    async fn test_baseline() {
+        let db = create_test_db().await;
+        db.execute("CREATE TABLE users (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
+        db.execute("CREATE TABLE posts (id INTEGER PRIMARY KEY)")
+            .await
+            .unwrap();
 
+        let mut manager = MigrationManager::new(db);
+        manager.add_migrations(users_and_posts());
         manager.add_migration(Migration::new(
-            2,
-            "create_posts",
-            "CREATE TABLE posts (id INTEGER PRIMARY KEY, title TEXT NOT NULL)",
-            "DROP TABLE posts",
+            3,
+            "create_comments",
+            "CREATE TABLE comments (id INTEGER PRIMARY KEY)",
+            "DROP TABLE comments",
         ));
 
-        let migrated = manager.migrate().await.unwrap();
-        assert_eq!(migrated, vec![1, 2]);
+        // Running 1 and 2 would fail: their tables exist already
+        assert_eq!(manager.baseline(2).await.unwrap(), vec![1, 2]);
+        assert_eq!(manager.baseline(2).await.unwrap(), Vec::<i64>::new());
+        assert!(manager.check_drift().await.unwrap().is_empty());
 
-        let current_version = manager.current_version().await.unwrap();
-        assert_eq!(current_version, Some(2));
+        let status = manager.migration_status().await.unwrap();
+        assert_eq!(status.get(&2), Some(&MigrationStatus::Applied));
+        assert_eq!(status.get(&3), Some(&MigrationStatus::Pending));
+
+        assert_eq!(manager.migrate().await.unwrap(), vec![3]);
+        assert!(table_exists(&manager, "comments").await);
+    }
+
+    #[tokio::test]
+    async fn test_out_of_order_migrations() {
+        let db = create_test_db().await;
+        let mut manager =
+            MigrationManager::new(db).with_out_of_order_policy(OutOfOrderPolicy::Fail);
+        manager.add_migration(users_and_posts().remove(0));
+        manager.add_migration(Migration::new(
+            3,
+            "create_comments",
+            "CREATE TABLE comments (id INTEGER PRIMARY KEY)",
+            "DROP TABLE comments",
+        ));
+        manager.migrate().await.unwrap();
+
+        // Version 2 arrives after 3 was deployed
+        manager.add_migration(users_and_posts().remove(1));
+        manager.add_migration(Migration::new(
+            4,
+            "create_tags",
+            "CREATE TABLE tags (id INTEGER PRIMARY KEY)",
+            "DROP TABLE tags",
+        ));
+        let older: Vec<i64> = manager
+            .out_of_order_migrations()
+            .await
+            .unwrap()
+            .iter()
+            .map(|migration| migration.version())
+            .collect();
+        assert_eq!(older, vec![2]);
+
+        let error = manager.migrate().await.unwrap_err();
+        assert!(matches!(error, DatabaseError::Migration(_)));
+        assert!(error
+            .to_string()
+            .contains("older than current version 3: 2"));
+        assert!(manager.plan_migrate().await.is_err());
+        assert_eq!(manager.current_version().await.unwrap(), Some(3));
+
+        let manager = manager.with_out_of_order_policy(OutOfOrderPolicy::Skip);
+        assert_eq!(manager.migrate().await.unwrap(), vec![4]);
+        assert!(!manager.is_applied(2).await.unwrap());
+
+        // The default applies them
+        let manager = manager.with_out_of_order_policy(OutOfOrderPolicy::default());
+        assert_eq!(manager.plan_migrate().await.unwrap().versions(), vec![2]);
+        assert_eq!(manager.migrate().await.unwrap(), vec![2]);
+        assert!(manager.out_of_order_migrations().await.unwrap().is_empty());
     }
 
     #[tokio::test]
@@ -625,6 +3056,121 @@ mod tests {
         assert_eq!(current_version, Some(1));
     }
 
+    #[tokio::test]
+    async fn test_migration_plan() {
+        let db = create_test_db().await;
+        let mut manager = MigrationManager::new(db);
+        manager.add_migrations(users_and_posts());
+        manager.add_migration(
+            Migration::new(3, "index_posts", "CREATE INDEX posts_id ON posts (id)", "")
+                .without_transaction(),
+        );
+
+        // Planning touches nothing, not even the tracking table
+        let plan = manager.plan_migrate().await.unwrap();
+        assert_eq!(plan.versions(), vec![1, 2, 3]);
+        assert!(plan
+            .steps
+            .iter()
+            .all(|step| step.direction == MigrationDirection::Up));
+        assert_eq!(
+            plan.steps[0].sql,
+            "CREATE TABLE users (id INTEGER PRIMARY KEY)"
+        );
+        assert!(plan.steps[2].irreversible);
+        assert!(!plan.steps[2].transactional);
+        assert!(!table_exists(&manager, "schema_migrations").await);
+        assert!(!table_exists(&manager, "users").await);
+
+        assert_eq!(
+            manager.plan_migrate_to(1).await.unwrap().versions(),
+            vec![1]
+        );
+        assert!(manager.plan_rollback(1).await.unwrap().is_empty());
+
+        manager.migrate_to(2).await.unwrap();
+        assert_eq!(manager.plan_migrate().await.unwrap().versions(), vec![3]);
+
+        let plan = manager.plan_rollback(1).await.unwrap();
+        assert_eq!(plan.versions(), vec![2]);
+        assert_eq!(plan.steps[0].direction, MigrationDirection::Down);
+        assert_eq!(plan.steps[0].sql, "DROP TABLE posts");
+
+        assert_eq!(
+            manager.plan_migrate_to(0).await.unwrap().versions(),
+            vec![2, 1]
+        );
+        assert_eq!(manager.plan_reset().await.unwrap().versions(), vec![2, 1]);
+
+        // The plan matches what the run then does
+        let plan = manager.plan_migrate().await.unwrap();
+        assert_eq!(manager.migrate().await.unwrap(), plan.versions());
+        assert_eq!(
+            manager
+                .plan_migrate()
+                .await
+                .unwrap()
+                .render(PlanFormat::Text),
+            "Nothing to migrate\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_migration_plan_schema_qualified_table() {
+        let db = create_test_db().await;
+        db.execute("ATTACH DATABASE ':memory:' AS app")
+            .await
+            .unwrap();
+        let mut manager = MigrationManager::with_table_name(db, "app.schema_migrations");
+        manager.add_migrations(users_and_posts());
+        assert_eq!(manager.plan_migrate().await.unwrap().versions(), vec![1, 2]);
+
+        manager.migrate_to(1).await.unwrap();
+        assert_eq!(manager.plan_migrate().await.unwrap().versions(), vec![2]);
+        assert_eq!(manager.plan_rollback(1).await.unwrap().versions(), vec![1]);
+    }
+
+    #[test]
+    fn test_migration_plan_rendering() {
+        let plan = MigrationPlan {
+            steps: vec![
+                MigrationStep {
+                    version: 2,
+                    name: "create_posts".to_string(),
+                    direction: MigrationDirection::Up,
+                    kind: MigrationKind::Sql,
+                    sql: "CREATE TABLE posts (id INTEGER);\nCREATE INDEX posts_id ON posts (id);\n"
+                        .to_string(),
+                    transactional: true,
+                    irreversible: false,
+                },
+                MigrationStep {
+                    version: 1,
+                    name: "seed".to_string(),
+                    direction: MigrationDirection::Down,
+                    kind: MigrationKind::Code,
+                    sql: String::new(),
+                    transactional: false,
+                    irreversible: true,
+                },
+            ],
+        };
+
+        assert_eq!(
+            plan.render(PlanFormat::Text),
+            "1. UP 2 create_posts\n   CREATE TABLE posts (id INTEGER);\n   CREATE INDEX posts_id ON posts (id);\n\
+             2. DOWN 1 seed (Rust code, irreversible, no transaction)\n"
+        );
+
+        let json: serde_json::Value = serde_json::from_str(&plan.render(PlanFormat::Json)).unwrap();
+        assert_eq!(json["steps"][0]["version"], 2);
+        assert_eq!(json["steps"][0]["direction"], "up");
+        assert_eq!(json["steps"][1]["direction"], "down");
+        assert_eq!(json["steps"][1]["kind"], "code");
+        assert_eq!(json["steps"][1]["irreversible"], true);
+        assert_eq!(json["steps"][1]["transactional"], false);
+    }
+
     #[tokio::test]
     async fn test_migration_status() {
         let db = create_test_db().await;
@@ -5,19 +5,38 @@
 
 pub mod database;
 pub mod database_types;
+pub mod dialect;
 pub mod error;
+pub mod identifier;
 pub mod migration;
 pub mod query_builder;
+pub mod result_set;
+pub mod retry;
+pub mod row;
+pub mod script;
 pub mod transaction;
 pub mod value;
 
 // Re-export commonly used types
 pub use database::{ConnectionBuilder, Database};
 pub use database_types::DatabaseType;
+pub use dialect::Dialect;
 pub use error::{DatabaseError, Result};
-pub use migration::{Migration, MigrationManager, MigrationStatus};
+pub use identifier::Identifier;
+pub use migration::{
+    Drift, DriftHook, DriftPolicy, EmbeddedMigrations, Migration, MigrationCode, MigrationContext,
+    MigrationDirection, MigrationKind, MigrationLock, MigrationManager, MigrationPlan,
+    MigrationStatus, MigrationStep, OutOfOrderPolicy, PlanFormat,
+};
 pub use query_builder::{
-    DeleteBuilder, InsertBuilder, OrderDirection, SelectBuilder, UpdateBuilder,
+    Comparison, DeleteBuilder, Filter, InsertBuilder, JoinCondition, Operator, OrderDirection,
+    SelectBuilder, UpdateBuilder,
+};
+pub use result_set::{ColumnInfo, ResultSet, Row, RowStream};
+pub use retry::{Retried, RetryPolicy};
+pub use row::{FromRow, FromValue};
+pub use transaction::{
+    AccessMode, DropReport, IsolationLevel, LockingMode, Transaction, TransactionGuard,
+    TransactionOptions,
 };
-pub use transaction::TransactionGuard;
 pub use value::{DatabaseResult, DatabaseRow, DatabaseValue};
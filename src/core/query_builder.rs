@@ -1,8 +1,15 @@
 //! Type-safe SQL query builder
 //!
 //! Provides a fluent API for building SQL queries with automatic parameter binding
-//! to prevent SQL injection attacks.
+//! to prevent SQL injection attacks. Every builder renders against a [`Dialect`],
+//! so the same expression runs on SQLite, PostgreSQL and MySQL.
+//!
+//! Table and column names are taken as [`Identifier`]s and quoted per dialect, so
+//! they are safe to take from user input; raw SQL fragments must be wrapped in
+//! [`Identifier::raw`] or [`JoinCondition::raw`] explicitly.
 
+use super::dialect::Dialect;
+pub use super::identifier::Identifier;
 use super::value::DatabaseValue;
 
 /// SQL comparison operators
@@ -22,12 +29,22 @@ pub enum Operator {
     Ge,
     /// LIKE pattern matching
     Like,
+    /// Case-insensitive LIKE (ILIKE on PostgreSQL, LOWER() on both sides elsewhere)
+    ILike,
     /// IN set membership
     In,
+    /// NOT IN set membership
+    NotIn,
+    /// BETWEEN two bounds (inclusive)
+    Between,
     /// IS NULL
     IsNull,
     /// IS NOT NULL
     IsNotNull,
+    /// Equal to the dialect's TRUE literal
+    IsTrue,
+    /// Equal to the dialect's FALSE literal
+    IsFalse,
 }
 
 impl Operator {
@@ -40,19 +57,492 @@ impl Operator {
             Operator::Gt => ">",
             Operator::Ge => ">=",
             Operator::Like => "LIKE",
+            Operator::ILike => "ILIKE",
             Operator::In => "IN",
+            Operator::NotIn => "NOT IN",
+            Operator::Between => "BETWEEN",
             Operator::IsNull => "IS NULL",
             Operator::IsNotNull => "IS NOT NULL",
+            Operator::IsTrue | Operator::IsFalse => "=",
+        }
+    }
+}
+
+/// Operators that compare one column with another
+///
+/// The subset of [`Operator`] accepted by [`Filter::columns`] and
+/// [`SelectBuilder::where_column`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    /// Equal to (=)
+    Eq,
+    /// Not equal to (!=)
+    Ne,
+    /// Less than (<)
+    Lt,
+    /// Less than or equal (<=)
+    Le,
+    /// Greater than (>)
+    Gt,
+    /// Greater than or equal (>=)
+    Ge,
+    /// LIKE pattern matching, with the right column as the pattern
+    Like,
+    /// Case-insensitive LIKE (ILIKE on PostgreSQL, LOWER() on both sides elsewhere)
+    ILike,
+}
+
+impl From<Comparison> for Operator {
+    fn from(comparison: Comparison) -> Self {
+        match comparison {
+            Comparison::Eq => Operator::Eq,
+            Comparison::Ne => Operator::Ne,
+            Comparison::Lt => Operator::Lt,
+            Comparison::Le => Operator::Le,
+            Comparison::Gt => Operator::Gt,
+            Comparison::Ge => Operator::Ge,
+            Comparison::Like => Operator::Like,
+            Comparison::ILike => Operator::ILike,
         }
     }
 }
 
+/// Right-hand side of a condition
+#[derive(Debug, Clone)]
+enum Operand {
+    /// No operand (IS NULL, IS TRUE, ...)
+    None,
+    /// A single bound value
+    Value(DatabaseValue),
+    /// A list of bound values, one placeholder each
+    List(Vec<DatabaseValue>),
+    /// Lower and upper bound
+    Range(DatabaseValue, DatabaseValue),
+    /// Another column
+    Column(Identifier),
+    /// A subquery whose parameters are merged into the statement
+    Subquery(Box<SelectBuilder>),
+}
+
 /// WHERE clause condition
 #[derive(Debug, Clone)]
 pub struct Condition {
-    column: String,
+    column: Identifier,
     operator: Operator,
-    value: Option<DatabaseValue>,
+    operand: Operand,
+}
+
+impl Condition {
+    /// Render the condition, allocating placeholders for its values
+    fn render(&self, dialect: Dialect, placeholders: &mut Placeholders) -> String {
+        let column = self.column.render(dialect);
+        let operator = self.operator.as_sql();
+        match (&self.operand, self.operator) {
+            (_, Operator::IsTrue | Operator::IsFalse) => format!(
+                "{} = {}",
+                column,
+                dialect.boolean_literal(self.operator == Operator::IsTrue)
+            ),
+            (Operand::None, _) => format!("{} {}", column, operator),
+            (Operand::Value(_), Operator::ILike) if dialect != Dialect::Postgres => {
+                format!("LOWER({}) LIKE LOWER({})", column, placeholders.next())
+            }
+            (Operand::Value(_), _) => format!("{} {} {}", column, operator, placeholders.next()),
+            // An empty list matches nothing (IN) or everything (NOT IN)
+            (Operand::List(values), _) if values.is_empty() => {
+                if self.operator == Operator::NotIn {
+                    "1 = 1".to_string()
+                } else {
+                    "1 = 0".to_string()
+                }
+            }
+            (Operand::List(values), _) => {
+                let list: Vec<String> = values.iter().map(|_| placeholders.next()).collect();
+                format!("{} {} ({})", column, operator, list.join(", "))
+            }
+            (Operand::Range(..), _) => format!(
+                "{} {} {} AND {}",
+                column,
+                operator,
+                placeholders.next(),
+                placeholders.next()
+            ),
+            (Operand::Column(other), Operator::ILike) if dialect != Dialect::Postgres => {
+                format!("LOWER({}) LIKE LOWER({})", column, other.render(dialect))
+            }
+            (Operand::Column(other), _) => {
+                format!("{} {} {}", column, operator, other.render(dialect))
+            }
+            (Operand::Subquery(subquery), _) => format!(
+                "{} {} ({})",
+                column,
+                operator,
+                subquery.render(dialect, placeholders)
+            ),
+        }
+    }
+
+    /// Append the bound parameters in render order
+    fn collect_params(&self, params: &mut Vec<DatabaseValue>) {
+        match &self.operand {
+            Operand::None | Operand::Column(_) => {}
+            Operand::Value(value) => params.push(value.clone()),
+            Operand::List(values) => params.extend(values.iter().cloned()),
+            Operand::Range(low, high) => params.extend([low.clone(), high.clone()]),
+            Operand::Subquery(subquery) => params.extend(subquery.params()),
+        }
+    }
+}
+
+/// Boolean condition tree for WHERE and HAVING clauses
+///
+/// Leaves are single [`Condition`]s; `And`/`Or` groups nest arbitrarily and are
+/// parenthesized when rendered inside another group. Parameters are bound in the
+/// order the conditions appear in the rendered SQL.
+///
+/// # Example
+///
+/// ```
+/// use rust_database_system::core::query_builder::{Filter, SelectBuilder};
+///
+/// // status = ? AND (role = ? OR NOT (banned = ?))
+/// let query = SelectBuilder::new("users").filter(
+///     Filter::eq("status", "active")
+///         .and(Filter::eq("role", "admin").or(!Filter::eq("banned", true))),
+/// );
+///
+/// assert_eq!(
+///     query.build(),
+///     "SELECT * FROM users WHERE status = ? AND (role = ? OR NOT (banned = ?))"
+/// );
+/// assert_eq!(query.params().len(), 3);
+/// ```
+#[derive(Debug, Clone)]
+pub enum Filter {
+    /// A single comparison
+    Condition(Condition),
+    /// All children must hold (an empty group is always true)
+    And(Vec<Filter>),
+    /// At least one child must hold (an empty group is always false)
+    Or(Vec<Filter>),
+    /// The child must not hold
+    Not(Box<Filter>),
+    /// The subquery returns at least one row
+    Exists(Box<SelectBuilder>),
+    /// The subquery returns no rows
+    NotExists(Box<SelectBuilder>),
+}
+
+impl Filter {
+    fn leaf(column: impl Into<Identifier>, operator: Operator, operand: Operand) -> Self {
+        Filter::Condition(Condition {
+            column: column.into(),
+            operator,
+            operand,
+        })
+    }
+
+    /// `column = value`
+    pub fn eq(column: impl Into<Identifier>, value: impl Into<DatabaseValue>) -> Self {
+        Self::leaf(column, Operator::Eq, Operand::Value(value.into()))
+    }
+
+    /// `column != value`
+    pub fn ne(column: impl Into<Identifier>, value: impl Into<DatabaseValue>) -> Self {
+        Self::leaf(column, Operator::Ne, Operand::Value(value.into()))
+    }
+
+    /// `column > value`
+    pub fn gt(column: impl Into<Identifier>, value: impl Into<DatabaseValue>) -> Self {
+        Self::leaf(column, Operator::Gt, Operand::Value(value.into()))
+    }
+
+    /// `column >= value`
+    pub fn ge(column: impl Into<Identifier>, value: impl Into<DatabaseValue>) -> Self {
+        Self::leaf(column, Operator::Ge, Operand::Value(value.into()))
+    }
+
+    /// `column < value`
+    pub fn lt(column: impl Into<Identifier>, value: impl Into<DatabaseValue>) -> Self {
+        Self::leaf(column, Operator::Lt, Operand::Value(value.into()))
+    }
+
+    /// `column <= value`
+    pub fn le(column: impl Into<Identifier>, value: impl Into<DatabaseValue>) -> Self {
+        Self::leaf(column, Operator::Le, Operand::Value(value.into()))
+    }
+
+    /// `column LIKE pattern`
+    pub fn like(column: impl Into<Identifier>, pattern: &str) -> Self {
+        Self::leaf(column, Operator::Like, Operand::Value(pattern.into()))
+    }
+
+    /// Case-insensitive `column LIKE pattern`
+    ///
+    /// Rendered as `ILIKE` on PostgreSQL and `LOWER(column) LIKE LOWER(?)` elsewhere.
+    pub fn ilike(column: impl Into<Identifier>, pattern: &str) -> Self {
+        Self::leaf(column, Operator::ILike, Operand::Value(pattern.into()))
+    }
+
+    /// `column IN (?, ?, ...)`, one placeholder per value
+    ///
+    /// An empty list renders as a condition that is always false.
+    pub fn in_list<V: Into<DatabaseValue>>(
+        column: impl Into<Identifier>,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        let values = values.into_iter().map(Into::into).collect();
+        Self::leaf(column, Operator::In, Operand::List(values))
+    }
+
+    /// `column NOT IN (?, ?, ...)`, one placeholder per value
+    ///
+    /// An empty list renders as a condition that is always true.
+    pub fn not_in_list<V: Into<DatabaseValue>>(
+        column: impl Into<Identifier>,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        let values = values.into_iter().map(Into::into).collect();
+        Self::leaf(column, Operator::NotIn, Operand::List(values))
+    }
+
+    /// `column BETWEEN low AND high`
+    pub fn between(
+        column: impl Into<Identifier>,
+        low: impl Into<DatabaseValue>,
+        high: impl Into<DatabaseValue>,
+    ) -> Self {
+        Self::leaf(
+            column,
+            Operator::Between,
+            Operand::Range(low.into(), high.into()),
+        )
+    }
+
+    /// `column IN (SELECT ...)`
+    pub fn in_subquery(column: impl Into<Identifier>, subquery: SelectBuilder) -> Self {
+        Self::leaf(column, Operator::In, Operand::Subquery(Box::new(subquery)))
+    }
+
+    /// `column NOT IN (SELECT ...)`
+    pub fn not_in_subquery(column: impl Into<Identifier>, subquery: SelectBuilder) -> Self {
+        Self::leaf(
+            column,
+            Operator::NotIn,
+            Operand::Subquery(Box::new(subquery)),
+        )
+    }
+
+    /// `EXISTS (SELECT ...)`
+    pub fn exists(subquery: SelectBuilder) -> Self {
+        Filter::Exists(Box::new(subquery))
+    }
+
+    /// `NOT EXISTS (SELECT ...)`
+    pub fn not_exists(subquery: SelectBuilder) -> Self {
+        Filter::NotExists(Box::new(subquery))
+    }
+
+    /// Compare two columns, e.g. `orders.shipped_at > orders.created_at`
+    pub fn columns(
+        left: impl Into<Identifier>,
+        comparison: Comparison,
+        right: impl Into<Identifier>,
+    ) -> Self {
+        Self::leaf(left, comparison.into(), Operand::Column(right.into()))
+    }
+
+    /// `column IS NULL`
+    pub fn is_null(column: impl Into<Identifier>) -> Self {
+        Self::leaf(column, Operator::IsNull, Operand::None)
+    }
+
+    /// `column IS NOT NULL`
+    pub fn is_not_null(column: impl Into<Identifier>) -> Self {
+        Self::leaf(column, Operator::IsNotNull, Operand::None)
+    }
+
+    /// `column = TRUE`, using the dialect's boolean literal
+    pub fn is_true(column: impl Into<Identifier>) -> Self {
+        Self::leaf(column, Operator::IsTrue, Operand::None)
+    }
+
+    /// `column = FALSE`, using the dialect's boolean literal
+    pub fn is_false(column: impl Into<Identifier>) -> Self {
+        Self::leaf(column, Operator::IsFalse, Operand::None)
+    }
+
+    /// Group filters with AND
+    pub fn all(filters: impl IntoIterator<Item = Filter>) -> Self {
+        Filter::And(filters.into_iter().collect())
+    }
+
+    /// Group filters with OR
+    pub fn any(filters: impl IntoIterator<Item = Filter>) -> Self {
+        Filter::Or(filters.into_iter().collect())
+    }
+
+    /// Combine with another filter using AND
+    ///
+    /// Extends `self` in place if it is already an AND group.
+    #[must_use]
+    pub fn and(self, other: Filter) -> Self {
+        match self {
+            Filter::And(mut children) => {
+                children.push(other);
+                Filter::And(children)
+            }
+            filter => Filter::And(vec![filter, other]),
+        }
+    }
+
+    /// Combine with another filter using OR
+    ///
+    /// Extends `self` in place if it is already an OR group.
+    #[must_use]
+    pub fn or(self, other: Filter) -> Self {
+        match self {
+            Filter::Or(mut children) => {
+                children.push(other);
+                Filter::Or(children)
+            }
+            filter => Filter::Or(vec![filter, other]),
+        }
+    }
+
+    /// Render the filter, allocating placeholders in order
+    fn render(&self, dialect: Dialect, placeholders: &mut Placeholders) -> String {
+        match self {
+            Filter::Condition(condition) => condition.render(dialect, placeholders),
+            Filter::And(children) => Self::render_group(children, "AND", dialect, placeholders),
+            Filter::Or(children) => Self::render_group(children, "OR", dialect, placeholders),
+            Filter::Not(child) => format!("NOT ({})", child.render(dialect, placeholders)),
+            Filter::Exists(subquery) => {
+                format!("EXISTS ({})", subquery.render(dialect, placeholders))
+            }
+            Filter::NotExists(subquery) => {
+                format!("NOT EXISTS ({})", subquery.render(dialect, placeholders))
+            }
+        }
+    }
+
+    fn render_group(
+        children: &[Filter],
+        logic: &str,
+        dialect: Dialect,
+        placeholders: &mut Placeholders,
+    ) -> String {
+        match children {
+            [] if logic == "AND" => "1 = 1".to_string(),
+            [] => "1 = 0".to_string(),
+            [only] => only.render(dialect, placeholders),
+            _ => {
+                let rendered: Vec<String> = children
+                    .iter()
+                    .map(|child| {
+                        let sql = child.render(dialect, placeholders);
+                        if child.is_compound() {
+                            format!("({})", sql)
+                        } else {
+                            sql
+                        }
+                    })
+                    .collect();
+                rendered.join(&format!(" {} ", logic))
+            }
+        }
+    }
+
+    /// Check whether the filter renders as several conditions joined by AND/OR
+    fn is_compound(&self) -> bool {
+        match self {
+            Filter::And(children) | Filter::Or(children) => match children.as_slice() {
+                [only] => only.is_compound(),
+                children => children.len() > 1,
+            },
+            Filter::Condition(_) | Filter::Not(_) | Filter::Exists(_) | Filter::NotExists(_) => {
+                false
+            }
+        }
+    }
+
+    /// Append the bound parameters in render order
+    fn collect_params(&self, params: &mut Vec<DatabaseValue>) {
+        match self {
+            Filter::Condition(condition) => condition.collect_params(params),
+            Filter::And(children) | Filter::Or(children) => {
+                for child in children {
+                    child.collect_params(params);
+                }
+            }
+            Filter::Not(child) => child.collect_params(params),
+            Filter::Exists(subquery) | Filter::NotExists(subquery) => {
+                params.extend(subquery.params())
+            }
+        }
+    }
+}
+
+impl From<Condition> for Filter {
+    fn from(condition: Condition) -> Self {
+        Filter::Condition(condition)
+    }
+}
+
+impl std::ops::Not for Filter {
+    type Output = Filter;
+
+    fn not(self) -> Filter {
+        Filter::Not(Box::new(self))
+    }
+}
+
+/// Sequential placeholder allocator for one statement
+struct Placeholders {
+    dialect: Dialect,
+    count: usize,
+}
+
+impl Placeholders {
+    fn new(dialect: Dialect) -> Self {
+        Self { dialect, count: 0 }
+    }
+
+    /// Allocate the next placeholder
+    fn next(&mut self) -> String {
+        self.count += 1;
+        self.dialect.placeholder(self.count)
+    }
+}
+
+/// Render a WHERE or HAVING clause (with a leading space), or an empty string
+///
+/// Top-level filters are joined with `logic` ("AND" or "OR").
+fn render_clause(
+    keyword: &str,
+    filters: &[Filter],
+    logic: &str,
+    dialect: Dialect,
+    placeholders: &mut Placeholders,
+) -> String {
+    if filters.is_empty() {
+        return String::new();
+    }
+
+    format!(
+        " {} {}",
+        keyword,
+        Filter::render_group(filters, logic, dialect, placeholders)
+    )
+}
+
+/// Collect the bound parameters of a list of filters in render order
+fn filter_params(filters: &[Filter]) -> Vec<DatabaseValue> {
+    let mut params = Vec::new();
+    for filter in filters {
+        filter.collect_params(&mut params);
+    }
+    params
 }
 
 /// JOIN types
@@ -79,12 +569,107 @@ impl JoinType {
     }
 }
 
+/// JOIN ... ON condition
+///
+/// Converting from a string parses `left <op> right`, where both sides are column
+/// identifiers and `<op>` is a comparison operator; both sides are quoted like
+/// any other identifier. Anything more complex needs [`JoinCondition::raw`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JoinCondition {
+    kind: JoinConditionKind,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum JoinConditionKind {
+    /// `left <op> right`
+    Compare {
+        left: Identifier,
+        operator: &'static str,
+        right: Identifier,
+    },
+    /// A single (boolean) column
+    Column(Identifier),
+    /// Raw SQL emitted verbatim
+    Raw(String),
+}
+
+/// Comparison operators accepted in JOIN conditions, longest first
+const JOIN_OPERATORS: &[&str] = &["<=", ">=", "<>", "!=", "=", "<", ">"];
+
+impl JoinCondition {
+    /// Join on equality of two columns
+    pub fn columns(left: impl Into<Identifier>, right: impl Into<Identifier>) -> Self {
+        Self {
+            kind: JoinConditionKind::Compare {
+                left: left.into(),
+                operator: "=",
+                right: right.into(),
+            },
+        }
+    }
+
+    /// Wrap a raw SQL condition that is emitted verbatim
+    ///
+    /// This bypasses all quoting; never pass untrusted input.
+    pub fn raw(sql: impl Into<String>) -> Self {
+        Self {
+            kind: JoinConditionKind::Raw(sql.into()),
+        }
+    }
+
+    /// Render the condition for a dialect
+    fn render(&self, dialect: Dialect) -> String {
+        match &self.kind {
+            JoinConditionKind::Compare {
+                left,
+                operator,
+                right,
+            } => format!(
+                "{} {} {}",
+                left.render(dialect),
+                operator,
+                right.render(dialect)
+            ),
+            JoinConditionKind::Column(column) => column.render(dialect),
+            JoinConditionKind::Raw(sql) => sql.clone(),
+        }
+    }
+}
+
+impl From<&str> for JoinCondition {
+    fn from(condition: &str) -> Self {
+        let found = condition.find(['=', '<', '>', '!']).and_then(|start| {
+            JOIN_OPERATORS
+                .iter()
+                .find(|op| condition[start..].starts_with(**op))
+                .map(|op| (start, *op))
+        });
+
+        let kind = match found {
+            Some((start, operator)) => JoinConditionKind::Compare {
+                left: Identifier::from(condition[..start].trim()),
+                operator,
+                right: Identifier::from(condition[start + operator.len()..].trim()),
+            },
+            None => JoinConditionKind::Column(Identifier::from(condition.trim())),
+        };
+
+        Self { kind }
+    }
+}
+
+impl From<String> for JoinCondition {
+    fn from(condition: String) -> Self {
+        Self::from(condition.as_str())
+    }
+}
+
 /// JOIN clause
 #[derive(Debug, Clone)]
 pub struct Join {
     join_type: JoinType,
-    table: String,
-    on_condition: String,
+    table: Identifier,
+    on_condition: JoinCondition,
 }
 
 /// ORDER BY direction
@@ -108,17 +693,16 @@ impl OrderDirection {
 /// SELECT query builder
 #[derive(Debug, Clone)]
 pub struct SelectBuilder {
-    table: String,
-    columns: Vec<String>,
+    table: Identifier,
+    columns: Vec<Identifier>,
     joins: Vec<Join>,
-    where_conditions: Vec<Condition>,
+    where_conditions: Vec<Filter>,
     where_logic: String, // "AND" or "OR"
-    order_by: Vec<(String, OrderDirection)>,
+    order_by: Vec<(Identifier, OrderDirection)>,
     limit: Option<usize>,
     offset: Option<usize>,
-    group_by: Vec<String>,
-    #[allow(dead_code)] // Reserved for future HAVING clause support
-    having_conditions: Vec<Condition>,
+    group_by: Vec<Identifier>,
+    having_conditions: Vec<Filter>,
 }
 
 impl SelectBuilder {
@@ -133,10 +717,10 @@ impl SelectBuilder {
     ///     .columns(&["id", "name", "email"])
     ///     .build();
     /// ```
-    pub fn new(table: impl Into<String>) -> Self {
+    pub fn new(table: impl Into<Identifier>) -> Self {
         Self {
             table: table.into(),
-            columns: vec!["*".to_string()],
+            columns: vec![Identifier::wildcard()],
             joins: Vec::new(),
             where_conditions: Vec::new(),
             where_logic: "AND".to_string(),
@@ -151,117 +735,295 @@ impl SelectBuilder {
     /// Select specific columns
     #[must_use]
     pub fn columns(mut self, columns: &[&str]) -> Self {
-        self.columns = columns.iter().map(|s| s.to_string()).collect();
+        self.columns = columns.iter().map(|s| Identifier::from(*s)).collect();
+        self
+    }
+
+    /// Add a column or raw expression to the selection
+    ///
+    /// The first call replaces the default `*`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_database_system::core::query_builder::{Identifier, SelectBuilder};
+    ///
+    /// let query = SelectBuilder::new("orders")
+    ///     .column("user_id")
+    ///     .column(Identifier::raw("SUM(total) AS spent"))
+    ///     .group_by(&["user_id"])
+    ///     .build();
+    /// assert_eq!(
+    ///     query,
+    ///     "SELECT user_id, SUM(total) AS spent FROM orders GROUP BY user_id"
+    /// );
+    /// ```
+    #[must_use]
+    pub fn column(mut self, column: impl Into<Identifier>) -> Self {
+        if self.columns == [Identifier::wildcard()] {
+            self.columns.clear();
+        }
+        self.columns.push(column.into());
         self
     }
 
     /// Select all columns (*)
     #[must_use]
     pub fn all_columns(mut self) -> Self {
-        self.columns = vec!["*".to_string()];
+        self.columns = vec![Identifier::wildcard()];
         self
     }
 
     /// Add a WHERE condition
     #[must_use]
-    pub fn where_eq(mut self, column: &str, value: impl Into<DatabaseValue>) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::Eq,
-            value: Some(value.into()),
-        });
+    pub fn where_eq(
+        mut self,
+        column: impl Into<Identifier>,
+        value: impl Into<DatabaseValue>,
+    ) -> Self {
+        self.where_conditions.push(Filter::eq(column, value));
         self
     }
 
     /// Add a WHERE column != value condition
     #[must_use]
-    pub fn where_ne(mut self, column: &str, value: impl Into<DatabaseValue>) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::Ne,
-            value: Some(value.into()),
-        });
+    pub fn where_ne(
+        mut self,
+        column: impl Into<Identifier>,
+        value: impl Into<DatabaseValue>,
+    ) -> Self {
+        self.where_conditions.push(Filter::ne(column, value));
         self
     }
 
     /// Add a WHERE column > value condition
     #[must_use]
-    pub fn where_gt(mut self, column: &str, value: impl Into<DatabaseValue>) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::Gt,
-            value: Some(value.into()),
-        });
+    pub fn where_gt(
+        mut self,
+        column: impl Into<Identifier>,
+        value: impl Into<DatabaseValue>,
+    ) -> Self {
+        self.where_conditions.push(Filter::gt(column, value));
         self
     }
 
     /// Add a WHERE column >= value condition
     #[must_use]
-    pub fn where_ge(mut self, column: &str, value: impl Into<DatabaseValue>) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::Ge,
-            value: Some(value.into()),
-        });
+    pub fn where_ge(
+        mut self,
+        column: impl Into<Identifier>,
+        value: impl Into<DatabaseValue>,
+    ) -> Self {
+        self.where_conditions.push(Filter::ge(column, value));
         self
     }
 
     /// Add a WHERE column < value condition
     #[must_use]
-    pub fn where_lt(mut self, column: &str, value: impl Into<DatabaseValue>) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::Lt,
-            value: Some(value.into()),
-        });
+    pub fn where_lt(
+        mut self,
+        column: impl Into<Identifier>,
+        value: impl Into<DatabaseValue>,
+    ) -> Self {
+        self.where_conditions.push(Filter::lt(column, value));
         self
     }
 
     /// Add a WHERE column <= value condition
     #[must_use]
-    pub fn where_le(mut self, column: &str, value: impl Into<DatabaseValue>) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::Le,
-            value: Some(value.into()),
-        });
+    pub fn where_le(
+        mut self,
+        column: impl Into<Identifier>,
+        value: impl Into<DatabaseValue>,
+    ) -> Self {
+        self.where_conditions.push(Filter::le(column, value));
         self
     }
 
     /// Add a WHERE column LIKE pattern condition
     #[must_use]
-    pub fn where_like(mut self, column: &str, pattern: &str) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::Like,
-            value: Some(DatabaseValue::String(pattern.to_string())),
-        });
+    pub fn where_like(mut self, column: impl Into<Identifier>, pattern: &str) -> Self {
+        self.where_conditions.push(Filter::like(column, pattern));
+        self
+    }
+
+    /// Add a case-insensitive WHERE column LIKE pattern condition
+    #[must_use]
+    pub fn where_ilike(mut self, column: impl Into<Identifier>, pattern: &str) -> Self {
+        self.where_conditions.push(Filter::ilike(column, pattern));
+        self
+    }
+
+    /// Add a WHERE column IN (...) condition with one placeholder per value
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_database_system::core::dialect::Dialect;
+    /// use rust_database_system::core::query_builder::SelectBuilder;
+    ///
+    /// let query = SelectBuilder::new("users")
+    ///     .where_in("id", [1, 2, 3])
+    ///     .where_between("age", 18, 65);
+    ///
+    /// assert_eq!(
+    ///     query.build_for(Dialect::Postgres),
+    ///     "SELECT * FROM users WHERE id IN ($1, $2, $3) AND age BETWEEN $4 AND $5"
+    /// );
+    /// assert_eq!(query.params().len(), 5);
+    /// ```
+    #[must_use]
+    pub fn where_in<V: Into<DatabaseValue>>(
+        mut self,
+        column: impl Into<Identifier>,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        self.where_conditions.push(Filter::in_list(column, values));
+        self
+    }
+
+    /// Add a WHERE column NOT IN (...) condition with one placeholder per value
+    #[must_use]
+    pub fn where_not_in<V: Into<DatabaseValue>>(
+        mut self,
+        column: impl Into<Identifier>,
+        values: impl IntoIterator<Item = V>,
+    ) -> Self {
+        self.where_conditions
+            .push(Filter::not_in_list(column, values));
+        self
+    }
+
+    /// Add a WHERE column BETWEEN low AND high condition
+    #[must_use]
+    pub fn where_between(
+        mut self,
+        column: impl Into<Identifier>,
+        low: impl Into<DatabaseValue>,
+        high: impl Into<DatabaseValue>,
+    ) -> Self {
+        self.where_conditions
+            .push(Filter::between(column, low, high));
+        self
+    }
+
+    /// Add a WHERE column IN (SELECT ...) condition
+    ///
+    /// The subquery's parameters are merged into this query's parameters.
+    #[must_use]
+    pub fn where_in_subquery(
+        mut self,
+        column: impl Into<Identifier>,
+        subquery: SelectBuilder,
+    ) -> Self {
+        self.where_conditions
+            .push(Filter::in_subquery(column, subquery));
+        self
+    }
+
+    /// Add a WHERE column NOT IN (SELECT ...) condition
+    #[must_use]
+    pub fn where_not_in_subquery(
+        mut self,
+        column: impl Into<Identifier>,
+        subquery: SelectBuilder,
+    ) -> Self {
+        self.where_conditions
+            .push(Filter::not_in_subquery(column, subquery));
+        self
+    }
+
+    /// Add a WHERE EXISTS (SELECT ...) condition
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_database_system::core::dialect::Dialect;
+    /// use rust_database_system::core::query_builder::{Comparison, SelectBuilder};
+    ///
+    /// let orders = SelectBuilder::new("orders")
+    ///     .column("id")
+    ///     .where_column("orders.user_id", Comparison::Eq, "users.id")
+    ///     .where_gt("orders.total", 100);
+    /// let query = SelectBuilder::new("users")
+    ///     .where_eq("active", true)
+    ///     .where_exists(orders);
+    ///
+    /// assert_eq!(
+    ///     query.build_for(Dialect::Postgres),
+    ///     "SELECT * FROM users WHERE active = $1 AND EXISTS \
+    ///      (SELECT id FROM orders WHERE orders.user_id = users.id AND orders.total > $2)"
+    /// );
+    /// assert_eq!(query.params().len(), 2);
+    /// ```
+    #[must_use]
+    pub fn where_exists(mut self, subquery: SelectBuilder) -> Self {
+        self.where_conditions.push(Filter::exists(subquery));
+        self
+    }
+
+    /// Add a WHERE NOT EXISTS (SELECT ...) condition
+    #[must_use]
+    pub fn where_not_exists(mut self, subquery: SelectBuilder) -> Self {
+        self.where_conditions.push(Filter::not_exists(subquery));
+        self
+    }
+
+    /// Add a WHERE condition comparing two columns
+    #[must_use]
+    pub fn where_column(
+        mut self,
+        left: impl Into<Identifier>,
+        comparison: Comparison,
+        right: impl Into<Identifier>,
+    ) -> Self {
+        self.where_conditions
+            .push(Filter::columns(left, comparison, right));
         self
     }
 
     /// Add a WHERE column IS NULL condition
     #[must_use]
-    pub fn where_null(mut self, column: &str) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::IsNull,
-            value: None,
-        });
+    pub fn where_null(mut self, column: impl Into<Identifier>) -> Self {
+        self.where_conditions.push(Filter::is_null(column));
         self
     }
 
     /// Add a WHERE column IS NOT NULL condition
     #[must_use]
-    pub fn where_not_null(mut self, column: &str) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::IsNotNull,
-            value: None,
-        });
+    pub fn where_not_null(mut self, column: impl Into<Identifier>) -> Self {
+        self.where_conditions.push(Filter::is_not_null(column));
+        self
+    }
+
+    /// Add a WHERE column = TRUE condition, using the dialect's boolean literal
+    #[must_use]
+    pub fn where_true(mut self, column: impl Into<Identifier>) -> Self {
+        self.where_conditions.push(Filter::is_true(column));
+        self
+    }
+
+    /// Add a WHERE column = FALSE condition, using the dialect's boolean literal
+    #[must_use]
+    pub fn where_false(mut self, column: impl Into<Identifier>) -> Self {
+        self.where_conditions.push(Filter::is_false(column));
+        self
+    }
+
+    /// Add a condition tree to the WHERE clause
+    ///
+    /// Combined with the other WHERE conditions like any single condition; use
+    /// [`Filter`] groups to mix AND and OR.
+    #[must_use]
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Self {
+        self.where_conditions.push(filter.into());
         self
     }
 
     /// Use OR logic for WHERE conditions instead of AND
+    ///
+    /// This applies to the top-level conditions only; for mixed logic pass a
+    /// [`Filter`] tree to [`filter`](Self::filter) instead.
     #[must_use]
     pub fn or_where(mut self) -> Self {
         self.where_logic = "OR".to_string();
@@ -270,42 +1032,50 @@ impl SelectBuilder {
 
     /// Add an INNER JOIN
     #[must_use]
-    pub fn join(mut self, table: &str, on_condition: &str) -> Self {
+    pub fn join(
+        mut self,
+        table: impl Into<Identifier>,
+        on_condition: impl Into<JoinCondition>,
+    ) -> Self {
         self.joins.push(Join {
             join_type: JoinType::Inner,
-            table: table.to_string(),
-            on_condition: on_condition.to_string(),
+            table: table.into(),
+            on_condition: on_condition.into(),
         });
         self
     }
 
     /// Add a LEFT JOIN
     #[must_use]
-    pub fn left_join(mut self, table: &str, on_condition: &str) -> Self {
+    pub fn left_join(
+        mut self,
+        table: impl Into<Identifier>,
+        on_condition: impl Into<JoinCondition>,
+    ) -> Self {
         self.joins.push(Join {
             join_type: JoinType::Left,
-            table: table.to_string(),
-            on_condition: on_condition.to_string(),
+            table: table.into(),
+            on_condition: on_condition.into(),
         });
         self
     }
 
     /// Add ORDER BY clause
     #[must_use]
-    pub fn order_by(mut self, column: &str, direction: OrderDirection) -> Self {
-        self.order_by.push((column.to_string(), direction));
+    pub fn order_by(mut self, column: impl Into<Identifier>, direction: OrderDirection) -> Self {
+        self.order_by.push((column.into(), direction));
         self
     }
 
     /// Add ORDER BY ASC
     #[must_use]
-    pub fn order_by_asc(self, column: &str) -> Self {
+    pub fn order_by_asc(self, column: impl Into<Identifier>) -> Self {
         self.order_by(column, OrderDirection::Asc)
     }
 
     /// Add ORDER BY DESC
     #[must_use]
-    pub fn order_by_desc(self, column: &str) -> Self {
+    pub fn order_by_desc(self, column: impl Into<Identifier>) -> Self {
         self.order_by(column, OrderDirection::Desc)
     }
 
@@ -326,98 +1096,135 @@ impl SelectBuilder {
     /// Add GROUP BY clause
     #[must_use]
     pub fn group_by(mut self, columns: &[&str]) -> Self {
-        self.group_by = columns.iter().map(|s| s.to_string()).collect();
+        self.group_by = columns.iter().map(|s| Identifier::from(*s)).collect();
+        self
+    }
+
+    /// Add a HAVING condition
+    ///
+    /// Multiple HAVING conditions are joined with AND. Aggregates are referenced
+    /// through [`Identifier::raw`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use rust_database_system::core::query_builder::{Filter, Identifier, SelectBuilder};
+    ///
+    /// let query = SelectBuilder::new("orders")
+    ///     .column("user_id")
+    ///     .where_eq("status", "paid")
+    ///     .group_by(&["user_id"])
+    ///     .having(Filter::gt(Identifier::raw("COUNT(*)"), 5));
+    ///
+    /// assert_eq!(
+    ///     query.build(),
+    ///     "SELECT user_id FROM orders WHERE status = ? GROUP BY user_id HAVING COUNT(*) > ?"
+    /// );
+    /// assert_eq!(query.params().len(), 2);
+    /// ```
+    #[must_use]
+    pub fn having(mut self, filter: impl Into<Filter>) -> Self {
+        self.having_conditions.push(filter.into());
         self
     }
 
-    /// Build the SQL query string
+    /// Build the SQL query string for SQLite
+    ///
+    /// Equivalent to `build_for(Dialect::Sqlite)`.
     pub fn build(&self) -> String {
-        let mut sql = format!("SELECT {} FROM {}", self.columns.join(", "), self.table);
+        self.build_for(Dialect::Sqlite)
+    }
+
+    /// Build the SQL query string for a specific dialect
+    pub fn build_for(&self, dialect: Dialect) -> String {
+        self.render(dialect, &mut Placeholders::new(dialect))
+    }
+
+    /// Render the query, continuing the placeholder numbering of an outer statement
+    fn render(&self, dialect: Dialect, placeholders: &mut Placeholders) -> String {
+        let columns: Vec<String> = self.columns.iter().map(|col| col.render(dialect)).collect();
+        let mut sql = format!(
+            "SELECT {} FROM {}",
+            columns.join(", "),
+            self.table.render(dialect)
+        );
 
         // Add JOINs
         for join in &self.joins {
             sql.push_str(&format!(
                 " {} {} ON {}",
                 join.join_type.as_sql(),
-                join.table,
-                join.on_condition
+                join.table.render(dialect),
+                join.on_condition.render(dialect)
             ));
         }
 
         // Add WHERE conditions
-        if !self.where_conditions.is_empty() {
-            sql.push_str(" WHERE ");
-            let conditions: Vec<String> = self
-                .where_conditions
-                .iter()
-                .enumerate()
-                .map(|(i, cond)| {
-                    let placeholder =
-                        if matches!(cond.operator, Operator::IsNull | Operator::IsNotNull) {
-                            format!("{} {}", cond.column, cond.operator.as_sql())
-                        } else {
-                            format!("{} {} ?", cond.column, cond.operator.as_sql())
-                        };
-
-                    if i == 0 {
-                        placeholder
-                    } else {
-                        format!(" {} {}", self.where_logic, placeholder)
-                    }
-                })
-                .collect();
-            sql.push_str(&conditions.join(""));
-        }
+        sql.push_str(&render_clause(
+            "WHERE",
+            &self.where_conditions,
+            &self.where_logic,
+            dialect,
+            placeholders,
+        ));
 
         // Add GROUP BY
         if !self.group_by.is_empty() {
-            sql.push_str(&format!(" GROUP BY {}", self.group_by.join(", ")));
+            let group_by: Vec<String> = self
+                .group_by
+                .iter()
+                .map(|col| col.render(dialect))
+                .collect();
+            sql.push_str(&format!(" GROUP BY {}", group_by.join(", ")));
         }
 
+        // Add HAVING conditions
+        sql.push_str(&render_clause(
+            "HAVING",
+            &self.having_conditions,
+            "AND",
+            dialect,
+            placeholders,
+        ));
+
         // Add ORDER BY
         if !self.order_by.is_empty() {
             sql.push_str(" ORDER BY ");
             let order_clauses: Vec<String> = self
                 .order_by
                 .iter()
-                .map(|(col, dir)| format!("{} {}", col, dir.as_sql()))
+                .map(|(col, dir)| format!("{} {}", col.render(dialect), dir.as_sql()))
                 .collect();
             sql.push_str(&order_clauses.join(", "));
         }
 
-        // Add LIMIT
-        if let Some(limit) = self.limit {
-            sql.push_str(&format!(" LIMIT {}", limit));
-        }
-
-        // Add OFFSET
-        if let Some(offset) = self.offset {
-            sql.push_str(&format!(" OFFSET {}", offset));
-        }
+        // Add LIMIT/OFFSET
+        sql.push_str(&dialect.limit_offset(self.limit, self.offset));
 
         sql
     }
 
     /// Get the parameter values for parameterized queries
+    ///
+    /// WHERE values come first, followed by HAVING values.
     pub fn params(&self) -> Vec<DatabaseValue> {
-        self.where_conditions
-            .iter()
-            .filter_map(|cond| cond.value.clone())
-            .collect()
+        let mut params = filter_params(&self.where_conditions);
+        params.extend(filter_params(&self.having_conditions));
+        params
     }
 }
 
 /// INSERT query builder
 #[derive(Debug, Clone)]
 pub struct InsertBuilder {
-    table: String,
-    columns: Vec<String>,
+    table: Identifier,
+    columns: Vec<Identifier>,
     values: Vec<DatabaseValue>,
 }
 
 impl InsertBuilder {
     /// Create a new INSERT query builder
-    pub fn new(table: impl Into<String>) -> Self {
+    pub fn new(table: impl Into<Identifier>) -> Self {
         Self {
             table: table.into(),
             columns: Vec::new(),
@@ -427,20 +1234,29 @@ impl InsertBuilder {
 
     /// Add a column-value pair
     #[must_use]
-    pub fn value(mut self, column: &str, value: impl Into<DatabaseValue>) -> Self {
-        self.columns.push(column.to_string());
+    pub fn value(mut self, column: impl Into<Identifier>, value: impl Into<DatabaseValue>) -> Self {
+        self.columns.push(column.into());
         self.values.push(value.into());
         self
     }
 
-    /// Build the SQL query string
+    /// Build the SQL query string for SQLite
+    ///
+    /// Equivalent to `build_for(Dialect::Sqlite)`.
     pub fn build(&self) -> String {
-        let placeholders: Vec<&str> = vec!["?"; self.values.len()];
+        self.build_for(Dialect::Sqlite)
+    }
+
+    /// Build the SQL query string for a specific dialect
+    pub fn build_for(&self, dialect: Dialect) -> String {
+        let mut placeholders = Placeholders::new(dialect);
+        let columns: Vec<String> = self.columns.iter().map(|col| col.render(dialect)).collect();
+        let values: Vec<String> = self.values.iter().map(|_| placeholders.next()).collect();
         format!(
             "INSERT INTO {} ({}) VALUES ({})",
-            self.table,
-            self.columns.join(", "),
-            placeholders.join(", ")
+            self.table.render(dialect),
+            columns.join(", "),
+            values.join(", ")
         )
     }
 
@@ -453,16 +1269,16 @@ impl InsertBuilder {
 /// UPDATE query builder
 #[derive(Debug, Clone)]
 pub struct UpdateBuilder {
-    table: String,
-    set_columns: Vec<String>,
+    table: Identifier,
+    set_columns: Vec<Identifier>,
     set_values: Vec<DatabaseValue>,
-    where_conditions: Vec<Condition>,
+    where_conditions: Vec<Filter>,
     where_logic: String,
 }
 
 impl UpdateBuilder {
     /// Create a new UPDATE query builder
-    pub fn new(table: impl Into<String>) -> Self {
+    pub fn new(table: impl Into<Identifier>) -> Self {
         Self {
             table: table.into(),
             set_columns: Vec::new(),
@@ -474,51 +1290,60 @@ impl UpdateBuilder {
 
     /// Set a column value
     #[must_use]
-    pub fn set(mut self, column: &str, value: impl Into<DatabaseValue>) -> Self {
-        self.set_columns.push(column.to_string());
+    pub fn set(mut self, column: impl Into<Identifier>, value: impl Into<DatabaseValue>) -> Self {
+        self.set_columns.push(column.into());
         self.set_values.push(value.into());
         self
     }
 
     /// Add a WHERE condition
     #[must_use]
-    pub fn where_eq(mut self, column: &str, value: impl Into<DatabaseValue>) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::Eq,
-            value: Some(value.into()),
-        });
+    pub fn where_eq(
+        mut self,
+        column: impl Into<Identifier>,
+        value: impl Into<DatabaseValue>,
+    ) -> Self {
+        self.where_conditions.push(Filter::eq(column, value));
+        self
+    }
+
+    /// Add a condition tree to the WHERE clause
+    #[must_use]
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Self {
+        self.where_conditions.push(filter.into());
         self
     }
 
-    /// Build the SQL query string
+    /// Build the SQL query string for SQLite
+    ///
+    /// Equivalent to `build_for(Dialect::Sqlite)`.
     pub fn build(&self) -> String {
+        self.build_for(Dialect::Sqlite)
+    }
+
+    /// Build the SQL query string for a specific dialect
+    pub fn build_for(&self, dialect: Dialect) -> String {
+        let mut placeholders = Placeholders::new(dialect);
         let set_clauses: Vec<String> = self
             .set_columns
             .iter()
-            .map(|col| format!("{} = ?", col))
+            .map(|col| format!("{} = {}", col.render(dialect), placeholders.next()))
             .collect();
 
-        let mut sql = format!("UPDATE {} SET {}", self.table, set_clauses.join(", "));
+        let mut sql = format!(
+            "UPDATE {} SET {}",
+            self.table.render(dialect),
+            set_clauses.join(", ")
+        );
 
         // Add WHERE conditions
-        if !self.where_conditions.is_empty() {
-            sql.push_str(" WHERE ");
-            let conditions: Vec<String> = self
-                .where_conditions
-                .iter()
-                .enumerate()
-                .map(|(i, cond)| {
-                    let placeholder = format!("{} {} ?", cond.column, cond.operator.as_sql());
-                    if i == 0 {
-                        placeholder
-                    } else {
-                        format!(" {} {}", self.where_logic, placeholder)
-                    }
-                })
-                .collect();
-            sql.push_str(&conditions.join(""));
-        }
+        sql.push_str(&render_clause(
+            "WHERE",
+            &self.where_conditions,
+            &self.where_logic,
+            dialect,
+            &mut placeholders,
+        ));
 
         sql
     }
@@ -526,11 +1351,7 @@ impl UpdateBuilder {
     /// Get the parameter values (SET values followed by WHERE values)
     pub fn params(&self) -> Vec<DatabaseValue> {
         let mut params = self.set_values.clone();
-        params.extend(
-            self.where_conditions
-                .iter()
-                .filter_map(|cond| cond.value.clone()),
-        );
+        params.extend(filter_params(&self.where_conditions));
         params
     }
 }
@@ -538,14 +1359,14 @@ impl UpdateBuilder {
 /// DELETE query builder
 #[derive(Debug, Clone)]
 pub struct DeleteBuilder {
-    table: String,
-    where_conditions: Vec<Condition>,
+    table: Identifier,
+    where_conditions: Vec<Filter>,
     where_logic: String,
 }
 
 impl DeleteBuilder {
     /// Create a new DELETE query builder
-    pub fn new(table: impl Into<String>) -> Self {
+    pub fn new(table: impl Into<Identifier>) -> Self {
         Self {
             table: table.into(),
             where_conditions: Vec::new(),
@@ -555,47 +1376,49 @@ impl DeleteBuilder {
 
     /// Add a WHERE condition
     #[must_use]
-    pub fn where_eq(mut self, column: &str, value: impl Into<DatabaseValue>) -> Self {
-        self.where_conditions.push(Condition {
-            column: column.to_string(),
-            operator: Operator::Eq,
-            value: Some(value.into()),
-        });
+    pub fn where_eq(
+        mut self,
+        column: impl Into<Identifier>,
+        value: impl Into<DatabaseValue>,
+    ) -> Self {
+        self.where_conditions.push(Filter::eq(column, value));
         self
     }
 
-    /// Build the SQL query string
+    /// Add a condition tree to the WHERE clause
+    #[must_use]
+    pub fn filter(mut self, filter: impl Into<Filter>) -> Self {
+        self.where_conditions.push(filter.into());
+        self
+    }
+
+    /// Build the SQL query string for SQLite
+    ///
+    /// Equivalent to `build_for(Dialect::Sqlite)`.
     pub fn build(&self) -> String {
-        let mut sql = format!("DELETE FROM {}", self.table);
+        self.build_for(Dialect::Sqlite)
+    }
+
+    /// Build the SQL query string for a specific dialect
+    pub fn build_for(&self, dialect: Dialect) -> String {
+        let mut sql = format!("DELETE FROM {}", self.table.render(dialect));
 
         // Add WHERE conditions
-        if !self.where_conditions.is_empty() {
-            sql.push_str(" WHERE ");
-            let conditions: Vec<String> = self
-                .where_conditions
-                .iter()
-                .enumerate()
-                .map(|(i, cond)| {
-                    let placeholder = format!("{} {} ?", cond.column, cond.operator.as_sql());
-                    if i == 0 {
-                        placeholder
-                    } else {
-                        format!(" {} {}", self.where_logic, placeholder)
-                    }
-                })
-                .collect();
-            sql.push_str(&conditions.join(""));
-        }
+        let mut placeholders = Placeholders::new(dialect);
+        sql.push_str(&render_clause(
+            "WHERE",
+            &self.where_conditions,
+            &self.where_logic,
+            dialect,
+            &mut placeholders,
+        ));
 
         sql
     }
 
     /// Get the parameter values
     pub fn params(&self) -> Vec<DatabaseValue> {
-        self.where_conditions
-            .iter()
-            .filter_map(|cond| cond.value.clone())
-            .collect()
+        filter_params(&self.where_conditions)
     }
 }
 
@@ -716,4 +1539,363 @@ mod tests {
         assert!(query.contains("LIMIT 100"));
         assert!(query.contains("OFFSET 20"));
     }
+
+    #[test]
+    fn test_postgres_placeholders() {
+        let select = SelectBuilder::new("users")
+            .where_eq("status", "active")
+            .where_gt("age", 18)
+            .where_null("deleted_at");
+        assert_eq!(
+            select.build_for(Dialect::Postgres),
+            "SELECT * FROM users WHERE status = $1 AND age > $2 AND deleted_at IS NULL"
+        );
+
+        let insert = InsertBuilder::new("users")
+            .value("name", "Alice")
+            .value("age", 30);
+        assert_eq!(
+            insert.build_for(Dialect::Postgres),
+            "INSERT INTO users (name, age) VALUES ($1, $2)"
+        );
+
+        // SET placeholders are numbered before WHERE placeholders, matching params()
+        let update = UpdateBuilder::new("users")
+            .set("name", "Bob")
+            .set("age", 31)
+            .where_eq("id", 1);
+        assert_eq!(
+            update.build_for(Dialect::Postgres),
+            "UPDATE users SET name = $1, age = $2 WHERE id = $3"
+        );
+
+        let delete = DeleteBuilder::new("users").where_eq("id", 42);
+        assert_eq!(
+            delete.build_for(Dialect::Postgres),
+            "DELETE FROM users WHERE id = $1"
+        );
+    }
+
+    #[test]
+    fn test_dialect_identifier_quoting() {
+        let builder = SelectBuilder::new("orders")
+            .columns(&["id", "group", "orders.order"])
+            .order_by_asc("order");
+
+        assert_eq!(
+            builder.build_for(Dialect::Sqlite),
+            "SELECT id, \"group\", orders.\"order\" FROM orders ORDER BY \"order\" ASC"
+        );
+        assert_eq!(
+            builder.build_for(Dialect::Mysql),
+            "SELECT id, `group`, orders.`order` FROM orders ORDER BY `order` ASC"
+        );
+    }
+
+    #[test]
+    fn test_dialect_limit_offset() {
+        let builder = SelectBuilder::new("users").offset(20);
+
+        assert_eq!(builder.build(), "SELECT * FROM users LIMIT -1 OFFSET 20");
+        assert_eq!(
+            builder.build_for(Dialect::Postgres),
+            "SELECT * FROM users OFFSET 20"
+        );
+        assert_eq!(
+            builder.build_for(Dialect::Mysql),
+            "SELECT * FROM users LIMIT 18446744073709551615 OFFSET 20"
+        );
+    }
+
+    #[test]
+    fn test_dialect_boolean_literals() {
+        let builder = SelectBuilder::new("users")
+            .where_true("active")
+            .where_false("banned")
+            .where_eq("role", "admin");
+
+        assert_eq!(
+            builder.build(),
+            "SELECT * FROM users WHERE active = 1 AND banned = 0 AND role = ?"
+        );
+        assert_eq!(
+            builder.build_for(Dialect::Postgres),
+            "SELECT * FROM users WHERE active = TRUE AND banned = FALSE AND role = $1"
+        );
+        assert_eq!(builder.params().len(), 1);
+    }
+
+    #[test]
+    fn test_hostile_identifiers_are_quoted() {
+        let builder = SelectBuilder::new("users")
+            .where_eq("name = '' OR 1=1 --", "x")
+            .order_by_desc("id; DROP TABLE users");
+
+        assert_eq!(
+            builder.build(),
+            "SELECT * FROM users WHERE \"name = '' OR 1=1 --\" = ? \
+             ORDER BY \"id; DROP TABLE users\" DESC"
+        );
+
+        let update = UpdateBuilder::new("users").set("a`b", 1).where_eq("id", 1);
+        assert_eq!(
+            update.build_for(Dialect::Mysql),
+            "UPDATE users SET `a``b` = ? WHERE id = ?"
+        );
+    }
+
+    #[test]
+    fn test_raw_columns() {
+        let query = SelectBuilder::new("orders")
+            .column("user_id")
+            .column(Identifier::raw("COUNT(*) AS total"))
+            .group_by(&["user_id"])
+            .build_for(Dialect::Postgres);
+
+        assert_eq!(
+            query,
+            "SELECT user_id, COUNT(*) AS total FROM orders GROUP BY user_id"
+        );
+    }
+
+    #[test]
+    fn test_join_conditions() {
+        let builder = SelectBuilder::new("users")
+            .join("order", "users.id = order.user_id")
+            .left_join(
+                "profiles",
+                JoinCondition::columns("users.id", "profiles.userId"),
+            )
+            .left_join(
+                "bans",
+                JoinCondition::raw("bans.user_id = users.id AND bans.active"),
+            );
+
+        assert_eq!(
+            builder.build_for(Dialect::Postgres),
+            "SELECT * FROM users \
+             INNER JOIN \"order\" ON users.id = \"order\".user_id \
+             LEFT JOIN profiles ON users.id = profiles.\"userId\" \
+             LEFT JOIN bans ON bans.user_id = users.id AND bans.active"
+        );
+        assert_eq!(
+            SelectBuilder::new("a").join("b", "a.x<=b.y").build(),
+            "SELECT * FROM a INNER JOIN b ON a.x <= b.y"
+        );
+    }
+
+    #[test]
+    fn test_nested_filters() {
+        let builder = SelectBuilder::new("users")
+            .where_eq("a", 1)
+            .filter(Filter::any([
+                Filter::eq("b", 2),
+                Filter::all([Filter::eq("c", 3), Filter::is_null("d")]),
+            ]));
+
+        assert_eq!(
+            builder.build_for(Dialect::Postgres),
+            "SELECT * FROM users WHERE a = $1 AND (b = $2 OR (c = $3 AND d IS NULL))"
+        );
+        assert_eq!(
+            builder.params(),
+            vec![
+                DatabaseValue::Int(1),
+                DatabaseValue::Int(2),
+                DatabaseValue::Int(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_not_and_or_where() {
+        let query = SelectBuilder::new("users")
+            .where_eq("role", "admin")
+            .filter(!Filter::eq("a", 1).and(Filter::eq("b", 2)))
+            .or_where()
+            .build();
+
+        assert_eq!(
+            query,
+            "SELECT * FROM users WHERE role = ? OR NOT (a = ? AND b = ?)"
+        );
+    }
+
+    #[test]
+    fn test_degenerate_groups() {
+        let single = SelectBuilder::new("t").filter(Filter::any([Filter::eq("a", 1)]));
+        assert_eq!(single.build(), "SELECT * FROM t WHERE a = ?");
+
+        let empty = SelectBuilder::new("t")
+            .filter(Filter::all([]))
+            .filter(Filter::any([]));
+        assert_eq!(empty.build(), "SELECT * FROM t WHERE 1 = 1 AND 1 = 0");
+    }
+
+    #[test]
+    fn test_having() {
+        let builder = SelectBuilder::new("orders")
+            .column("user_id")
+            .column(Identifier::raw("SUM(total) AS spent"))
+            .where_eq("status", "paid")
+            .group_by(&["user_id"])
+            .having(Filter::gt(Identifier::raw("SUM(total)"), 100))
+            .having(Filter::any([
+                Filter::ge(Identifier::raw("COUNT(*)"), 3),
+                Filter::is_true(Identifier::raw("MAX(vip)")),
+            ]))
+            .order_by_desc("spent");
+
+        assert_eq!(
+            builder.build_for(Dialect::Postgres),
+            "SELECT user_id, SUM(total) AS spent FROM orders WHERE status = $1 \
+             GROUP BY user_id HAVING SUM(total) > $2 AND (COUNT(*) >= $3 OR MAX(vip) = TRUE) \
+             ORDER BY spent DESC"
+        );
+        assert_eq!(
+            builder.params(),
+            vec![
+                DatabaseValue::String("paid".to_string()),
+                DatabaseValue::Int(100),
+                DatabaseValue::Int(3)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_update_delete_filters() {
+        let update = UpdateBuilder::new("users")
+            .set("status", "inactive")
+            .filter(Filter::lt("last_login", 100).or(Filter::is_null("last_login")));
+        assert_eq!(
+            update.build_for(Dialect::Postgres),
+            "UPDATE users SET status = $1 WHERE last_login < $2 OR last_login IS NULL"
+        );
+        assert_eq!(update.params().len(), 2);
+
+        let delete = DeleteBuilder::new("sessions")
+            .where_eq("user_id", 7)
+            .filter(Filter::any([
+                Filter::is_true("expired"),
+                Filter::lt("ttl", 0),
+            ]));
+        assert_eq!(
+            delete.build(),
+            "DELETE FROM sessions WHERE user_id = ? AND (expired = 1 OR ttl < ?)"
+        );
+        assert_eq!(delete.params().len(), 2);
+    }
+
+    #[test]
+    fn test_where_in_expands_placeholders() {
+        let builder = SelectBuilder::new("users")
+            .where_in("id", [1, 2, 3])
+            .where_not_in("status", ["banned", "deleted"])
+            .where_eq("active", true);
+
+        assert_eq!(
+            builder.build(),
+            "SELECT * FROM users WHERE id IN (?, ?, ?) AND status NOT IN (?, ?) AND active = ?"
+        );
+        assert_eq!(
+            builder.build_for(Dialect::Postgres),
+            "SELECT * FROM users WHERE id IN ($1, $2, $3) AND status NOT IN ($4, $5) AND active = $6"
+        );
+        assert_eq!(builder.params().len(), 6);
+        assert_eq!(builder.params()[3], DatabaseValue::from("banned"));
+    }
+
+    #[test]
+    fn test_where_in_empty_list() {
+        let builder = SelectBuilder::new("users")
+            .where_in("id", Vec::<i32>::new())
+            .or_where()
+            .where_not_in("id", Vec::<i32>::new());
+
+        assert_eq!(builder.build(), "SELECT * FROM users WHERE 1 = 0 OR 1 = 1");
+        assert!(builder.params().is_empty());
+    }
+
+    #[test]
+    fn test_between_and_ilike() {
+        let builder = SelectBuilder::new("users")
+            .where_between("age", 18, 65)
+            .where_ilike("name", "al%");
+
+        assert_eq!(
+            builder.build_for(Dialect::Postgres),
+            "SELECT * FROM users WHERE age BETWEEN $1 AND $2 AND name ILIKE $3"
+        );
+        assert_eq!(
+            builder.build_for(Dialect::Mysql),
+            "SELECT * FROM users WHERE age BETWEEN ? AND ? AND LOWER(name) LIKE LOWER(?)"
+        );
+        assert_eq!(
+            builder.params(),
+            vec![
+                DatabaseValue::Int(18),
+                DatabaseValue::Int(65),
+                DatabaseValue::from("al%")
+            ]
+        );
+    }
+
+    #[test]
+    fn test_subqueries_merge_params() {
+        let paid = SelectBuilder::new("orders")
+            .column("user_id")
+            .where_eq("status", "paid")
+            .group_by(&["user_id"])
+            .having(Filter::gt(Identifier::raw("COUNT(*)"), 2));
+        let refunds = SelectBuilder::new("refunds").column("id").where_column(
+            "refunds.user_id",
+            Comparison::Eq,
+            "users.id",
+        );
+
+        let builder = SelectBuilder::new("users")
+            .where_eq("active", true)
+            .where_in_subquery("id", paid)
+            .where_not_exists(refunds)
+            .where_lt("age", 30);
+
+        assert_eq!(
+            builder.build_for(Dialect::Postgres),
+            "SELECT * FROM users WHERE active = $1 \
+             AND id IN (SELECT user_id FROM orders WHERE status = $2 GROUP BY user_id HAVING COUNT(*) > $3) \
+             AND NOT EXISTS (SELECT id FROM refunds WHERE refunds.user_id = users.id) \
+             AND age < $4"
+        );
+        assert_eq!(
+            builder.params(),
+            vec![
+                DatabaseValue::Bool(true),
+                DatabaseValue::from("paid"),
+                DatabaseValue::Int(2),
+                DatabaseValue::Int(30)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_column_comparisons() {
+        let update = UpdateBuilder::new("orders")
+            .set("late", true)
+            .filter(Filter::columns("shipped_at", Comparison::Gt, "due_at"));
+        assert_eq!(
+            update.build_for(Dialect::Mysql),
+            "UPDATE orders SET late = ? WHERE shipped_at > due_at"
+        );
+        assert_eq!(update.params().len(), 1);
+
+        let delete = DeleteBuilder::new("users").filter(Filter::columns(
+            "email",
+            Comparison::ILike,
+            "backup_email",
+        ));
+        assert_eq!(
+            delete.build(),
+            "DELETE FROM users WHERE LOWER(email) LIKE LOWER(backup_email)"
+        );
+    }
 }
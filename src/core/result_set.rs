@@ -0,0 +1,395 @@
+//! Ordered query results with column metadata
+//!
+//! A [`ResultSet`] keeps the columns in the order the database returned them and
+//! stores each row as a plain vector of values sharing one column list. Unlike
+//! [`DatabaseRow`], duplicate column names (e.g. two `id` columns from a join)
+//! stay distinct and can be read by index.
+
+use super::error::{DatabaseError, Result};
+use super::row::FromValue;
+use super::value::{DatabaseResult, DatabaseRow, DatabaseValue};
+use futures::Stream;
+use std::collections::BTreeSet;
+use std::pin::Pin;
+use std::sync::Arc;
+#[cfg(feature = "sqlite")]
+use tokio::sync::mpsc;
+
+/// Number of rows a streaming query buffers ahead of its consumer
+#[cfg(feature = "sqlite")]
+pub(crate) const ROW_STREAM_BUFFER: usize = 256;
+
+/// Stream of rows returned by `query_stream`
+///
+/// Rows are produced as the consumer polls, so memory use stays bounded by the
+/// stream buffer rather than the size of the result. An error ends the stream.
+pub type RowStream = Pin<Box<dyn Stream<Item = Result<Row>> + Send>>;
+
+/// Create a bounded channel whose receiving half is a [`RowStream`]
+///
+/// Producers block (or wait) once [`ROW_STREAM_BUFFER`] rows are queued, and see
+/// a send error once the stream has been dropped.
+#[cfg(feature = "sqlite")]
+pub(crate) fn row_channel() -> (mpsc::Sender<Result<Row>>, RowStream) {
+    let (sender, receiver) = mpsc::channel(ROW_STREAM_BUFFER);
+    let stream = futures::stream::unfold(receiver, |mut receiver| async move {
+        receiver.recv().await.map(|row| (row, receiver))
+    });
+    (sender, Box::pin(stream))
+}
+
+/// Metadata of a result column
+///
+/// Which fields are available depends on what the backend's driver reports.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ColumnInfo {
+    /// Column name (or alias) as returned by the query
+    pub name: String,
+    /// Declared type, e.g. `INTEGER`, `int4` or `VARCHAR`
+    pub declared_type: Option<String>,
+    /// Whether the column accepts NULL, if known
+    pub nullable: Option<bool>,
+    /// Table the column originates from, if known
+    pub table: Option<String>,
+}
+
+impl ColumnInfo {
+    /// Create column metadata with only a name
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            ..Self::default()
+        }
+    }
+
+    /// Set the declared type
+    #[must_use]
+    pub fn with_declared_type(mut self, declared_type: impl Into<String>) -> Self {
+        self.declared_type = Some(declared_type.into());
+        self
+    }
+
+    /// Set the nullability
+    #[must_use]
+    pub fn with_nullable(mut self, nullable: bool) -> Self {
+        self.nullable = Some(nullable);
+        self
+    }
+
+    /// Set the origin table
+    #[must_use]
+    pub fn with_table(mut self, table: impl Into<String>) -> Self {
+        self.table = Some(table.into());
+        self
+    }
+}
+
+/// Column lookup key: a position or a column name
+pub trait ColumnIndex {
+    /// Resolve to a position in `columns`
+    fn index(&self, columns: &[ColumnInfo]) -> Option<usize>;
+
+    /// Describe the key for error messages
+    fn describe(&self) -> String;
+}
+
+impl ColumnIndex for usize {
+    fn index(&self, columns: &[ColumnInfo]) -> Option<usize> {
+        (*self < columns.len()).then_some(*self)
+    }
+
+    fn describe(&self) -> String {
+        format!("#{}", self)
+    }
+}
+
+impl ColumnIndex for &str {
+    /// Names resolve to the first column with that name
+    fn index(&self, columns: &[ColumnInfo]) -> Option<usize> {
+        columns.iter().position(|column| column.name == *self)
+    }
+
+    fn describe(&self) -> String {
+        self.to_string()
+    }
+}
+
+/// One row of a [`ResultSet`]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Row {
+    columns: Arc<[ColumnInfo]>,
+    values: Vec<DatabaseValue>,
+}
+
+impl Row {
+    /// Create a row; `values` must line up with `columns`
+    pub fn new(columns: Arc<[ColumnInfo]>, values: Vec<DatabaseValue>) -> Self {
+        debug_assert_eq!(columns.len(), values.len());
+        Self { columns, values }
+    }
+
+    /// Get a value by position or name
+    pub fn get<I: ColumnIndex>(&self, index: I) -> Option<&DatabaseValue> {
+        index.index(&self.columns).and_then(|i| self.values.get(i))
+    }
+
+    /// Decode a value by position or name
+    ///
+    /// # Errors
+    ///
+    /// Returns `ColumnNotFound` if there is no such column, or `TypeMismatch` naming
+    /// the column if the value cannot be converted
+    pub fn try_get<T: FromValue, I: ColumnIndex>(&self, index: I) -> Result<T> {
+        let i = index
+            .index(&self.columns)
+            .ok_or_else(|| DatabaseError::ColumnNotFound(index.describe()))?;
+        T::from_value(&self.values[i]).map_err(|err| match err {
+            DatabaseError::TypeMismatch { expected, actual } => DatabaseError::TypeMismatch {
+                expected: format!("{} for column '{}'", expected, self.columns[i].name),
+                actual,
+            },
+            other => other,
+        })
+    }
+
+    /// Column metadata shared by all rows of the result
+    pub fn columns(&self) -> &[ColumnInfo] {
+        &self.columns
+    }
+
+    /// Values in column order
+    pub fn values(&self) -> &[DatabaseValue] {
+        &self.values
+    }
+
+    /// Take the values in column order
+    pub fn into_values(self) -> Vec<DatabaseValue> {
+        self.values
+    }
+
+    /// Number of columns
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Check whether the row has no columns
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Convert to a name-keyed [`DatabaseRow`]
+    ///
+    /// When several columns share a name, the last one wins.
+    pub fn to_map(&self) -> DatabaseRow {
+        self.columns
+            .iter()
+            .zip(&self.values)
+            .map(|(column, value)| (column.name.clone(), value.clone()))
+            .collect()
+    }
+}
+
+/// Query result with ordered columns and rows
+///
+/// # Example
+///
+/// ```
+/// use rust_database_system::core::result_set::{ColumnInfo, ResultSet};
+/// use rust_database_system::core::value::DatabaseValue;
+///
+/// let result = ResultSet::new(
+///     vec![ColumnInfo::new("id"), ColumnInfo::new("id")],
+///     vec![vec![DatabaseValue::Long(1), DatabaseValue::Long(7)]],
+/// );
+///
+/// let row = &result.rows()[0];
+/// assert_eq!(row.get(1), Some(&DatabaseValue::Long(7)));
+/// assert_eq!(row.try_get::<i64, _>("id").unwrap(), 1);
+/// ```
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ResultSet {
+    columns: Arc<[ColumnInfo]>,
+    rows: Vec<Row>,
+}
+
+impl ResultSet {
+    /// Create a result set from column metadata and value vectors
+    pub fn new(columns: Vec<ColumnInfo>, rows: Vec<Vec<DatabaseValue>>) -> Self {
+        let columns: Arc<[ColumnInfo]> = columns.into();
+        let rows = rows
+            .into_iter()
+            .map(|values| Row::new(Arc::clone(&columns), values))
+            .collect();
+        Self { columns, rows }
+    }
+
+    /// Build a result set from name-keyed rows
+    ///
+    /// Used by backends without ordered results. The columns are every name
+    /// found in any row, sorted by name, since `DatabaseRow` has no order of its
+    /// own; cells missing from a row are filled with NULL.
+    pub fn from_database_result(result: DatabaseResult) -> Self {
+        let names: BTreeSet<String> = result.iter().flat_map(|row| row.keys().cloned()).collect();
+
+        let rows = result
+            .into_iter()
+            .map(|mut row| {
+                names
+                    .iter()
+                    .map(|name| row.remove(name).unwrap_or(DatabaseValue::Null))
+                    .collect()
+            })
+            .collect();
+        Self::new(names.into_iter().map(ColumnInfo::new).collect(), rows)
+    }
+
+    /// Column metadata
+    pub fn columns(&self) -> &[ColumnInfo] {
+        &self.columns
+    }
+
+    /// Position of the first column with the given name
+    pub fn column_index(&self, name: &str) -> Option<usize> {
+        name.index(&self.columns)
+    }
+
+    /// Rows in result order
+    pub fn rows(&self) -> &[Row] {
+        &self.rows
+    }
+
+    /// Number of rows
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Check whether the result has no rows
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// Iterate over the rows
+    pub fn iter(&self) -> std::slice::Iter<'_, Row> {
+        self.rows.iter()
+    }
+
+    /// Convert to the name-keyed [`DatabaseResult`] representation
+    pub fn into_database_result(self) -> DatabaseResult {
+        self.rows.iter().map(Row::to_map).collect()
+    }
+}
+
+impl From<ResultSet> for DatabaseResult {
+    fn from(result: ResultSet) -> Self {
+        result.into_database_result()
+    }
+}
+
+impl IntoIterator for ResultSet {
+    type Item = Row;
+    type IntoIter = std::vec::IntoIter<Row>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ResultSet {
+    type Item = &'a Row;
+    type IntoIter = std::slice::Iter<'a, Row>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.rows.iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn sample() -> ResultSet {
+        ResultSet::new(
+            vec![
+                ColumnInfo::new("id").with_table("users"),
+                ColumnInfo::new("name").with_declared_type("TEXT"),
+                ColumnInfo::new("id").with_table("orders"),
+            ],
+            vec![
+                vec![1i64.into(), "Alice".into(), 10i64.into()],
+                vec![2i64.into(), DatabaseValue::Null, 20i64.into()],
+            ],
+        )
+    }
+
+    #[test]
+    fn test_index_and_name_access() {
+        let result = sample();
+        assert_eq!(result.len(), 2);
+        assert_eq!(result.column_index("name"), Some(1));
+        assert_eq!(result.columns()[2].table.as_deref(), Some("orders"));
+
+        let row = &result.rows()[0];
+        assert_eq!(row.get("id"), Some(&DatabaseValue::Long(1)));
+        assert_eq!(row.get(2), Some(&DatabaseValue::Long(10)));
+        assert_eq!(row.get(3), None);
+        assert_eq!(row.try_get::<String, _>("name").unwrap(), "Alice");
+
+        let row = &result.rows()[1];
+        assert_eq!(row.try_get::<Option<String>, _>(1).unwrap(), None);
+        let err = row.try_get::<String, _>("name").unwrap_err();
+        assert!(err.to_string().contains("column 'name'"));
+        assert!(matches!(
+            row.try_get::<i64, _>("missing"),
+            Err(DatabaseError::ColumnNotFound(_))
+        ));
+    }
+
+    #[cfg(feature = "sqlite")]
+    #[tokio::test]
+    async fn test_row_channel() {
+        use futures::StreamExt;
+
+        let (sender, stream) = row_channel();
+        let columns: Arc<[ColumnInfo]> = vec![ColumnInfo::new("n")].into();
+        tokio::spawn(async move {
+            for n in 0..(ROW_STREAM_BUFFER as i64 * 2) {
+                let row = Row::new(Arc::clone(&columns), vec![n.into()]);
+                if sender.send(Ok(row)).await.is_err() {
+                    break;
+                }
+            }
+        });
+
+        let rows: Vec<Row> = stream.map(|row| row.unwrap()).collect().await;
+        assert_eq!(rows.len(), ROW_STREAM_BUFFER * 2);
+        assert_eq!(rows[3].try_get::<i64, _>("n").unwrap(), 3);
+    }
+
+    #[test]
+    fn test_database_result_conversion() {
+        let rows: DatabaseResult = sample().into();
+        assert_eq!(rows.len(), 2);
+        // Duplicate names collapse, last column wins
+        assert_eq!(rows[0].get("id"), Some(&DatabaseValue::Long(10)));
+        assert_eq!(rows[0].len(), 2);
+
+        let result = ResultSet::from_database_result(rows);
+        let names: Vec<&str> = result.columns().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "name"]);
+        assert_eq!(result.rows()[1].get("name"), Some(&DatabaseValue::Null));
+
+        // Rows of schemaless backends may carry keys the first row lacks
+        let mut first = DatabaseRow::new();
+        first.insert("id".to_string(), DatabaseValue::Long(1));
+        let mut second = first.clone();
+        second.insert("tags".to_string(), DatabaseValue::String("a".to_string()));
+        let result = ResultSet::from_database_result(vec![first, second]);
+        let names: Vec<&str> = result.columns().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["id", "tags"]);
+        assert_eq!(result.rows()[0].get("tags"), Some(&DatabaseValue::Null));
+        assert_eq!(
+            result.rows()[1].get("tags"),
+            Some(&DatabaseValue::String("a".to_string()))
+        );
+    }
+}
@@ -0,0 +1,402 @@
+//! Retry policies for transactions
+//!
+//! Transactions can fail because of a concurrent transaction rather than a bug:
+//! PostgreSQL serialization failures (`40001`) and deadlocks (`40P01`), MySQL
+//! deadlocks, and SQLite's "database is locked". Such a transaction must be run
+//! again from the start. [`RetryPolicy`] decides which errors are retried and how
+//! long to wait between attempts; [`Database::transaction_with_retry`] and
+//! [`RetryPolicy::run`] apply it.
+//!
+//! [`Database::transaction_with_retry`]: super::database::Database::transaction_with_retry
+
+use super::error::{DatabaseError, Result};
+use std::collections::hash_map::RandomState;
+use std::fmt;
+use std::future::Future;
+use std::hash::{BuildHasher, Hasher};
+use std::sync::Arc;
+use std::time::Duration;
+
+/// Predicate deciding whether an error is retried
+pub type RetryPredicate = Arc<dyn Fn(&DatabaseError) -> bool + Send + Sync>;
+
+/// Value produced by a retried operation, with the number of attempts it took
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Retried<T> {
+    /// Value of the successful attempt
+    pub value: T,
+    /// Number of attempts made, including the successful one
+    pub attempts: u32,
+}
+
+impl<T> Retried<T> {
+    /// Discard the attempt count
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+/// How often and how quickly to retry failed transactions
+///
+/// The delay before retry `n` is `initial_backoff * multiplier^(n - 1)`, capped at
+/// `max_backoff`. With jitter enabled a random delay between half and all of that
+/// is used, so competing transactions do not retry in lockstep.
+///
+/// By default errors are retried when [`DatabaseError::is_retryable`] says so, up
+/// to 5 attempts in total, starting at 10ms and doubling up to 1s.
+///
+/// # Example
+///
+/// ```
+/// use rust_database_system::core::error::DatabaseError;
+/// use rust_database_system::core::retry::RetryPolicy;
+/// use std::time::Duration;
+///
+/// let policy = RetryPolicy::new()
+///     .with_max_attempts(10)
+///     .with_initial_backoff(Duration::from_millis(5))
+///     .with_max_backoff(Duration::from_millis(500))
+///     .with_predicate(|error| {
+///         error.is_retryable() || matches!(error, DatabaseError::QueryTimeout { .. })
+///     });
+/// assert_eq!(policy.max_attempts(), 10);
+/// ```
+#[derive(Clone)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    initial_backoff: Duration,
+    max_backoff: Duration,
+    multiplier: f64,
+    jitter: bool,
+    predicate: RetryPredicate,
+}
+
+impl RetryPolicy {
+    /// Create a policy with the default settings
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the total number of attempts, including the first (at least 1)
+    #[must_use]
+    pub fn with_max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts.max(1);
+        self
+    }
+
+    /// Set the delay before the first retry
+    #[must_use]
+    pub fn with_initial_backoff(mut self, backoff: Duration) -> Self {
+        self.initial_backoff = backoff;
+        self
+    }
+
+    /// Set the upper bound for the delay between attempts
+    #[must_use]
+    pub fn with_max_backoff(mut self, backoff: Duration) -> Self {
+        self.max_backoff = backoff;
+        self
+    }
+
+    /// Set the factor the delay grows by after each retry (at least 1.0)
+    #[must_use]
+    pub fn with_multiplier(mut self, multiplier: f64) -> Self {
+        self.multiplier = multiplier.max(1.0);
+        self
+    }
+
+    /// Enable or disable random jitter of the delay
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Set the predicate deciding which errors are retried
+    #[must_use]
+    pub fn with_predicate<F>(mut self, predicate: F) -> Self
+    where
+        F: Fn(&DatabaseError) -> bool + Send + Sync + 'static,
+    {
+        self.predicate = Arc::new(predicate);
+        self
+    }
+
+    /// Total number of attempts, including the first
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Check whether `error` is retried under this policy
+    pub fn should_retry(&self, error: &DatabaseError) -> bool {
+        (self.predicate)(error)
+    }
+
+    /// Delay before retry number `retry` (1 for the first retry), before jitter
+    pub fn backoff(&self, retry: u32) -> Duration {
+        let exponent = i32::try_from(retry.saturating_sub(1)).unwrap_or(i32::MAX);
+        let seconds = self.initial_backoff.as_secs_f64() * self.multiplier.powi(exponent);
+        Duration::try_from_secs_f64(seconds)
+            .unwrap_or(self.max_backoff)
+            .min(self.max_backoff)
+    }
+
+    /// Decide how to continue after attempt number `attempt` failed with `error`
+    ///
+    /// Returns the delay before the next attempt, or the error to report:
+    /// `error` itself if it is not retried, or `RetriesExhausted` if it is but
+    /// no attempts are left.
+    pub(crate) fn on_failure(
+        &self,
+        attempt: u32,
+        error: DatabaseError,
+    ) -> std::result::Result<Duration, DatabaseError> {
+        if !self.should_retry(&error) {
+            return Err(error);
+        }
+        if attempt >= self.max_attempts {
+            return Err(DatabaseError::RetriesExhausted {
+                attempts: attempt,
+                last_error: Box::new(error),
+            });
+        }
+
+        let delay = self.backoff(attempt);
+        if !self.jitter || delay.is_zero() {
+            return Ok(delay);
+        }
+        // Random point between half and all of the delay
+        let random = RandomState::new().build_hasher().finish();
+        let fraction = 0.5 + (random as f64 / u64::MAX as f64) / 2.0;
+        Ok(delay.mul_f64(fraction))
+    }
+
+    /// Run `operation` until it succeeds, fails with an error that is not
+    /// retried, or runs out of attempts
+    ///
+    /// `operation` receives the attempt number (starting at 1) and must do its
+    /// work from scratch each time, e.g. by opening a new `PooledTransaction`.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error that is not retried, or `RetriesExhausted` wrapping
+    /// the last error once every attempt failed
+    pub async fn run<F, Fut, T>(&self, mut operation: F) -> Result<Retried<T>>
+    where
+        F: FnMut(u32) -> Fut,
+        Fut: Future<Output = Result<T>>,
+    {
+        let mut attempt = 1;
+        loop {
+            match operation(attempt).await {
+                Ok(value) => {
+                    return Ok(Retried {
+                        value,
+                        attempts: attempt,
+                    })
+                }
+                Err(error) => {
+                    let delay = self.on_failure(attempt, error)?;
+                    tokio::time::sleep(delay).await;
+                    attempt += 1;
+                }
+            }
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 5,
+            initial_backoff: Duration::from_millis(10),
+            max_backoff: Duration::from_secs(1),
+            multiplier: 2.0,
+            jitter: true,
+            predicate: Arc::new(DatabaseError::is_retryable),
+        }
+    }
+}
+
+impl fmt::Debug for RetryPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RetryPolicy")
+            .field("max_attempts", &self.max_attempts)
+            .field("initial_backoff", &self.initial_backoff)
+            .field("max_backoff", &self.max_backoff)
+            .field("multiplier", &self.multiplier)
+            .field("jitter", &self.jitter)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backends::SqliteDatabase;
+    use crate::core::database::Database;
+    use crate::core::transaction::{LockingMode, TransactionOptions};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn fast_policy() -> RetryPolicy {
+        RetryPolicy::new()
+            .with_initial_backoff(Duration::from_millis(1))
+            .with_jitter(false)
+    }
+
+    #[test]
+    fn test_backoff() {
+        let policy = RetryPolicy::new()
+            .with_initial_backoff(Duration::from_millis(10))
+            .with_max_backoff(Duration::from_millis(50))
+            .with_multiplier(2.0);
+        assert_eq!(policy.backoff(1), Duration::from_millis(10));
+        assert_eq!(policy.backoff(2), Duration::from_millis(20));
+        assert_eq!(policy.backoff(3), Duration::from_millis(40));
+        assert_eq!(policy.backoff(4), Duration::from_millis(50));
+        assert_eq!(policy.backoff(u32::MAX), Duration::from_millis(50));
+
+        for _ in 0..100 {
+            let delay = policy
+                .on_failure(3, DatabaseError::transaction_conflict("conflict"))
+                .unwrap();
+            assert!(delay >= Duration::from_millis(20) && delay <= Duration::from_millis(40));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_until_success() {
+        let outcome = fast_policy()
+            .run(|attempt| async move {
+                if attempt < 3 {
+                    Err(DatabaseError::transaction_conflict("could not serialize"))
+                } else {
+                    Ok(attempt * 10)
+                }
+            })
+            .await
+            .unwrap();
+        assert_eq!(
+            outcome,
+            Retried {
+                value: 30,
+                attempts: 3
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_run_gives_up() {
+        let calls = AtomicU32::new(0);
+        let result: Result<Retried<()>> = fast_policy()
+            .with_max_attempts(2)
+            .run(|_| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(DatabaseError::transaction_conflict("deadlock"))
+            })
+            .await;
+        match result {
+            Err(DatabaseError::RetriesExhausted {
+                attempts,
+                last_error,
+            }) => {
+                assert_eq!(attempts, 2);
+                assert!(matches!(*last_error, DatabaseError::TransactionConflict(_)));
+            }
+            other => panic!("expected RetriesExhausted, got {:?}", other),
+        }
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+
+        // Errors outside the predicate are returned at once
+        calls.store(0, Ordering::SeqCst);
+        let result: Result<Retried<()>> = fast_policy()
+            .run(|_| async {
+                calls.fetch_add(1, Ordering::SeqCst);
+                Err(DatabaseError::query("syntax error"))
+            })
+            .await;
+        assert!(matches!(result, Err(DatabaseError::QueryError(_))));
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_with_retry() -> Result<()> {
+        let db = SqliteDatabase::new();
+        db.connect(":memory:").await?;
+        db.execute("CREATE TABLE test (name TEXT)").await?;
+
+        let attempts = AtomicU32::new(0);
+        let outcome = db
+            .transaction_with_retry(&fast_policy(), |tx| {
+                let attempt = attempts.fetch_add(1, Ordering::SeqCst) + 1;
+                Box::pin(async move {
+                    tx.execute("INSERT INTO test (name) VALUES ('row')").await?;
+                    if attempt == 1 {
+                        return Err(DatabaseError::from(rusqlite::Error::SqliteFailure(
+                            rusqlite::ffi::Error::new(rusqlite::ffi::SQLITE_BUSY),
+                            Some("database is locked".to_string()),
+                        )));
+                    }
+                    Ok(attempt)
+                })
+            })
+            .await?;
+
+        assert_eq!(outcome.attempts, 2);
+        assert_eq!(outcome.value, 2);
+        assert!(!db.in_transaction());
+        // The first attempt was rolled back
+        let results = db.query("SELECT * FROM test").await?;
+        assert_eq!(results.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_transaction_with_retry_retries_busy_begin() -> Result<()> {
+        let path = std::env::temp_dir().join(format!("retry_busy_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+
+        let writer = SqliteDatabase::new();
+        writer.connect(path.to_str().unwrap()).await?;
+        writer.execute("CREATE TABLE test (name TEXT)").await?;
+        let db = SqliteDatabase::new();
+        db.connect(path.to_str().unwrap()).await?;
+        db.query("PRAGMA busy_timeout = 0").await?;
+
+        let immediate = TransactionOptions::new().with_locking_mode(LockingMode::Immediate);
+        writer.begin_transaction_with_options(immediate).await?;
+        writer
+            .execute("INSERT INTO test (name) VALUES ('writer')")
+            .await?;
+        let release = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            writer.commit().await
+        });
+
+        // BEGIN IMMEDIATE fails with SQLITE_BUSY until the writer commits
+        let outcome = db
+            .transaction_with_retry_options(
+                &fast_policy()
+                    .with_initial_backoff(Duration::from_millis(10))
+                    .with_multiplier(1.0)
+                    .with_max_attempts(50),
+                immediate,
+                |db| {
+                    Box::pin(
+                        async move { db.execute("INSERT INTO test (name) VALUES ('row')").await },
+                    )
+                },
+            )
+            .await?;
+        release.await.unwrap()?;
+
+        assert!(outcome.attempts > 1);
+        assert!(!db.in_transaction());
+        let results = db.query("SELECT * FROM test").await?;
+        assert_eq!(results.len(), 2);
+
+        db.disconnect().await?;
+        let _ = std::fs::remove_file(&path);
+        Ok(())
+    }
+}
@@ -0,0 +1,300 @@
+//! Typed row decoding
+//!
+//! [`FromValue`] converts a single [`DatabaseValue`] into a Rust type and
+//! [`FromRow`] builds a whole struct from a [`DatabaseRow`]. With the `macros`
+//! feature (enabled by default), `#[derive(FromRow)]` generates the latter:
+//!
+//! ```
+//! # #[cfg(feature = "macros")]
+//! # {
+//! use rust_database_system::core::row::FromRow;
+//! use rust_database_system::core::value::{DatabaseRow, DatabaseValue};
+//!
+//! #[derive(FromRow)]
+//! struct Address {
+//!     city: String,
+//! }
+//!
+//! #[derive(FromRow)]
+//! struct User {
+//!     id: i64,
+//!     #[from_row(rename = "user_name")]
+//!     name: String,
+//!     #[from_row(default)]
+//!     score: i32,
+//!     email: Option<String>,
+//!     #[from_row(flatten)]
+//!     address: Address,
+//! }
+//!
+//! let mut row = DatabaseRow::new();
+//! row.insert("id".to_string(), DatabaseValue::Long(1));
+//! row.insert("user_name".to_string(), "Alice".into());
+//! row.insert("email".to_string(), DatabaseValue::Null);
+//! row.insert("city".to_string(), "Seoul".into());
+//!
+//! let user = User::from_row(&row).unwrap();
+//! assert_eq!(user.name, "Alice");
+//! assert_eq!(user.score, 0);
+//! assert_eq!(user.email, None);
+//! assert_eq!(user.address.city, "Seoul");
+//! # }
+//! ```
+//!
+//! Conversions are strict: integers are only narrowed when the value fits and
+//! strings are never parsed, so a schema mismatch surfaces as a `TypeMismatch`
+//! error naming the column instead of a silently wrong value.
+
+use super::error::{DatabaseError, Result};
+use super::value::{DatabaseRow, DatabaseValue};
+
+#[cfg(feature = "macros")]
+pub use rust_database_system_macros::FromRow;
+
+/// Conversion from a single column value
+pub trait FromValue: Sized {
+    /// Convert the value, returning `TypeMismatch` if it has an incompatible type
+    fn from_value(value: &DatabaseValue) -> Result<Self>;
+}
+
+/// Conversion from a whole result row
+pub trait FromRow: Sized {
+    /// Build the value from a row
+    ///
+    /// # Errors
+    ///
+    /// Returns `ColumnNotFound` for missing columns and `TypeMismatch` for columns
+    /// whose value cannot be converted
+    fn from_row(row: &DatabaseRow) -> Result<Self>;
+}
+
+impl FromRow for DatabaseRow {
+    fn from_row(row: &DatabaseRow) -> Result<Self> {
+        Ok(row.clone())
+    }
+}
+
+/// Decode a column of a row
+///
+/// # Errors
+///
+/// Returns `ColumnNotFound` if the row has no such column, or `TypeMismatch`
+/// naming the column if the value cannot be converted
+pub fn get<T: FromValue>(row: &DatabaseRow, column: &str) -> Result<T> {
+    let value = row
+        .get(column)
+        .ok_or_else(|| DatabaseError::ColumnNotFound(column.to_string()))?;
+    T::from_value(value).map_err(|err| with_column(err, column))
+}
+
+/// Decode a column of a row, falling back to `T::default()` when the column is
+/// missing or NULL
+///
+/// # Errors
+///
+/// Returns `TypeMismatch` naming the column if the value cannot be converted
+pub fn get_or_default<T: FromValue + Default>(row: &DatabaseRow, column: &str) -> Result<T> {
+    match row.get(column) {
+        None | Some(DatabaseValue::Null) => Ok(T::default()),
+        Some(value) => T::from_value(value).map_err(|err| with_column(err, column)),
+    }
+}
+
+/// Add the column name to a conversion error
+fn with_column(err: DatabaseError, column: &str) -> DatabaseError {
+    match err {
+        DatabaseError::TypeMismatch { expected, actual } => DatabaseError::TypeMismatch {
+            expected: format!("{} for column '{}'", expected, column),
+            actual,
+        },
+        other => other,
+    }
+}
+
+fn mismatch(expected: &str, value: &DatabaseValue) -> DatabaseError {
+    DatabaseError::type_mismatch(expected, value.type_name())
+}
+
+fn out_of_range(expected: &str, value: &DatabaseValue) -> DatabaseError {
+    DatabaseError::type_mismatch(
+        expected,
+        &format!("{} {} (out of range)", value.type_name(), value.as_string()),
+    )
+}
+
+impl FromValue for DatabaseValue {
+    fn from_value(value: &DatabaseValue) -> Result<Self> {
+        Ok(value.clone())
+    }
+}
+
+impl<T: FromValue> FromValue for Option<T> {
+    fn from_value(value: &DatabaseValue) -> Result<Self> {
+        match value {
+            DatabaseValue::Null => Ok(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+}
+
+impl FromValue for bool {
+    fn from_value(value: &DatabaseValue) -> Result<Self> {
+        match value {
+            DatabaseValue::Bool(v) => Ok(*v),
+            // SQLite and MySQL store booleans as 0/1
+            DatabaseValue::Int(v @ (0 | 1)) => Ok(*v == 1),
+            DatabaseValue::Long(v @ (0 | 1)) => Ok(*v == 1),
+            other => Err(mismatch("bool", other)),
+        }
+    }
+}
+
+impl FromValue for i32 {
+    fn from_value(value: &DatabaseValue) -> Result<Self> {
+        match value {
+            DatabaseValue::Int(v) => Ok(*v),
+            DatabaseValue::Long(v) => i32::try_from(*v).map_err(|_| out_of_range("i32", value)),
+            other => Err(mismatch("i32", other)),
+        }
+    }
+}
+
+impl FromValue for i64 {
+    fn from_value(value: &DatabaseValue) -> Result<Self> {
+        match value {
+            DatabaseValue::Int(v) => Ok(i64::from(*v)),
+            DatabaseValue::Long(v) | DatabaseValue::Timestamp(v) => Ok(*v),
+            other => Err(mismatch("i64", other)),
+        }
+    }
+}
+
+impl FromValue for f32 {
+    fn from_value(value: &DatabaseValue) -> Result<Self> {
+        match value {
+            DatabaseValue::Float(v) => Ok(*v),
+            other => Err(mismatch("f32", other)),
+        }
+    }
+}
+
+impl FromValue for f64 {
+    fn from_value(value: &DatabaseValue) -> Result<Self> {
+        match value {
+            DatabaseValue::Double(v) => Ok(*v),
+            DatabaseValue::Float(v) => Ok(f64::from(*v)),
+            DatabaseValue::Int(v) => Ok(f64::from(*v)),
+            other => Err(mismatch("f64", other)),
+        }
+    }
+}
+
+impl FromValue for String {
+    fn from_value(value: &DatabaseValue) -> Result<Self> {
+        match value {
+            DatabaseValue::String(v) => Ok(v.clone()),
+            other => Err(mismatch("String", other)),
+        }
+    }
+}
+
+impl FromValue for Vec<u8> {
+    fn from_value(value: &DatabaseValue) -> Result<Self> {
+        match value {
+            DatabaseValue::Bytes(v) => Ok(v.clone()),
+            other => Err(mismatch("Vec<u8>", other)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn row(columns: &[(&str, DatabaseValue)]) -> DatabaseRow {
+        columns
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_strict_conversions() {
+        assert_eq!(i32::from_value(&DatabaseValue::Long(7)).unwrap(), 7);
+        assert_eq!(i64::from_value(&DatabaseValue::Int(7)).unwrap(), 7);
+        assert!(bool::from_value(&DatabaseValue::Int(1)).unwrap());
+        assert_eq!(f64::from_value(&DatabaseValue::Float(0.5)).unwrap(), 0.5);
+        assert_eq!(
+            Option::<String>::from_value(&DatabaseValue::Null).unwrap(),
+            None
+        );
+
+        assert!(i32::from_value(&DatabaseValue::String("7".to_string())).is_err());
+        assert!(bool::from_value(&DatabaseValue::Int(2)).is_err());
+        assert!(String::from_value(&DatabaseValue::Null).is_err());
+
+        let err = i32::from_value(&DatabaseValue::Long(i64::MAX)).unwrap_err();
+        assert!(err.to_string().contains("out of range"));
+    }
+
+    #[test]
+    fn test_get_reports_column() {
+        let row = row(&[("age", "old".into())]);
+
+        let err = get::<i32>(&row, "age").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Type mismatch: expected i32 for column 'age', got string"
+        );
+
+        let err = get::<i32>(&row, "missing").unwrap_err();
+        assert!(matches!(err, DatabaseError::ColumnNotFound(ref c) if c == "missing"));
+    }
+
+    #[test]
+    fn test_get_or_default() {
+        let row = row(&[("null", DatabaseValue::Null), ("n", DatabaseValue::Int(3))]);
+
+        assert_eq!(get_or_default::<i32>(&row, "missing").unwrap(), 0);
+        assert_eq!(get_or_default::<String>(&row, "null").unwrap(), "");
+        assert_eq!(get_or_default::<i32>(&row, "n").unwrap(), 3);
+        assert!(get_or_default::<String>(&row, "n").is_err());
+    }
+
+    #[cfg(feature = "macros")]
+    #[test]
+    fn test_derive() {
+        #[derive(Debug, FromRow)]
+        struct Audit {
+            created_by: String,
+        }
+
+        #[derive(Debug, FromRow)]
+        struct Post {
+            id: i64,
+            #[from_row(rename = "post_title")]
+            title: String,
+            #[from_row(default)]
+            views: i64,
+            r#type: Option<String>,
+            #[from_row(flatten)]
+            audit: Audit,
+        }
+
+        let post = Post::from_row(&row(&[
+            ("id", DatabaseValue::Int(1)),
+            ("post_title", "Hello".into()),
+            ("type", DatabaseValue::Null),
+            ("created_by", "alice".into()),
+        ]))
+        .unwrap();
+        assert_eq!(post.id, 1);
+        assert_eq!(post.title, "Hello");
+        assert_eq!(post.views, 0);
+        assert_eq!(post.r#type, None);
+        assert_eq!(post.audit.created_by, "alice");
+
+        let err = Post::from_row(&row(&[("id", DatabaseValue::Int(1))])).unwrap_err();
+        assert!(matches!(err, DatabaseError::ColumnNotFound(ref c) if c == "post_title"));
+    }
+}
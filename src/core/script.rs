@@ -0,0 +1,419 @@
+//! SQL script splitting
+//!
+//! Drivers differ in how they run a script of several statements: SQLite and
+//! PostgreSQL accept it in one call, MySQL needs one statement at a time, and
+//! some statements (PostgreSQL's `CREATE INDEX CONCURRENTLY`) must not share a
+//! call with others. [`split_statements`] cuts a script at the semicolons that
+//! end statements, skipping those inside string literals, quoted identifiers,
+//! comments, dollar-quoted bodies and `BEGIN ... END` blocks of triggers and
+//! routines.
+
+use super::dialect::Dialect;
+
+/// Split a SQL script into its statements
+///
+/// Statements are returned without leading comments, surrounding whitespace and
+/// their terminating semicolon; empty statements and fragments holding only
+/// comments are dropped.
+///
+/// The scanner understands:
+/// - `'string'` literals with `''` escapes, plus backslash escapes in MySQL
+///   strings and PostgreSQL `E'...'` strings
+/// - `"quoted"` identifiers, and `` `backtick` `` identifiers in MySQL
+/// - `-- line` and `/* block */` comments (nested in PostgreSQL), and `# line`
+///   comments in MySQL
+/// - PostgreSQL dollar quoting (`$$ ... $$`, `$tag$ ... $tag$`)
+/// - `BEGIN ... END` bodies of `CREATE TRIGGER`, `CREATE PROCEDURE` and
+///   `CREATE FUNCTION`, including nested `CASE ... END`
+///
+/// # Example
+///
+/// ```
+/// use rust_database_system::core::dialect::Dialect;
+/// use rust_database_system::core::script::split_statements;
+///
+/// let script = "
+///     CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT); -- users
+///     INSERT INTO users (name) VALUES ('a;b');
+///     CREATE TRIGGER users_ai AFTER INSERT ON users BEGIN
+///         UPDATE users SET name = upper(name) WHERE id = NEW.id;
+///     END;
+/// ";
+///
+/// let statements = split_statements(script, Dialect::Sqlite);
+/// assert_eq!(statements.len(), 3);
+/// assert_eq!(statements[1], "INSERT INTO users (name) VALUES ('a;b')");
+/// ```
+pub fn split_statements(script: &str, dialect: Dialect) -> Vec<&str> {
+    Splitter::new(script, dialect).split()
+}
+
+/// Scanner state for [`split_statements`]
+struct Splitter<'a> {
+    script: &'a str,
+    bytes: &'a [u8],
+    dialect: Dialect,
+    pos: usize,
+}
+
+/// Keywords of the current statement that decide how `BEGIN` and `END` nest
+#[derive(Default)]
+struct StatementState {
+    /// Leading keywords seen so far (only the first few are kept)
+    leading: Vec<String>,
+    /// Whether the statement creates a trigger or routine with a block body
+    has_block_body: bool,
+    /// Open `BEGIN` / `CASE` blocks
+    depth: usize,
+    /// Whether the previous word was `END`, which may be followed by `IF`, `CASE`, ...
+    skip_next_word: bool,
+    /// Whether the statement contains anything besides whitespace and comments
+    has_code: bool,
+}
+
+impl StatementState {
+    /// Number of leading keywords needed to recognise a block-bodied statement
+    const LEADING_WORDS: usize = 6;
+
+    fn on_word(&mut self, word: &str) {
+        let word = word.to_ascii_uppercase();
+
+        if self.leading.len() < Self::LEADING_WORDS {
+            self.leading.push(word.clone());
+            if self.leading[0] == "CREATE"
+                && matches!(word.as_str(), "TRIGGER" | "PROCEDURE" | "FUNCTION")
+            {
+                self.has_block_body = true;
+            }
+        }
+        if !self.has_block_body {
+            return;
+        }
+
+        if self.skip_next_word {
+            self.skip_next_word = false;
+            match word.as_str() {
+                // END IF / END LOOP / END WHILE / END REPEAT close MySQL control flow
+                "IF" | "LOOP" | "WHILE" | "REPEAT" => {
+                    self.depth += 1;
+                    return;
+                }
+                // END CASE closes a CASE statement, already counted by END
+                "CASE" => return,
+                _ => {}
+            }
+        }
+
+        match word.as_str() {
+            "BEGIN" | "CASE" => self.depth += 1,
+            "END" => {
+                self.depth = self.depth.saturating_sub(1);
+                self.skip_next_word = true;
+            }
+            _ => {}
+        }
+    }
+
+    /// Any token other than a word or whitespace ends an `END ...` keyword pair
+    fn on_symbol(&mut self) {
+        self.skip_next_word = false;
+    }
+}
+
+impl<'a> Splitter<'a> {
+    fn new(script: &'a str, dialect: Dialect) -> Self {
+        Self {
+            script,
+            bytes: script.as_bytes(),
+            dialect,
+            pos: 0,
+        }
+    }
+
+    fn peek(&self, offset: usize) -> Option<u8> {
+        self.bytes.get(self.pos + offset).copied()
+    }
+
+    fn split(mut self) -> Vec<&'a str> {
+        let mut statements = Vec::new();
+        let mut start = 0;
+        let mut state = StatementState::default();
+
+        while let Some(byte) = self.peek(0) {
+            match byte {
+                b';' if state.depth == 0 => {
+                    if state.has_code {
+                        statements.push(self.script[start..self.pos].trim());
+                    }
+                    self.pos += 1;
+                    start = self.pos;
+                    state = StatementState::default();
+                    continue;
+                }
+                b'-' if self.peek(1) == Some(b'-') => {
+                    self.skip_line_comment();
+                    continue;
+                }
+                b'#' if self.dialect == Dialect::Mysql => {
+                    self.skip_line_comment();
+                    continue;
+                }
+                b'/' if self.peek(1) == Some(b'*') => {
+                    self.skip_block_comment();
+                    continue;
+                }
+                _ => {}
+            }
+
+            if !state.has_code && !byte.is_ascii_whitespace() {
+                // Leading whitespace and comments are not part of the statement
+                state.has_code = true;
+                start = self.pos;
+            }
+            if !byte.is_ascii_whitespace() && !Self::is_word_start(byte) {
+                state.on_symbol();
+            }
+            match byte {
+                b'\'' => {
+                    let backslash_escapes = self.dialect == Dialect::Mysql
+                        || (self.dialect == Dialect::Postgres
+                            && self.pos > 0
+                            && matches!(self.bytes[self.pos - 1], b'E' | b'e')
+                            && !self.is_word_byte_at(self.pos.wrapping_sub(2)));
+                    self.skip_quoted(b'\'', backslash_escapes);
+                }
+                b'"' => self.skip_quoted(b'"', self.dialect == Dialect::Mysql),
+                b'`' if self.dialect == Dialect::Mysql => self.skip_quoted(b'`', false),
+                b'$' if self.dialect == Dialect::Postgres => self.skip_dollar_quoted(),
+                _ if Self::is_word_start(byte) => {
+                    let word_start = self.pos;
+                    while self.peek(0).is_some_and(Self::is_word_byte) {
+                        self.pos += 1;
+                    }
+                    state.on_word(&self.script[word_start..self.pos]);
+                }
+                _ if byte.is_ascii_alphanumeric() || !byte.is_ascii() => {
+                    // Digits and multi-byte characters cannot start a keyword
+                    while self.peek(0).is_some_and(Self::is_word_byte) {
+                        self.pos += 1;
+                    }
+                }
+                _ => self.pos += 1,
+            }
+        }
+
+        if state.has_code {
+            statements.push(self.script[start..].trim());
+        }
+        statements
+    }
+
+    fn is_word_start(byte: u8) -> bool {
+        byte.is_ascii_alphabetic() || byte == b'_'
+    }
+
+    fn is_word_byte(byte: u8) -> bool {
+        byte.is_ascii_alphanumeric() || byte == b'_' || byte == b'$' || !byte.is_ascii()
+    }
+
+    fn is_word_byte_at(&self, index: usize) -> bool {
+        self.bytes
+            .get(index)
+            .copied()
+            .is_some_and(Self::is_word_byte)
+    }
+
+    /// Skip to the end of the line (the newline is left for the caller)
+    fn skip_line_comment(&mut self) {
+        while self.peek(0).is_some_and(|byte| byte != b'\n') {
+            self.pos += 1;
+        }
+    }
+
+    /// Skip a `/* ... */` comment, honouring nesting in PostgreSQL
+    fn skip_block_comment(&mut self) {
+        let nested = self.dialect == Dialect::Postgres;
+        let mut depth = 0;
+        while let Some(byte) = self.peek(0) {
+            if byte == b'/' && self.peek(1) == Some(b'*') && (depth == 0 || nested) {
+                depth += 1;
+                self.pos += 2;
+            } else if byte == b'*' && self.peek(1) == Some(b'/') {
+                depth -= 1;
+                self.pos += 2;
+                if depth == 0 {
+                    return;
+                }
+            } else {
+                self.pos += 1;
+            }
+        }
+    }
+
+    /// Skip a quoted literal or identifier; a doubled quote is an escaped quote
+    fn skip_quoted(&mut self, quote: u8, backslash_escapes: bool) {
+        self.pos += 1;
+        while let Some(byte) = self.peek(0) {
+            self.pos += 1;
+            if backslash_escapes && byte == b'\\' {
+                self.pos += 1;
+            } else if byte == quote {
+                if self.peek(0) == Some(quote) {
+                    self.pos += 1;
+                } else {
+                    return;
+                }
+            }
+        }
+        self.pos = self.pos.min(self.bytes.len());
+    }
+
+    /// Skip a `$tag$ ... $tag$` body, or a lone `$` (e.g. a `$1` placeholder)
+    fn skip_dollar_quoted(&mut self) {
+        let rest = &self.script[self.pos + 1..];
+        let tag_len = rest
+            .bytes()
+            .take_while(|&byte| byte.is_ascii_alphanumeric() || byte == b'_')
+            .count();
+        let tag_is_valid = rest.as_bytes().get(tag_len) == Some(&b'$')
+            && !rest.as_bytes().first().is_some_and(u8::is_ascii_digit);
+        if !tag_is_valid {
+            self.pos += 1;
+            return;
+        }
+
+        let delimiter = &self.script[self.pos..self.pos + tag_len + 2];
+        let body_start = self.pos + delimiter.len();
+        self.pos = match self.script[body_start..].find(delimiter) {
+            Some(end) => body_start + end + delimiter.len(),
+            None => self.bytes.len(),
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_simple() {
+        let statements = split_statements(
+            "CREATE TABLE a (id INTEGER);\n\nINSERT INTO a VALUES (1);;\n  SELECT 1  ",
+            Dialect::Sqlite,
+        );
+        assert_eq!(
+            statements,
+            vec![
+                "CREATE TABLE a (id INTEGER)",
+                "INSERT INTO a VALUES (1)",
+                "SELECT 1"
+            ]
+        );
+        assert!(split_statements("  ;\n -- only a comment\n", Dialect::Sqlite).is_empty());
+    }
+
+    #[test]
+    fn test_split_strings_and_comments() {
+        let script = "INSERT INTO t VALUES ('it''s; fine', \"col;name\"); -- trailing; comment\n\
+                      /* block; comment */ SELECT 2; /* tail */";
+        assert_eq!(
+            split_statements(script, Dialect::Sqlite),
+            vec![
+                "INSERT INTO t VALUES ('it''s; fine', \"col;name\")",
+                "SELECT 2"
+            ]
+        );
+
+        // MySQL: backslash escapes, backticks and # comments
+        let script = "INSERT INTO `a;b` VALUES ('\\'; x'); # note; here\nSELECT 1";
+        assert_eq!(
+            split_statements(script, Dialect::Mysql),
+            vec!["INSERT INTO `a;b` VALUES ('\\'; x')", "SELECT 1"]
+        );
+
+        // PostgreSQL: E'' strings escape with backslashes, nested block comments
+        let script = "SELECT E'\\'; x', 'C:\\'; /* a /* b; */ c; */ SELECT 3";
+        assert_eq!(
+            split_statements(script, Dialect::Postgres),
+            vec!["SELECT E'\\'; x', 'C:\\'", "SELECT 3"]
+        );
+    }
+
+    #[test]
+    fn test_split_dollar_quoting() {
+        let script = "CREATE FUNCTION f() RETURNS trigger AS $body$\n\
+                      BEGIN\n  NEW.x := 1; RETURN NEW;\nEND;\n$body$ LANGUAGE plpgsql;\n\
+                      SELECT $$a;b$$, $1;\n\
+                      DO $$ BEGIN PERFORM 1; END $$";
+        assert_eq!(
+            split_statements(script, Dialect::Postgres),
+            vec![
+                "CREATE FUNCTION f() RETURNS trigger AS $body$\nBEGIN\n  NEW.x := 1; RETURN NEW;\nEND;\n$body$ LANGUAGE plpgsql",
+                "SELECT $$a;b$$, $1",
+                "DO $$ BEGIN PERFORM 1; END $$"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_triggers() {
+        let script = "CREATE TEMP TRIGGER t AFTER INSERT ON a BEGIN\n\
+                        UPDATE a SET v = CASE WHEN NEW.v > 0 THEN 1 ELSE 0 END WHERE id = NEW.id;\n\
+                        INSERT INTO log VALUES (NEW.id);\n\
+                      END;\n\
+                      BEGIN;\n\
+                      INSERT INTO a VALUES (1);\n\
+                      END;";
+        assert_eq!(
+            split_statements(script, Dialect::Sqlite),
+            vec![
+                "CREATE TEMP TRIGGER t AFTER INSERT ON a BEGIN\n\
+                 UPDATE a SET v = CASE WHEN NEW.v > 0 THEN 1 ELSE 0 END WHERE id = NEW.id;\n\
+                 INSERT INTO log VALUES (NEW.id);\n\
+                 END",
+                "BEGIN",
+                "INSERT INTO a VALUES (1)",
+                "END"
+            ]
+        );
+
+        // MySQL routines with control flow
+        let script = "CREATE PROCEDURE p() BEGIN\n\
+                        IF 1 THEN SELECT 1; END IF;\n\
+                        CASE WHEN 1 THEN SELECT 2; END CASE;\n\
+                        WHILE 0 DO SELECT 3; END WHILE;\n\
+                      END;\n\
+                      CALL p()";
+        let statements = split_statements(script, Dialect::Mysql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("END WHILE;\nEND"));
+        assert_eq!(statements[1], "CALL p()");
+
+        // A block closed by a bare END does not pair with the next statement's keyword
+        let script = "CREATE PROCEDURE p() BEGIN BEGIN SELECT 1; END; \
+                      IF 1 THEN SELECT 2; END IF; END; CALL p()";
+        let statements = split_statements(script, Dialect::Mysql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("END IF; END"));
+        assert_eq!(statements[1], "CALL p()");
+
+        let script = "CREATE PROCEDURE p() BEGIN BEGIN SELECT 1; END; \
+                      CASE WHEN 1 THEN SELECT 2; END CASE; END; CALL p()";
+        let statements = split_statements(script, Dialect::Mysql);
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("END CASE; END"));
+        assert_eq!(statements[1], "CALL p()");
+    }
+
+    #[test]
+    fn test_split_unterminated() {
+        assert_eq!(
+            split_statements("SELECT 'abc; SELECT 1", Dialect::Sqlite),
+            vec!["SELECT 'abc; SELECT 1"]
+        );
+        assert_eq!(
+            split_statements("SELECT $x$ never closed;", Dialect::Postgres),
+            vec!["SELECT $x$ never closed;"]
+        );
+    }
+}
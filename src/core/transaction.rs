@@ -1,11 +1,723 @@
 //! Transaction guard for automatic rollback on drop
 //!
 //! This module provides RAII-style transaction management with automatic rollback.
+//!
+//! Transactions nest: SQL backends track how many levels are open, run a real
+//! `BEGIN`/`COMMIT`/`ROLLBACK` for the outermost level and a savepoint for every
+//! level inside it. Library code can therefore open a transaction without knowing
+//! whether its caller already did.
+//!
+//! A guard dropped without commit or rollback cannot run async code, so it asks
+//! the backend to roll its level back before the next operation
+//! ([`Database::defer_rollback`]). The rollback then runs on the caller's runtime,
+//! in order with the statements that follow.
 
 use super::database::Database;
-use super::error::Result;
-use std::sync::atomic::{AtomicBool, Ordering};
-use std::sync::Arc;
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+use super::dialect::Dialect;
+use super::error::{DatabaseError, Result};
+use super::result_set::ResultSet;
+use super::row::FromRow;
+use super::value::{DatabaseResult, DatabaseValue};
+use async_trait::async_trait;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+/// SQL transaction isolation level
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IsolationLevel {
+    /// READ UNCOMMITTED (PostgreSQL runs it as READ COMMITTED)
+    ReadUncommitted,
+    /// READ COMMITTED
+    ReadCommitted,
+    /// REPEATABLE READ
+    RepeatableRead,
+    /// SERIALIZABLE
+    Serializable,
+}
+
+impl IsolationLevel {
+    /// SQL spelling of the level
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            IsolationLevel::ReadUncommitted => "READ UNCOMMITTED",
+            IsolationLevel::ReadCommitted => "READ COMMITTED",
+            IsolationLevel::RepeatableRead => "REPEATABLE READ",
+            IsolationLevel::Serializable => "SERIALIZABLE",
+        }
+    }
+}
+
+/// Whether a transaction may write
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AccessMode {
+    /// READ WRITE
+    ReadWrite,
+    /// READ ONLY: writes fail
+    ReadOnly,
+}
+
+impl AccessMode {
+    /// SQL spelling of the mode
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            AccessMode::ReadWrite => "READ WRITE",
+            AccessMode::ReadOnly => "READ ONLY",
+        }
+    }
+}
+
+/// When a SQLite transaction takes its locks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LockingMode {
+    /// `BEGIN DEFERRED`: locks are taken on first read or write (SQLite's default)
+    Deferred,
+    /// `BEGIN IMMEDIATE`: the write lock is taken at once, so later writes cannot
+    /// fail with SQLITE_BUSY
+    Immediate,
+    /// `BEGIN EXCLUSIVE`: like `Immediate`, and also keeps readers out in
+    /// non-WAL journal modes
+    Exclusive,
+}
+
+impl LockingMode {
+    /// SQL keyword of the mode
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            LockingMode::Deferred => "DEFERRED",
+            LockingMode::Immediate => "IMMEDIATE",
+            LockingMode::Exclusive => "EXCLUSIVE",
+        }
+    }
+}
+
+/// Options for starting a transaction
+///
+/// Unset options keep the backend's defaults. Backends reject options they
+/// cannot honour with `UnsupportedOperation` rather than silently ignoring them:
+///
+/// | Option | SQLite | PostgreSQL | MySQL |
+/// |---|---|---|---|
+/// | isolation level | accepted (always SERIALIZABLE) | yes | yes |
+/// | access mode | READ WRITE only | yes | yes |
+/// | deferrable | no | yes | no |
+/// | locking mode | yes | no | no |
+///
+/// Options only apply to the outermost transaction; a nested transaction
+/// (savepoint) cannot change them.
+///
+/// # Example
+///
+/// ```
+/// use rust_database_system::core::transaction::{AccessMode, IsolationLevel, TransactionOptions};
+///
+/// let options = TransactionOptions::new()
+///     .with_isolation_level(IsolationLevel::Serializable)
+///     .with_access_mode(AccessMode::ReadOnly)
+///     .with_deferrable(true);
+/// assert!(!options.is_default());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct TransactionOptions {
+    /// Isolation level
+    pub isolation_level: Option<IsolationLevel>,
+    /// Read-write or read-only
+    pub access_mode: Option<AccessMode>,
+    /// PostgreSQL DEFERRABLE (only meaningful for SERIALIZABLE READ ONLY)
+    pub deferrable: bool,
+    /// SQLite locking mode
+    pub locking_mode: Option<LockingMode>,
+}
+
+impl TransactionOptions {
+    /// Create options that keep every backend default
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the isolation level
+    #[must_use]
+    pub fn with_isolation_level(mut self, level: IsolationLevel) -> Self {
+        self.isolation_level = Some(level);
+        self
+    }
+
+    /// Set the access mode
+    #[must_use]
+    pub fn with_access_mode(mut self, mode: AccessMode) -> Self {
+        self.access_mode = Some(mode);
+        self
+    }
+
+    /// Set PostgreSQL's DEFERRABLE flag
+    #[must_use]
+    pub fn with_deferrable(mut self, deferrable: bool) -> Self {
+        self.deferrable = deferrable;
+        self
+    }
+
+    /// Set the SQLite locking mode
+    #[must_use]
+    pub fn with_locking_mode(mut self, mode: LockingMode) -> Self {
+        self.locking_mode = Some(mode);
+        self
+    }
+
+    /// Check whether no option is set
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Statements starting an outermost transaction with these options
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedOperation` if the dialect cannot honour an option
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    pub(crate) fn begin_statements(&self, dialect: Dialect) -> Result<Vec<String>> {
+        match dialect {
+            Dialect::Sqlite => {
+                if self.access_mode == Some(AccessMode::ReadOnly) {
+                    return Err(unsupported_option("READ ONLY", "SQLite"));
+                }
+                if self.deferrable {
+                    return Err(unsupported_option("DEFERRABLE", "SQLite"));
+                }
+                // Every SQLite transaction is SERIALIZABLE, which satisfies any
+                // requested isolation level
+                Ok(vec![match self.locking_mode {
+                    Some(mode) => format!("BEGIN {} TRANSACTION", mode.as_sql()),
+                    None => "BEGIN TRANSACTION".to_string(),
+                }])
+            }
+            Dialect::Postgres => {
+                if self.locking_mode.is_some() {
+                    return Err(unsupported_option("locking modes", "PostgreSQL"));
+                }
+                let mut statement = "BEGIN".to_string();
+                if let Some(level) = self.isolation_level {
+                    statement.push_str(" ISOLATION LEVEL ");
+                    statement.push_str(level.as_sql());
+                }
+                if let Some(mode) = self.access_mode {
+                    statement.push(' ');
+                    statement.push_str(mode.as_sql());
+                }
+                if self.deferrable {
+                    statement.push_str(" DEFERRABLE");
+                }
+                Ok(vec![statement])
+            }
+            Dialect::Mysql => {
+                if self.locking_mode.is_some() {
+                    return Err(unsupported_option("locking modes", "MySQL"));
+                }
+                if self.deferrable {
+                    return Err(unsupported_option("DEFERRABLE", "MySQL"));
+                }
+                let mut statements = Vec::with_capacity(2);
+                // Without SESSION or GLOBAL this only applies to the next transaction
+                if let Some(level) = self.isolation_level {
+                    statements.push(format!(
+                        "SET TRANSACTION ISOLATION LEVEL {}",
+                        level.as_sql()
+                    ));
+                }
+                statements.push(match self.access_mode {
+                    Some(mode) => format!("START TRANSACTION {}", mode.as_sql()),
+                    None => "START TRANSACTION".to_string(),
+                });
+                Ok(statements)
+            }
+        }
+    }
+
+    /// Fail unless the options can be used for a nested transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns `UnsupportedOperation` if any option is set
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    pub(crate) fn ensure_nestable(&self) -> Result<()> {
+        if self.is_default() {
+            Ok(())
+        } else {
+            Err(DatabaseError::unsupported(
+                "Transaction options cannot be applied to a nested transaction",
+            ))
+        }
+    }
+}
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+fn unsupported_option(option: &str, backend: &str) -> DatabaseError {
+    DatabaseError::unsupported(format!(
+        "{} transactions are not supported by {}",
+        option, backend
+    ))
+}
+
+/// Name of the savepoint backing nesting level `level` (1 for the first nested level)
+pub(crate) fn savepoint_name(level: usize) -> String {
+    format!("rds_savepoint_{}", level)
+}
+
+/// Statement opening the savepoint for nesting level `level`
+pub(crate) fn savepoint_sql(level: usize) -> String {
+    format!("SAVEPOINT {}", savepoint_name(level))
+}
+
+/// Statement keeping the work of nesting level `level`
+pub(crate) fn release_savepoint_sql(level: usize) -> String {
+    format!("RELEASE SAVEPOINT {}", savepoint_name(level))
+}
+
+/// Statements discarding the work of nesting level `level`
+///
+/// `ROLLBACK TO` keeps the savepoint open, so it is released afterwards.
+pub(crate) fn rollback_to_savepoint_sql(level: usize) -> [String; 2] {
+    [
+        format!("ROLLBACK TO SAVEPOINT {}", savepoint_name(level)),
+        release_savepoint_sql(level),
+    ]
+}
+
+/// Statements discarding nesting level `depth` (1 for the outermost transaction)
+/// together with every level inside it
+pub(crate) fn abandoned_rollback_sql(depth: usize) -> Vec<String> {
+    if depth <= 1 {
+        vec!["ROLLBACK".to_string()]
+    } else {
+        rollback_to_savepoint_sql(depth - 1).into()
+    }
+}
+
+/// Transaction level abandoned by a dropped [`TransactionGuard`]
+///
+/// Backends keep one per connection and roll the level back before their next
+/// operation.
+#[derive(Debug, Default)]
+pub(crate) struct AbandonedLevel(Mutex<Option<usize>>);
+
+impl AbandonedLevel {
+    /// Record that level `depth` and every level inside it must be rolled back
+    pub(crate) fn mark(&self, depth: usize) {
+        let mut level = self.0.lock().unwrap_or_else(|e| e.into_inner());
+        *level = Some(level.map_or(depth, |level| level.min(depth)));
+    }
+
+    /// Outermost abandoned level, if any
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    pub(crate) fn level(&self) -> Option<usize> {
+        *self.0.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Take the outermost abandoned level, leaving none
+    pub(crate) fn take(&self) -> Option<usize> {
+        self.0.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+
+    /// Transaction depth once the abandoned level is rolled back
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    pub(crate) fn effective_depth(&self, depth: usize) -> usize {
+        self.level().map_or(depth, |level| depth.min(level - 1))
+    }
+}
+
+/// Callback run once a transaction's outcome is known
+type Callback = Box<dyn FnOnce() + Send>;
+
+/// `on_commit` / `on_rollback` callbacks registered on a transaction
+///
+/// Callbacks run in registration order once the outcome is known; the callbacks
+/// for the other outcome are discarded. Returned by
+/// [`Database::transaction_callbacks`] for backends that keep them per
+/// transaction level.
+#[derive(Default)]
+pub struct TransactionCallbacks {
+    on_commit: Mutex<Vec<Callback>>,
+    on_rollback: Mutex<Vec<Callback>>,
+}
+
+impl TransactionCallbacks {
+    /// Register a callback for a successful commit
+    pub(crate) fn on_commit(&self, callback: Callback) {
+        lock(&self.on_commit).push(callback);
+    }
+
+    /// Register a callback for a rollback
+    pub(crate) fn on_rollback(&self, callback: Callback) {
+        lock(&self.on_rollback).push(callback);
+    }
+
+    /// Run the commit callbacks and discard the rollback callbacks
+    pub(crate) fn committed(&self) {
+        lock(&self.on_rollback).clear();
+        let callbacks = std::mem::take(&mut *lock(&self.on_commit));
+        callbacks.into_iter().for_each(|callback| callback());
+    }
+
+    /// Run the rollback callbacks and discard the commit callbacks
+    pub(crate) fn rolled_back(&self) {
+        lock(&self.on_commit).clear();
+        let callbacks = std::mem::take(&mut *lock(&self.on_rollback));
+        callbacks.into_iter().for_each(|callback| callback());
+    }
+
+    /// Hand every callback to the enclosing transaction
+    ///
+    /// Used when a savepoint is released: its work, and so its outcome, now
+    /// belongs to the enclosing transaction.
+    pub(crate) fn merge_into(&self, parent: &TransactionCallbacks) {
+        let on_commit = std::mem::take(&mut *lock(&self.on_commit));
+        let on_rollback = std::mem::take(&mut *lock(&self.on_rollback));
+        lock(&parent.on_commit).extend(on_commit);
+        lock(&parent.on_rollback).extend(on_rollback);
+    }
+}
+
+impl std::fmt::Debug for TransactionCallbacks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TransactionCallbacks")
+            .field("on_commit", &lock(&self.on_commit).len())
+            .field("on_rollback", &lock(&self.on_rollback).len())
+            .finish()
+    }
+}
+
+/// Callbacks of every open transaction level of a connection
+///
+/// SQL backends keep one next to their transaction depth and update it under
+/// the same lock. Committing a savepoint hands its callbacks to the enclosing
+/// level, so commit callbacks only run once the outermost COMMIT succeeded, and
+/// they turn into rollbacks if an enclosing level rolls back instead. The
+/// callbacks returned by the methods below should run once the connection is
+/// unlocked.
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+#[derive(Debug, Default)]
+pub(crate) struct CallbackStack(Mutex<Vec<Arc<TransactionCallbacks>>>);
+
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+impl CallbackStack {
+    /// Record that a transaction level was opened
+    pub(crate) fn push(&self) {
+        lock(&self.0).push(Arc::default());
+    }
+
+    /// Callbacks of open level `depth` (1 for the outermost transaction)
+    pub(crate) fn level(&self, depth: usize) -> Option<Arc<TransactionCallbacks>> {
+        depth
+            .checked_sub(1)
+            .and_then(|index| lock(&self.0).get(index).cloned())
+    }
+
+    /// Record that the innermost level committed
+    ///
+    /// Returns the callbacks to run [`committed`](TransactionCallbacks::committed)
+    /// on if it was the outermost transaction.
+    pub(crate) fn commit(&self) -> Option<Arc<TransactionCallbacks>> {
+        let mut levels = lock(&self.0);
+        let finished = levels.pop()?;
+        match levels.last() {
+            Some(parent) => {
+                finished.merge_into(parent);
+                None
+            }
+            None => Some(finished),
+        }
+    }
+
+    /// Record that the innermost level rolled back, returning its callbacks
+    pub(crate) fn rollback(&self) -> Option<Arc<TransactionCallbacks>> {
+        lock(&self.0).pop()
+    }
+
+    /// Record that level `depth` and every level inside it are gone, returning
+    /// their callbacks innermost first
+    pub(crate) fn truncate(&self, depth: usize) -> Vec<Arc<TransactionCallbacks>> {
+        let mut levels = lock(&self.0);
+        let keep = depth.saturating_sub(1).min(levels.len());
+        levels.drain(keep..).rev().collect()
+    }
+}
+
+/// Run the rollback callbacks of levels returned by [`CallbackStack::truncate`]
+#[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+pub(crate) fn run_rolled_back(levels: Vec<Arc<TransactionCallbacks>>) {
+    levels.iter().for_each(|callbacks| callbacks.rolled_back());
+}
+
+/// Lock a callback list, ignoring poisoning from a panicking callback
+fn lock<T>(mutex: &Mutex<T>) -> std::sync::MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// What a [`TransactionGuard`] did about its transaction when dropped without
+/// commit or rollback
+#[derive(Debug)]
+pub enum DropReport {
+    /// The backend rolls the transaction back before its next operation
+    RollbackDeferred {
+        /// Transaction level of the guard (1 for the outermost transaction)
+        depth: usize,
+    },
+    /// The backend cannot defer rollbacks, and a rollback spawned on the
+    /// current runtime succeeded
+    RolledBack {
+        /// Transaction level of the guard (1 for the outermost transaction)
+        depth: usize,
+    },
+    /// The backend cannot defer rollbacks, and a rollback spawned on the
+    /// current runtime failed
+    RollbackFailed {
+        /// Transaction level of the guard (1 for the outermost transaction)
+        depth: usize,
+        /// Error returned by the rollback
+        error: DatabaseError,
+    },
+    /// The backend cannot defer rollbacks and there is no runtime to roll back
+    /// on; the database discards the transaction when the connection closes
+    NoRuntime {
+        /// Transaction level of the guard (1 for the outermost transaction)
+        depth: usize,
+    },
+    /// The transaction held a pooled connection, which was discarded; the
+    /// database rolls the transaction back as the connection closes
+    ConnectionDiscarded {
+        /// Transaction level (always 1)
+        depth: usize,
+    },
+}
+
+/// Callback receiving [`DropReport`]s
+pub type DropHook = Arc<dyn Fn(&DropReport) + Send + Sync>;
+
+/// Transaction bound to one connection, returned by [`Database::begin`]
+///
+/// Every statement runs on the connection the transaction started on, whatever
+/// the backend: single-connection backends use their one connection (opening a
+/// savepoint if a transaction is already active there), pooled backends hold a
+/// pool connection until commit or rollback. Dropping the transaction without
+/// either rolls it back.
+///
+/// # Example
+///
+/// ```ignore
+/// async fn record_order(db: &impl Database) -> Result<()> {
+///     let tx = db.begin().await?;
+///     tx.execute("INSERT INTO orders (id) VALUES (1)").await?;
+///     tx.execute("INSERT INTO audit (order_id) VALUES (1)").await?;
+///     tx.commit().await
+/// }
+/// ```
+#[async_trait]
+pub trait Transaction: Send + Sync {
+    /// Execute a query that doesn't return results
+    async fn execute(&self, query: &str) -> Result<u64>;
+
+    /// Execute a parameterized query that doesn't return results
+    async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64>;
+
+    /// Execute a script of several statements separated by semicolons
+    async fn execute_batch(&self, script: &str) -> Result<()>;
+
+    /// Execute a SELECT query and return ordered rows with column metadata
+    async fn query_rows(&self, query: &str) -> Result<ResultSet>;
+
+    /// Execute a parameterized SELECT query and return ordered rows with column metadata
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet>;
+
+    /// Execute a SELECT query and return results
+    async fn query(&self, query: &str) -> Result<DatabaseResult> {
+        self.query_rows(query)
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    /// Execute a parameterized SELECT query
+    async fn query_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<DatabaseResult> {
+        self.query_rows_with_params(query, params)
+            .await
+            .map(ResultSet::into_database_result)
+    }
+
+    /// Commit the transaction
+    ///
+    /// If COMMIT fails, the transaction is rolled back.
+    async fn commit(self: Box<Self>) -> Result<()>;
+
+    /// Roll the transaction back
+    async fn rollback(self: Box<Self>) -> Result<()>;
+}
+
+impl dyn Transaction + '_ {
+    /// Execute a SELECT query and decode every row into `T`
+    pub async fn query_as<T: FromRow>(&self, query: &str) -> Result<Vec<T>> {
+        let rows = self.query(query).await?;
+        rows.iter().map(T::from_row).collect()
+    }
+
+    /// Execute a parameterized SELECT query and decode every row into `T`
+    pub async fn query_as_with_params<T: FromRow>(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<Vec<T>> {
+        let rows = self.query_with_params(query, params).await?;
+        rows.iter().map(T::from_row).collect()
+    }
+}
+
+/// [`Transaction`] on the single connection of a SQL backend
+///
+/// Opens one transaction level on the database; dropping it unfinished hands
+/// that level to [`Database::defer_rollback`].
+pub(crate) struct SharedTransaction<'a, D: Database> {
+    db: &'a D,
+    /// Transaction level of this transaction (1 for the outermost transaction)
+    depth: usize,
+    finished: bool,
+}
+
+impl<'a, D: Database> SharedTransaction<'a, D> {
+    /// Begin a transaction level on `db`
+    pub(crate) async fn begin(db: &'a D, options: TransactionOptions) -> Result<Self> {
+        let depth = db.begin_transaction_level(options).await?;
+
+        Ok(Self {
+            db,
+            depth,
+            finished: false,
+        })
+    }
+}
+
+#[async_trait]
+impl<D: Database> Transaction for SharedTransaction<'_, D> {
+    async fn execute(&self, query: &str) -> Result<u64> {
+        self.db.execute(query).await
+    }
+
+    async fn execute_with_params(&self, query: &str, params: &[DatabaseValue]) -> Result<u64> {
+        self.db.execute_with_params(query, params).await
+    }
+
+    async fn execute_batch(&self, script: &str) -> Result<()> {
+        self.db.execute_batch(script).await
+    }
+
+    async fn query_rows(&self, query: &str) -> Result<ResultSet> {
+        self.db.query_rows(query).await
+    }
+
+    async fn query_rows_with_params(
+        &self,
+        query: &str,
+        params: &[DatabaseValue],
+    ) -> Result<ResultSet> {
+        self.db.query_rows_with_params(query, params).await
+    }
+
+    async fn commit(mut self: Box<Self>) -> Result<()> {
+        // A failed COMMIT leaves the level open; dropping the transaction then
+        // rolls it back
+        self.db.commit().await?;
+        self.finished = true;
+        Ok(())
+    }
+
+    async fn rollback(mut self: Box<Self>) -> Result<()> {
+        // Likewise, a failed ROLLBACK is retried when the transaction drops
+        self.db.rollback().await?;
+        self.finished = true;
+        Ok(())
+    }
+}
+
+impl<D: Database> Drop for SharedTransaction<'_, D> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.db.defer_rollback(self.depth);
+        }
+    }
+}
+
+/// Connection-bound transaction shared by a [`TransactionGuard`] and its
+/// savepoints, for backends that keep no transaction state of their own
+struct BoundTransaction {
+    state: tokio::sync::Mutex<BoundState>,
+    /// Level dropped without commit or rollback while the state was in use
+    abandoned: AbandonedLevel,
+}
+
+struct BoundState {
+    /// `None` once the outermost guard has finished
+    transaction: Option<Box<dyn Transaction>>,
+    /// Number of open transaction levels
+    depth: usize,
+}
+
+impl BoundState {
+    fn transaction(&self) -> Result<&dyn Transaction> {
+        self.transaction.as_deref().ok_or_else(|| {
+            DatabaseError::transaction("The transaction is already finished".to_string())
+        })
+    }
+}
+
+impl BoundTransaction {
+    fn new(transaction: Box<dyn Transaction>) -> Self {
+        Self {
+            state: tokio::sync::Mutex::new(BoundState {
+                transaction: Some(transaction),
+                depth: 1,
+            }),
+            abandoned: AbandonedLevel::default(),
+        }
+    }
+
+    /// Lock the transaction, first rolling back an abandoned level
+    async fn lock(&self) -> Result<tokio::sync::MutexGuard<'_, BoundState>> {
+        let mut state = self.state.lock().await;
+        if let Some(level) = self.abandoned.take() {
+            if level == 1 {
+                if let Some(transaction) = state.transaction.take() {
+                    transaction.rollback().await?;
+                }
+            } else if state.depth >= level {
+                let rollback = abandoned_rollback_sql(level).join("; ");
+                if let Err(e) = state.transaction()?.execute_batch(&rollback).await {
+                    self.abandoned.mark(level);
+                    return Err(e);
+                }
+            }
+            state.depth = state.depth.min(level - 1);
+        }
+        Ok(state)
+    }
+
+    /// Roll level `depth` back for a guard dropped without commit or rollback
+    fn abandon(&self, depth: usize) -> DropReport {
+        if depth == 1 {
+            if let Ok(mut state) = self.state.try_lock() {
+                // Dropping the transaction discards its connection
+                state.transaction = None;
+                state.depth = 0;
+                return DropReport::ConnectionDiscarded { depth };
+            }
+        }
+        self.abandoned.mark(depth);
+        DropReport::RollbackDeferred { depth }
+    }
+}
 
 /// Transaction guard that automatically rolls back on drop if not committed
 ///
@@ -30,10 +742,59 @@ use std::sync::Arc;
 ///
 /// // If commit() is not called (e.g., due to error), automatic rollback occurs
 /// ```
+///
+/// # Dropping without commit or rollback
+///
+/// SQLite, PostgreSQL and MySQL roll a dropped guard's transaction back before
+/// their next operation, and `transaction_depth()` drops at once, so the rollback
+/// has always happened by the time the database is used again. On the pooled
+/// backends the guard holds a connection of its own and discards it. Other
+/// backends spawn the rollback on the current runtime. Call
+/// [`close()`](Self::close) to roll back explicitly and see its error, and use
+/// [`with_drop_hook()`](Self::with_drop_hook) to observe what a drop did:
+///
+/// ```ignore
+/// let tx = TransactionGuard::begin(Arc::clone(&db))
+///     .await?
+///     .with_drop_hook(|report| tracing::warn!(?report, "transaction dropped"));
+/// ```
+///
+/// # Nesting
+///
+/// `begin()` on a database that is already in a transaction opens a savepoint,
+/// and [`savepoint()`](Self::savepoint) opens one explicitly as a child guard.
+/// Committing a child releases its savepoint into the parent; rolling it back
+/// discards only the child's work:
+///
+/// ```ignore
+/// let tx = TransactionGuard::begin(Arc::clone(&db)).await?;
+/// tx.execute("INSERT INTO orders (id) VALUES (1)").await?;
+///
+/// let child = tx.savepoint().await?;
+/// child.execute("INSERT INTO audit (order_id) VALUES (1)").await?;
+/// child.rollback().await?; // the order is kept, the audit row is not
+///
+/// tx.commit().await?;
+/// ```
 pub struct TransactionGuard<D: Database + 'static> {
     db: Arc<D>,
     committed: AtomicBool,
     rolled_back: AtomicBool,
+    /// Child guards that have not finished yet
+    open_children: Arc<AtomicUsize>,
+    /// The parent's `open_children`, for child guards
+    parent: Option<Arc<AtomicUsize>>,
+    /// Transaction level of this guard (1 for the outermost transaction)
+    depth: usize,
+    drop_hook: Option<DropHook>,
+    callbacks: Arc<TransactionCallbacks>,
+    /// Whether the backend runs `callbacks` when the level's outcome is known
+    backend_callbacks: bool,
+    /// The parent's callbacks, which inherit this guard's callbacks on commit
+    parent_callbacks: Option<Arc<TransactionCallbacks>>,
+    /// Transaction the statements run on, for backends that return one from
+    /// [`Database::begin_owned`]
+    bound: Option<Arc<BoundTransaction>>,
 }
 
 impl<D: Database + 'static> TransactionGuard<D> {
@@ -43,36 +804,206 @@ impl<D: Database + 'static> TransactionGuard<D> {
     ///
     /// Returns an error if:
     /// - Database is not connected
-    /// - A transaction is already active
+    /// - A transaction is already active and the backend cannot nest transactions
     /// - Database operation fails
     pub async fn begin(db: Arc<D>) -> Result<Self> {
-        db.begin_transaction().await?;
+        Self::begin_with_options(db, TransactionOptions::default()).await
+    }
 
-        Ok(Self {
+    /// Begin a new transaction with isolation level, access mode or locking options
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if [`begin()`](Self::begin) would, or `UnsupportedOperation`
+    /// if the backend cannot honour the options
+    pub async fn begin_with_options(db: Arc<D>, options: TransactionOptions) -> Result<Self> {
+        // Pooled backends keep no transaction state, so the guard holds the
+        // transaction and its connection itself
+        if let Some(transaction) = db.begin_owned(options).await? {
+            let bound = Arc::new(BoundTransaction::new(transaction));
+            return Ok(Self::new(db, 1, None, Some(bound)));
+        }
+
+        let depth = db.begin_transaction_level(options).await?;
+        // A guard opened inside a transaction only owns a savepoint, so its
+        // callbacks belong to the level and wait for the enclosing transaction
+        let level_callbacks = db.transaction_callbacks(depth);
+
+        Ok(Self::new(db, depth, level_callbacks, None))
+    }
+
+    fn new(
+        db: Arc<D>,
+        depth: usize,
+        level_callbacks: Option<Arc<TransactionCallbacks>>,
+        bound: Option<Arc<BoundTransaction>>,
+    ) -> Self {
+        Self {
             db,
             committed: AtomicBool::new(false),
             rolled_back: AtomicBool::new(false),
-        })
+            open_children: Arc::new(AtomicUsize::new(0)),
+            parent: None,
+            depth,
+            drop_hook: None,
+            backend_callbacks: level_callbacks.is_some(),
+            callbacks: level_callbacks.unwrap_or_default(),
+            parent_callbacks: None,
+            bound,
+        }
     }
 
-    /// Execute a query within the transaction
+    /// Call `hook` with a [`DropReport`] if the guard is dropped without commit
+    /// or rollback
+    ///
+    /// Savepoints opened with [`savepoint()`](Self::savepoint) inherit the hook.
+    #[must_use]
+    pub fn with_drop_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(&DropReport) + Send + Sync + 'static,
+    {
+        self.drop_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Begin a nested transaction backed by a savepoint
+    ///
+    /// The child must be committed or rolled back before this guard is.
     ///
     /// # Errors
     ///
-    /// Returns an error if the query fails
-    pub async fn execute(&self, query: &str) -> Result<u64> {
+    /// Returns an error if this transaction is finished, the backend cannot nest
+    /// transactions, or the SAVEPOINT statement fails
+    pub async fn savepoint(&self) -> Result<TransactionGuard<D>> {
+        self.ensure_active("begin a savepoint in")?;
+
+        let mut child = match &self.bound {
+            Some(bound) => {
+                let mut state = bound.lock().await?;
+                let depth = state.depth + 1;
+                state
+                    .transaction()?
+                    .execute_batch(&savepoint_sql(depth - 1))
+                    .await?;
+                state.depth = depth;
+                Self::new(Arc::clone(&self.db), depth, None, Some(Arc::clone(bound)))
+            }
+            None => Self::begin(Arc::clone(&self.db)).await?,
+        };
+        self.open_children.fetch_add(1, Ordering::AcqRel);
+        child.parent = Some(Arc::clone(&self.open_children));
+        child.drop_hook = self.drop_hook.clone();
+        if !child.backend_callbacks {
+            child.parent_callbacks = Some(Arc::clone(&self.callbacks));
+        }
+        Ok(child)
+    }
+
+    /// Run `callback` once the transaction has committed
+    ///
+    /// Callbacks run in registration order after COMMIT succeeds, and are
+    /// discarded if the transaction rolls back. On a savepoint, whether opened
+    /// with [`savepoint()`](Self::savepoint) or by beginning a guard while the
+    /// database is already in a transaction, committing hands the callbacks to
+    /// the enclosing transaction, so they run only when the outermost
+    /// transaction commits.
+    pub fn on_commit<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.callbacks.on_commit(Box::new(callback));
+    }
+
+    /// Run `callback` once the transaction has rolled back
+    ///
+    /// Callbacks run in registration order after an explicit rollback, or when
+    /// the guard is dropped without commit or rollback. On a savepoint they also
+    /// run if the enclosing transaction rolls back after the savepoint committed.
+    pub fn on_rollback<F>(&self, callback: F)
+    where
+        F: FnOnce() + Send + 'static,
+    {
+        self.callbacks.on_rollback(Box::new(callback));
+    }
+
+    /// Fail if the transaction was committed or rolled back
+    fn ensure_active(&self, action: &str) -> Result<()> {
         if self.committed.load(Ordering::Acquire) {
-            return Err(crate::core::DatabaseError::transaction(
-                "Cannot execute on committed transaction".to_string(),
-            ));
+            return Err(crate::core::DatabaseError::transaction(format!(
+                "Cannot {} committed transaction",
+                action
+            )));
         }
         if self.rolled_back.load(Ordering::Acquire) {
+            return Err(crate::core::DatabaseError::transaction(format!(
+                "Cannot {} rolled back transaction",
+                action
+            )));
+        }
+        Ok(())
+    }
+
+    /// Fail if a child guard is still open
+    fn ensure_no_open_children(&self) -> Result<()> {
+        if self.open_children.load(Ordering::Acquire) > 0 {
             return Err(crate::core::DatabaseError::transaction(
-                "Cannot execute on rolled back transaction".to_string(),
+                "A nested transaction is still open".to_string(),
             ));
         }
+        Ok(())
+    }
 
-        self.db.execute(query).await
+    /// Tell the parent guard that this child has finished
+    fn detach_from_parent(&mut self) {
+        if let Some(parent) = self.parent.take() {
+            parent.fetch_sub(1, Ordering::AcqRel);
+        }
+    }
+
+    /// Commit or roll back this guard's transaction level
+    async fn finish(&self, commit: bool) -> Result<()> {
+        let bound = match &self.bound {
+            Some(bound) => bound,
+            None if commit => return self.db.commit().await,
+            None => return self.db.rollback().await,
+        };
+
+        let mut state = bound.lock().await?;
+        if self.depth == 1 {
+            let transaction = state.transaction.take().ok_or_else(|| {
+                DatabaseError::transaction("The transaction is already finished".to_string())
+            })?;
+            state.depth = 0;
+            if commit {
+                transaction.commit().await
+            } else {
+                transaction.rollback().await
+            }
+        } else {
+            let level = self.depth - 1;
+            let sql = if commit {
+                release_savepoint_sql(level)
+            } else {
+                rollback_to_savepoint_sql(level).join("; ")
+            };
+            state.transaction()?.execute_batch(&sql).await?;
+            state.depth = level;
+            Ok(())
+        }
+    }
+
+    /// Execute a query within the transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the query fails
+    pub async fn execute(&self, query: &str) -> Result<u64> {
+        self.ensure_active("execute on")?;
+
+        match &self.bound {
+            Some(bound) => bound.lock().await?.transaction()?.execute(query).await,
+            None => self.db.execute(query).await,
+        }
     }
 
     /// Execute a parameterized query within the transaction
@@ -85,18 +1016,40 @@ impl<D: Database + 'static> TransactionGuard<D> {
         query: &str,
         params: &[crate::core::DatabaseValue],
     ) -> Result<u64> {
-        if self.committed.load(Ordering::Acquire) {
-            return Err(crate::core::DatabaseError::transaction(
-                "Cannot execute on committed transaction".to_string(),
-            ));
-        }
-        if self.rolled_back.load(Ordering::Acquire) {
-            return Err(crate::core::DatabaseError::transaction(
-                "Cannot execute on rolled back transaction".to_string(),
-            ));
+        self.ensure_active("execute on")?;
+
+        match &self.bound {
+            Some(bound) => {
+                bound
+                    .lock()
+                    .await?
+                    .transaction()?
+                    .execute_with_params(query, params)
+                    .await
+            }
+            None => self.db.execute_with_params(query, params).await,
         }
+    }
 
-        self.db.execute_with_params(query, params).await
+    /// Execute a script of several statements within the transaction
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if any statement fails
+    pub async fn execute_batch(&self, script: &str) -> Result<()> {
+        self.ensure_active("execute on")?;
+
+        match &self.bound {
+            Some(bound) => {
+                bound
+                    .lock()
+                    .await?
+                    .transaction()?
+                    .execute_batch(script)
+                    .await
+            }
+            None => self.db.execute_batch(script).await,
+        }
     }
 
     /// Query within the transaction
@@ -105,18 +1058,12 @@ impl<D: Database + 'static> TransactionGuard<D> {
     ///
     /// Returns an error if the query fails
     pub async fn query(&self, query: &str) -> Result<crate::core::DatabaseResult> {
-        if self.committed.load(Ordering::Acquire) {
-            return Err(crate::core::DatabaseError::transaction(
-                "Cannot query on committed transaction".to_string(),
-            ));
-        }
-        if self.rolled_back.load(Ordering::Acquire) {
-            return Err(crate::core::DatabaseError::transaction(
-                "Cannot query on rolled back transaction".to_string(),
-            ));
-        }
+        self.ensure_active("query on")?;
 
-        self.db.query(query).await
+        match &self.bound {
+            Some(bound) => bound.lock().await?.transaction()?.query(query).await,
+            None => self.db.query(query).await,
+        }
     }
 
     /// Query with parameters within the transaction
@@ -129,18 +1076,19 @@ impl<D: Database + 'static> TransactionGuard<D> {
         query: &str,
         params: &[crate::core::DatabaseValue],
     ) -> Result<crate::core::DatabaseResult> {
-        if self.committed.load(Ordering::Acquire) {
-            return Err(crate::core::DatabaseError::transaction(
-                "Cannot query on committed transaction".to_string(),
-            ));
-        }
-        if self.rolled_back.load(Ordering::Acquire) {
-            return Err(crate::core::DatabaseError::transaction(
-                "Cannot query on rolled back transaction".to_string(),
-            ));
-        }
+        self.ensure_active("query on")?;
 
-        self.db.query_with_params(query, params).await
+        match &self.bound {
+            Some(bound) => {
+                bound
+                    .lock()
+                    .await?
+                    .transaction()?
+                    .query_with_params(query, params)
+                    .await
+            }
+            None => self.db.query_with_params(query, params).await,
+        }
     }
 
     /// Commit the transaction
@@ -151,15 +1099,23 @@ impl<D: Database + 'static> TransactionGuard<D> {
     /// # Errors
     ///
     /// Returns an error if the commit fails
-    pub async fn commit(self) -> Result<()> {
+    pub async fn commit(mut self) -> Result<()> {
         if self.rolled_back.load(Ordering::Acquire) {
             return Err(crate::core::DatabaseError::transaction(
                 "Cannot commit a rolled back transaction".to_string(),
             ));
         }
+        self.ensure_no_open_children()?;
 
-        self.db.commit().await?;
+        self.finish(true).await?;
         self.committed.store(true, Ordering::Release);
+        self.detach_from_parent();
+        if !self.backend_callbacks {
+            match self.parent_callbacks.take() {
+                Some(parent) => self.callbacks.merge_into(&parent),
+                None => self.callbacks.committed(),
+            }
+        }
 
         // FIXED: Removed mem::forget to prevent resource leak
         // Drop will see committed=true and skip rollback
@@ -176,19 +1132,37 @@ impl<D: Database + 'static> TransactionGuard<D> {
     /// # Errors
     ///
     /// Returns an error if the rollback fails
-    pub async fn rollback(self) -> Result<()> {
+    pub async fn rollback(mut self) -> Result<()> {
         if self.committed.load(Ordering::Acquire) {
             return Err(crate::core::DatabaseError::transaction(
                 "Cannot rollback a committed transaction".to_string(),
             ));
         }
+        self.ensure_no_open_children()?;
 
-        self.db.rollback().await?;
+        self.finish(false).await?;
         self.rolled_back.store(true, Ordering::Release);
+        self.detach_from_parent();
+        self.callbacks.rolled_back();
 
         Ok(())
     }
 
+    /// Roll the transaction back now if it is still open
+    ///
+    /// The async counterpart of dropping the guard: the rollback runs before this
+    /// returns and its error is returned instead of going to the drop hook.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a child guard is still open or the rollback fails
+    pub async fn close(self) -> Result<()> {
+        if self.committed.load(Ordering::Acquire) || self.rolled_back.load(Ordering::Acquire) {
+            return Ok(());
+        }
+        self.rollback().await
+    }
+
     /// Check if the transaction has been committed
     pub fn is_committed(&self) -> bool {
         self.committed.load(Ordering::Acquire)
@@ -202,51 +1176,44 @@ impl<D: Database + 'static> TransactionGuard<D> {
 
 impl<D: Database + 'static> Drop for TransactionGuard<D> {
     fn drop(&mut self) {
+        self.detach_from_parent();
+
         // Auto-rollback if neither committed nor rolled back
-        if !self.committed.load(Ordering::Acquire) && !self.rolled_back.load(Ordering::Acquire) {
-            // SECURITY FIX: Use spawn_blocking to ensure rollback is properly scheduled
-            // This is safer than fire-and-forget spawn, as it properly queues the blocking work
+        if self.committed.load(Ordering::Acquire) || self.rolled_back.load(Ordering::Acquire) {
+            return;
+        }
+        self.rolled_back.store(true, Ordering::Release);
 
+        let depth = self.depth;
+        let report = if let Some(bound) = &self.bound {
+            bound.abandon(depth)
+        } else if self.db.defer_rollback(depth) {
+            DropReport::RollbackDeferred { depth }
+        } else if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            // The backend cannot defer the rollback; run it on the owning runtime
+            // and report once it has finished
             let db = Arc::clone(&self.db);
-            self.rolled_back.store(true, Ordering::Release);
-
-            // Try to perform rollback if in tokio runtime
-            if let Ok(handle) = tokio::runtime::Handle::try_current() {
-                // Use spawn_blocking to schedule rollback on the blocking thread pool
-                // This works with both single-threaded and multi-threaded runtimes
-                handle.spawn_blocking(move || {
-                    // Create a new runtime for the blocking operation
-                    // This is necessary because we can't use async in spawn_blocking directly
-                    // SAFETY FIX: Handle runtime creation failure gracefully instead of panicking
-                    match tokio::runtime::Runtime::new() {
-                        Ok(rt) => {
-                            if let Err(e) = rt.block_on(db.rollback()) {
-                                eprintln!(
-                                    "[ERROR] TransactionGuard auto-rollback failed: {}. \
-                                     Data may be in inconsistent state.", e
-                                );
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!(
-                                "[CRITICAL] TransactionGuard cannot create runtime for auto-rollback: {}. \
-                                 Transaction may leak. This typically indicates system resource exhaustion.", e
-                            );
-                        }
-                    }
-                });
+            let hook = self.drop_hook.clone();
+            let callbacks = Arc::clone(&self.callbacks);
+            handle.spawn(async move {
+                let report = match db.rollback().await {
+                    Ok(()) => DropReport::RolledBack { depth },
+                    Err(error) => DropReport::RollbackFailed { depth, error },
+                };
+                callbacks.rolled_back();
+                if let Some(hook) = hook {
+                    hook(&report);
+                }
+            });
+            return;
+        } else {
+            DropReport::NoRuntime { depth }
+        };
 
-                eprintln!(
-                    "[WARNING] TransactionGuard dropped without explicit commit/rollback. \
-                     Auto-rollback queued. Best practice: call commit() or rollback() explicitly."
-                );
-            } else {
-                // Not in a tokio runtime - transaction will be rolled back on connection close
-                eprintln!(
-                    "[WARNING] TransactionGuard dropped outside tokio runtime. \
-                     Transaction will be implicitly rolled back by database on connection close."
-                );
-            }
+        // The work can no longer commit, so the rollback callbacks run now
+        self.callbacks.rolled_back();
+        if let Some(hook) = &self.drop_hook {
+            hook(&report);
         }
     }
 }
@@ -297,14 +1264,392 @@ mod tests {
             // Drop without commit - should rollback
         }
 
-        // Give the async rollback task time to complete
-        tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+        // The rollback is visible at once and runs before the next statement
+        assert!(!db.in_transaction());
 
         // Verify data was not committed
         let results = db.query("SELECT * FROM test").await.unwrap();
         assert_eq!(results.len(), 0);
     }
 
+    #[tokio::test]
+    async fn test_transaction_guard_drop_report() {
+        let db = Arc::new(SqliteDatabase::new());
+        db.connect(":memory:").await.unwrap();
+
+        db.execute("CREATE TABLE test (id INTEGER PRIMARY KEY, value TEXT)")
+            .await
+            .unwrap();
+
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&reports);
+        let tx = TransactionGuard::begin(Arc::clone(&db))
+            .await
+            .unwrap()
+            .with_drop_hook(move |report| sink.lock().unwrap().push(format!("{:?}", report)));
+        tx.execute("INSERT INTO test (value) VALUES ('kept')")
+            .await
+            .unwrap();
+
+        // A dropped child only discards its own level
+        {
+            let child = tx.savepoint().await.unwrap();
+            child
+                .execute("INSERT INTO test (value) VALUES ('discarded')")
+                .await
+                .unwrap();
+        }
+        assert_eq!(db.transaction_depth(), 1);
+        assert_eq!(*reports.lock().unwrap(), ["RollbackDeferred { depth: 2 }"]);
+
+        tx.commit().await.unwrap();
+        let results = db.query("SELECT value FROM test").await.unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].get("value"),
+            Some(&crate::core::DatabaseValue::String("kept".to_string()))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_transaction_guard_close() {
+        let db = Arc::new(SqliteDatabase::new());
+        db.connect(":memory:").await.unwrap();
+
+        db.execute("CREATE TABLE test (id INTEGER PRIMARY KEY, value TEXT)")
+            .await
+            .unwrap();
+
+        let tx = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+        tx.execute("INSERT INTO test (value) VALUES ('closed')")
+            .await
+            .unwrap();
+        tx.close().await.unwrap();
+
+        assert!(!db.in_transaction());
+        let results = db.query("SELECT * FROM test").await.unwrap();
+        assert_eq!(results.len(), 0);
+    }
+
+    /// Backend-agnostic code: commit one transaction and drop another
+    async fn write_through_begin<D: Database>(db: &D) -> Result<()> {
+        db.execute("CREATE TABLE test (id INTEGER PRIMARY KEY, value TEXT)")
+            .await?;
+
+        let tx = db.begin().await?;
+        tx.execute("INSERT INTO test (value) VALUES ('committed')")
+            .await?;
+        assert_eq!(tx.query("SELECT * FROM test").await?.len(), 1);
+        tx.commit().await?;
+
+        {
+            let tx = db.begin().await?;
+            tx.execute("INSERT INTO test (value) VALUES ('dropped')")
+                .await?;
+        }
+
+        let tx = db.begin().await?;
+        tx.execute("INSERT INTO test (value) VALUES ('rolled back')")
+            .await?;
+        tx.rollback().await?;
+
+        assert_eq!(db.query("SELECT * FROM test").await?.len(), 1);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_begin_binds_connection() {
+        let db = SqliteDatabase::new();
+        db.connect(":memory:").await.unwrap();
+        write_through_begin(&db).await.unwrap();
+        assert!(!db.in_transaction());
+
+        let path = std::env::temp_dir().join(format!("rds_begin_test_{}.db", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let pooled = crate::backends::PooledSqliteDatabase::new(path.to_str().unwrap())
+            .await
+            .unwrap();
+        write_through_begin(&pooled).await.unwrap();
+        assert!(matches!(
+            pooled.begin_transaction().await,
+            Err(DatabaseError::UnsupportedOperation(_))
+        ));
+        drop(pooled);
+        let _ = std::fs::remove_file(&path);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_guard_savepoint() {
+        let db = Arc::new(SqliteDatabase::new());
+        db.connect(":memory:").await.unwrap();
+
+        db.execute("CREATE TABLE test (id INTEGER PRIMARY KEY, value TEXT)")
+            .await
+            .unwrap();
+
+        let tx = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+        tx.execute("INSERT INTO test (value) VALUES ('outer')")
+            .await
+            .unwrap();
+
+        let child = tx.savepoint().await.unwrap();
+        child
+            .execute("INSERT INTO test (value) VALUES ('inner')")
+            .await
+            .unwrap();
+        assert_eq!(db.transaction_depth(), 2);
+
+        let grandchild = child.savepoint().await.unwrap();
+        assert_eq!(db.transaction_depth(), 3);
+        grandchild.commit().await.unwrap();
+
+        child.rollback().await.unwrap();
+        assert_eq!(db.transaction_depth(), 1);
+
+        // A guard begun inside a transaction nests instead of failing
+        let nested = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+        nested
+            .execute("INSERT INTO test (value) VALUES ('nested')")
+            .await
+            .unwrap();
+        nested.commit().await.unwrap();
+
+        tx.commit().await.unwrap();
+
+        let results = db
+            .query("SELECT value FROM test ORDER BY id")
+            .await
+            .unwrap();
+        let values: Vec<String> = results
+            .iter()
+            .map(|row| row.get("value").unwrap().as_string())
+            .collect();
+        assert_eq!(values, vec!["outer", "nested"]);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_guard_callbacks() {
+        let db = Arc::new(SqliteDatabase::new());
+        db.connect(":memory:").await.unwrap();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let record = |name: &'static str| {
+            let events = Arc::clone(&events);
+            move || events.lock().unwrap().push(name)
+        };
+
+        // Commit callbacks run in order; a committed savepoint hands its
+        // callbacks to the parent and a rolled back one runs its own
+        let tx = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+        tx.on_commit(record("commit 1"));
+        tx.on_rollback(record("never"));
+        let child = tx.savepoint().await.unwrap();
+        child.on_commit(record("child commit"));
+        child.commit().await.unwrap();
+        let discarded = tx.savepoint().await.unwrap();
+        discarded.on_commit(record("never"));
+        discarded.on_rollback(record("child rollback"));
+        discarded.rollback().await.unwrap();
+        tx.on_commit(record("commit 2"));
+        assert_eq!(*events.lock().unwrap(), vec!["child rollback"]);
+        tx.commit().await.unwrap();
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec!["child rollback", "commit 1", "child commit", "commit 2"]
+        );
+
+        // Explicit rollback
+        events.lock().unwrap().clear();
+        let tx = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+        tx.on_commit(record("never"));
+        tx.on_rollback(record("rollback 1"));
+        tx.on_rollback(record("rollback 2"));
+        tx.rollback().await.unwrap();
+        assert_eq!(*events.lock().unwrap(), vec!["rollback 1", "rollback 2"]);
+
+        // Dropping the guard counts as a rollback
+        events.lock().unwrap().clear();
+        {
+            let tx = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+            tx.on_commit(record("never"));
+            tx.on_rollback(record("dropped"));
+        }
+        assert_eq!(*events.lock().unwrap(), vec!["dropped"]);
+    }
+
+    #[tokio::test]
+    async fn test_implicitly_nested_guard_callbacks() {
+        let db = Arc::new(SqliteDatabase::new());
+        db.connect(":memory:").await.unwrap();
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let record = |name: &'static str| {
+            let events = Arc::clone(&events);
+            move || events.lock().unwrap().push(name)
+        };
+
+        // A guard begun inside a transaction only releases a savepoint on commit,
+        // so its commit callbacks wait for the enclosing transaction
+        let outer = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+        let inner = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+        inner.on_commit(record("never"));
+        inner.on_rollback(record("inner rollback"));
+        inner.commit().await.unwrap();
+        assert!(events.lock().unwrap().is_empty());
+        outer.rollback().await.unwrap();
+        assert_eq!(*events.lock().unwrap(), vec!["inner rollback"]);
+
+        events.lock().unwrap().clear();
+        let outer = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+        let inner = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+        inner.on_commit(record("inner commit"));
+        inner.commit().await.unwrap();
+        assert!(events.lock().unwrap().is_empty());
+        outer.commit().await.unwrap();
+        assert_eq!(*events.lock().unwrap(), vec!["inner commit"]);
+
+        // The enclosing transaction need not be a guard, and dropping it counts
+        // as a rollback
+        events.lock().unwrap().clear();
+        {
+            let tx = db.begin().await.unwrap();
+            let inner = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+            inner.on_commit(record("never"));
+            inner.on_rollback(record("outer dropped"));
+            inner.commit().await.unwrap();
+            assert!(events.lock().unwrap().is_empty());
+            drop(tx);
+        }
+        assert_eq!(*events.lock().unwrap(), vec!["outer dropped"]);
+        assert_eq!(db.transaction_depth(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_transaction_guard_open_child_blocks_commit() {
+        let db = Arc::new(SqliteDatabase::new());
+        db.connect(":memory:").await.unwrap();
+
+        let tx = TransactionGuard::begin(Arc::clone(&db)).await.unwrap();
+        let child = tx.savepoint().await.unwrap();
+
+        // The parent cannot finish while the child is open
+        assert!(tx.ensure_no_open_children().is_err());
+        child.commit().await.unwrap();
+        tx.commit().await.unwrap();
+        assert!(!db.in_transaction());
+    }
+
+    #[test]
+    fn test_savepoint_sql() {
+        assert_eq!(savepoint_sql(1), "SAVEPOINT rds_savepoint_1");
+        assert_eq!(
+            release_savepoint_sql(2),
+            "RELEASE SAVEPOINT rds_savepoint_2"
+        );
+        assert_eq!(
+            rollback_to_savepoint_sql(1),
+            [
+                "ROLLBACK TO SAVEPOINT rds_savepoint_1".to_string(),
+                "RELEASE SAVEPOINT rds_savepoint_1".to_string(),
+            ]
+        );
+    }
+
+    #[cfg(any(feature = "sqlite", feature = "postgres", feature = "mysql"))]
+    #[test]
+    fn test_transaction_options_sql() {
+        let defaults = TransactionOptions::new();
+        assert!(defaults.is_default());
+        assert_eq!(
+            defaults.begin_statements(Dialect::Sqlite).unwrap(),
+            ["BEGIN TRANSACTION"]
+        );
+        assert_eq!(
+            defaults.begin_statements(Dialect::Postgres).unwrap(),
+            ["BEGIN"]
+        );
+        assert_eq!(
+            defaults.begin_statements(Dialect::Mysql).unwrap(),
+            ["START TRANSACTION"]
+        );
+
+        let options = TransactionOptions::new()
+            .with_isolation_level(IsolationLevel::Serializable)
+            .with_access_mode(AccessMode::ReadOnly);
+        assert_eq!(
+            options
+                .with_deferrable(true)
+                .begin_statements(Dialect::Postgres)
+                .unwrap(),
+            ["BEGIN ISOLATION LEVEL SERIALIZABLE READ ONLY DEFERRABLE"]
+        );
+        assert_eq!(
+            options.begin_statements(Dialect::Mysql).unwrap(),
+            [
+                "SET TRANSACTION ISOLATION LEVEL SERIALIZABLE",
+                "START TRANSACTION READ ONLY"
+            ]
+        );
+
+        let immediate = TransactionOptions::new().with_locking_mode(LockingMode::Immediate);
+        assert_eq!(
+            immediate.begin_statements(Dialect::Sqlite).unwrap(),
+            ["BEGIN IMMEDIATE TRANSACTION"]
+        );
+
+        for (options, dialect) in [
+            (options, Dialect::Sqlite),
+            (immediate, Dialect::Postgres),
+            (immediate, Dialect::Mysql),
+            (
+                TransactionOptions::new().with_deferrable(true),
+                Dialect::Mysql,
+            ),
+        ] {
+            assert!(matches!(
+                options.begin_statements(dialect),
+                Err(DatabaseError::UnsupportedOperation(_))
+            ));
+        }
+        assert!(immediate.ensure_nestable().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_transaction_guard_with_options() {
+        let db = Arc::new(SqliteDatabase::new());
+        db.connect(":memory:").await.unwrap();
+
+        db.execute("CREATE TABLE test (id INTEGER PRIMARY KEY, value TEXT)")
+            .await
+            .unwrap();
+
+        let options = TransactionOptions::new()
+            .with_isolation_level(IsolationLevel::Serializable)
+            .with_locking_mode(LockingMode::Immediate);
+        let tx = TransactionGuard::begin_with_options(Arc::clone(&db), options)
+            .await
+            .unwrap();
+        tx.execute("INSERT INTO test (value) VALUES ('immediate')")
+            .await
+            .unwrap();
+
+        // Savepoints cannot change the options of the enclosing transaction
+        assert!(matches!(
+            TransactionGuard::begin_with_options(Arc::clone(&db), options).await,
+            Err(DatabaseError::UnsupportedOperation(_))
+        ));
+        assert_eq!(db.transaction_depth(), 1);
+        tx.commit().await.unwrap();
+
+        let read_only = TransactionOptions::new().with_access_mode(AccessMode::ReadOnly);
+        assert!(matches!(
+            TransactionGuard::begin_with_options(Arc::clone(&db), read_only).await,
+            Err(DatabaseError::UnsupportedOperation(_))
+        ));
+        assert!(!db.in_transaction());
+
+        let results = db.query("SELECT * FROM test").await.unwrap();
+        assert_eq!(results.len(), 1);
+    }
+
     #[tokio::test]
     async fn test_transaction_guard_explicit_rollback() {
         let db = Arc::new(SqliteDatabase::new());
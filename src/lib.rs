@@ -14,7 +14,7 @@
 //! - **Async Support**: Async/await support with Tokio
 //! - **Multiple Backends**: Support for SQLite, PostgreSQL, MySQL, MongoDB, and Redis
 //! - **Transaction Management**: Full ACID transaction support
-//! - **Connection Pooling**: Efficient connection management (planned)
+//! - **Connection Pooling**: Efficient connection management for SQLite and PostgreSQL
 //! - **Cross-Platform**: Works on Windows, Linux, and macOS
 //!
 //! ## Supported Databases
@@ -23,9 +23,9 @@
 //! |----------|--------|----------|
 //! | SQLite | ✅ Implemented | Full support, bundled |
 //! | PostgreSQL | 🔄 Planned | Async, prepared statements |
-//! | MySQL | 🔄 Planned | Async, transactions |
-//! | MongoDB | 🔄 Planned | Document operations |
-//! | Redis | 🔄 Planned | Key-value operations |
+//! | MySQL | ✅ Implemented | Async, transactions (`mysql` feature) |
+//! | MongoDB | ✅ Implemented | Document operations, aggregation (`mongodb_support` feature) |
+//! | Redis | ✅ Implemented | Key-value operations, pub/sub (`redis_support` feature) |
 //!
 //! ## Quick Start
 //!
@@ -124,9 +124,12 @@
 //! | Thread Safety | Manual (mutex) | Automatic (Arc+Mutex) |
 //! | Memory Safety | Manual (smart pointers) | Automatic (ownership) |
 //! | Async Support | C++20 coroutines | Tokio async/await |
-//! | Connection Pooling | ✓ | Planned |
+//! | Connection Pooling | ✓ | ✓ (SQLite, PostgreSQL) |
 //! | Performance | High | High |
 
+// Lets the derive macros refer to `::rust_database_system` from inside this crate
+extern crate self as rust_database_system;
+
 /// Core database system types and traits
 pub mod core;
 
@@ -148,7 +151,8 @@ pub mod backends;
 pub mod prelude {
     pub use crate::core::{
         ConnectionBuilder, Database, DatabaseError, DatabaseResult, DatabaseRow, DatabaseType,
-        DatabaseValue, Result, TransactionGuard,
+        DatabaseValue, FromRow, FromValue, IsolationLevel, Result, ResultSet, RetryPolicy,
+        Transaction, TransactionGuard, TransactionOptions,
     };
 
     #[cfg(feature = "sqlite")]
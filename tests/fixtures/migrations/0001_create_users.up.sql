@@ -0,0 +1,3 @@
+-- Users and their audit trail
+CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL);
+CREATE TABLE audit (user_id INTEGER, note TEXT);
@@ -128,25 +128,55 @@ mod sqlite_tests {
     }
 
     #[tokio::test]
-    async fn test_nested_transaction_prevention() {
+    async fn test_nested_transactions() {
         let db = Arc::new(SqliteDatabase::new());
         db.connect(":memory:").await.expect("Failed to connect");
 
+        db.execute("CREATE TABLE items (name TEXT)")
+            .await
+            .expect("Failed to create table");
+
         // Start first transaction
         db.begin_transaction()
             .await
             .expect("Failed to begin transaction");
+        db.execute("INSERT INTO items VALUES ('outer')")
+            .await
+            .expect("Failed to insert");
 
-        // Try to start nested transaction - should fail
-        let result = db.begin_transaction().await;
-        assert!(result.is_err());
-        assert!(result
-            .unwrap_err()
-            .to_string()
-            .contains("Already in a transaction"));
+        // Nested transactions open savepoints instead of failing
+        db.begin_transaction()
+            .await
+            .expect("Failed to begin nested transaction");
+        assert_eq!(db.transaction_depth(), 2);
+        db.execute("INSERT INTO items VALUES ('discarded')")
+            .await
+            .expect("Failed to insert");
+        db.rollback().await.expect("Failed to rollback savepoint");
 
-        // Cleanup
-        db.rollback().await.expect("Failed to rollback");
+        db.begin_transaction()
+            .await
+            .expect("Failed to begin nested transaction");
+        db.execute("INSERT INTO items VALUES ('released')")
+            .await
+            .expect("Failed to insert");
+        db.commit().await.expect("Failed to release savepoint");
+
+        // The outer transaction is still open after the nested ones finish
+        assert_eq!(db.transaction_depth(), 1);
+        db.commit().await.expect("Failed to commit");
+        assert!(!db.in_transaction());
+        assert!(db.commit().await.is_err());
+
+        let results = db
+            .query("SELECT name FROM items ORDER BY name")
+            .await
+            .expect("Query failed");
+        let names: Vec<String> = results
+            .iter()
+            .map(|row| row.get("name").unwrap().as_string())
+            .collect();
+        assert_eq!(names, vec!["outer", "released"]);
     }
 
     #[tokio::test]
@@ -323,12 +353,157 @@ mod sqlite_tests {
         let retrieved = results[0].get("data").expect("Column not found").as_bytes();
         assert_eq!(retrieved, Some(&binary_data[..]));
     }
+
+    #[cfg(feature = "macros")]
+    #[tokio::test]
+    async fn test_query_as() {
+        use rust_database_system::core::error::DatabaseError;
+        use rust_database_system::core::row::FromRow;
+
+        #[derive(Debug, FromRow)]
+        struct User {
+            id: i64,
+            #[from_row(rename = "full_name")]
+            name: String,
+            active: bool,
+            nickname: Option<String>,
+        }
+
+        let db = Arc::new(SqliteDatabase::new());
+        db.connect(":memory:").await.expect("Failed to connect");
+
+        db.execute(
+            "CREATE TABLE users (id INTEGER PRIMARY KEY, full_name TEXT, active INTEGER, nickname TEXT)",
+        )
+        .await
+        .expect("Failed to create table");
+        db.execute("INSERT INTO users VALUES (1, 'Alice', 1, NULL), (2, 'Bob', 0, 'bobby')")
+            .await
+            .expect("Failed to insert");
+
+        let users: Vec<User> = db
+            .query_as_with_params(
+                "SELECT * FROM users WHERE id >= ? ORDER BY id",
+                &[DatabaseValue::Int(1)],
+            )
+            .await
+            .expect("Query failed");
+        assert_eq!(users.len(), 2);
+        assert_eq!(users[0].id, 1);
+        assert_eq!(users[0].name, "Alice");
+        assert!(users[0].active);
+        assert_eq!(users[0].nickname, None);
+        assert_eq!(users[1].nickname.as_deref(), Some("bobby"));
+
+        // A column of the wrong type is reported with its name
+        let err = db
+            .query_as::<User>("SELECT id, id AS full_name, active, nickname FROM users")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, DatabaseError::TypeMismatch { .. }));
+        assert!(err.to_string().contains("column 'full_name'"));
+    }
+
+    #[tokio::test]
+    async fn test_query_rows_keeps_order_and_duplicates() {
+        let db = Arc::new(SqliteDatabase::new());
+        db.connect(":memory:").await.expect("Failed to connect");
+
+        db.execute("CREATE TABLE users (id INTEGER PRIMARY KEY, name TEXT NOT NULL)")
+            .await
+            .expect("Failed to create users");
+        db.execute("CREATE TABLE orders (id INTEGER PRIMARY KEY, user_id INTEGER, total REAL)")
+            .await
+            .expect("Failed to create orders");
+        db.execute("INSERT INTO users VALUES (1, 'Alice')")
+            .await
+            .expect("Failed to insert user");
+        db.execute("INSERT INTO orders VALUES (10, 1, 9.5), (11, 1, 20.0)")
+            .await
+            .expect("Failed to insert orders");
+
+        let result = db
+            .query_rows_with_params(
+                "SELECT orders.total, users.id, orders.id, users.name FROM users \
+                 JOIN orders ON orders.user_id = users.id WHERE users.id = ? ORDER BY orders.id",
+                &[DatabaseValue::Int(1)],
+            )
+            .await
+            .expect("Query failed");
+
+        let names: Vec<&str> = result.columns().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["total", "id", "id", "name"]);
+        assert_eq!(result.columns()[0].declared_type.as_deref(), Some("REAL"));
+        assert_eq!(result.columns()[3].declared_type.as_deref(), Some("TEXT"));
+
+        assert_eq!(result.len(), 2);
+        let row = &result.rows()[1];
+        assert_eq!(row.try_get::<i64, _>(1).unwrap(), 1);
+        assert_eq!(row.try_get::<i64, _>(2).unwrap(), 11);
+        assert_eq!(row.try_get::<String, _>("name").unwrap(), "Alice");
+
+        // Column metadata is available without any rows
+        let empty = db
+            .query_rows("SELECT id, name FROM users WHERE id < 0")
+            .await
+            .expect("Query failed");
+        assert!(empty.is_empty());
+        assert_eq!(empty.columns().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_query_stream() {
+        use futures::{StreamExt, TryStreamExt};
+
+        let db = Arc::new(SqliteDatabase::new());
+        db.connect(":memory:").await.expect("Failed to connect");
+
+        let query = "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < ?) \
+                     SELECT x, x * 2 AS doubled FROM n";
+
+        let mut stream = db
+            .query_stream_with_params(query, &[DatabaseValue::Int(100_000)])
+            .await
+            .expect("Failed to start stream");
+        let mut count = 0i64;
+        let mut sum = 0i64;
+        while let Some(row) = stream.try_next().await.expect("Failed to read row") {
+            count += 1;
+            sum += row.try_get::<i64, _>("doubled").unwrap();
+        }
+        assert_eq!(count, 100_000);
+        assert_eq!(sum, 100_000 * 100_001);
+
+        // Dropping a stream early releases the connection
+        let first: Vec<_> = db
+            .query_stream_with_params(query, &[DatabaseValue::Int(100_000)])
+            .await
+            .expect("Failed to start stream")
+            .take(3)
+            .collect()
+            .await;
+        assert_eq!(first.len(), 3);
+        let result = tokio::time::timeout(Duration::from_secs(5), db.query("SELECT 1 AS one"))
+            .await
+            .expect("Connection still held by dropped stream")
+            .expect("Query failed");
+        assert_eq!(result.len(), 1);
+
+        // SQL errors are returned before any row is read
+        assert!(db.query_stream("SELECT * FROM missing").await.is_err());
+    }
 }
 
 #[cfg(feature = "postgres")]
 mod postgres_tests {
+    use futures::{StreamExt, TryStreamExt};
     use rust_database_system::backends::postgres::PostgresDatabase;
     use rust_database_system::core::database::Database;
+    use rust_database_system::core::dialect::Dialect;
+    use rust_database_system::core::query_builder::{
+        Comparison, DeleteBuilder, Filter, Identifier, InsertBuilder, SelectBuilder, UpdateBuilder,
+    };
+    use rust_database_system::core::value::DatabaseValue;
     use std::sync::Arc;
 
     // Note: These tests require a running PostgreSQL instance
@@ -354,4 +529,320 @@ mod postgres_tests {
         assert!(result.is_ok());
         assert!(db.is_connected());
     }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features postgres -- --ignored
+    async fn test_postgres_query_builder() {
+        let url = match get_postgres_url() {
+            Some(url) => url,
+            None => {
+                println!("Skipping test: POSTGRES_URL not set");
+                return;
+            }
+        };
+
+        let db = Arc::new(PostgresDatabase::new());
+        db.connect(&url).await.expect("Failed to connect");
+        let dialect = Dialect::from_database_type(db.database_type()).expect("SQL dialect");
+
+        db.execute("DROP TABLE IF EXISTS it_builder_users")
+            .await
+            .expect("Failed to drop table");
+        db.execute(
+            "CREATE TABLE it_builder_users (id SERIAL PRIMARY KEY, name TEXT, \"order\" INT, active BOOLEAN)",
+        )
+        .await
+        .expect("Failed to create table");
+
+        for (name, order) in [("Alice", 1), ("Bob", 2)] {
+            let insert = InsertBuilder::new("it_builder_users")
+                .value("name", name)
+                .value("order", order)
+                .value("active", true);
+            db.execute_with_params(&insert.build_for(dialect), &insert.params())
+                .await
+                .expect("Failed to insert");
+        }
+
+        let update = UpdateBuilder::new("it_builder_users")
+            .set("order", 3)
+            .where_eq("name", "Bob");
+        db.execute_with_params(&update.build_for(dialect), &update.params())
+            .await
+            .expect("Failed to update");
+
+        let select = SelectBuilder::new("it_builder_users")
+            .columns(&["name", "order"])
+            .where_true("active")
+            .where_gt("order", 1)
+            .order_by_desc("order")
+            .offset(0);
+        let results = db
+            .query_with_params(&select.build_for(dialect), &select.params())
+            .await
+            .expect("Query failed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].get("name").expect("Column not found").as_str(),
+            Some("Bob")
+        );
+
+        let grouped = SelectBuilder::new("it_builder_users")
+            .column("active")
+            .column(Identifier::raw("COUNT(*) AS total"))
+            .filter(
+                Filter::eq("name", "Alice")
+                    .or(Filter::ge("order", 3).and(Filter::is_true("active"))),
+            )
+            .group_by(&["active"])
+            .having(Filter::gt(Identifier::raw("COUNT(*)"), 1i64));
+        let results = db
+            .query_with_params(&grouped.build_for(dialect), &grouped.params())
+            .await
+            .expect("Query failed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].get("total").expect("Column not found").as_long(),
+            Some(2)
+        );
+
+        let others = SelectBuilder::new(Identifier::raw("it_builder_users other"))
+            .column("other.id")
+            .where_column("other.order", Comparison::Lt, "it_builder_users.order");
+        let operators = SelectBuilder::new("it_builder_users")
+            .column("name")
+            .where_in("name", ["Alice", "Bob", "Carol"])
+            .where_between("order", 2, 5)
+            .where_ilike("name", "b%")
+            .where_exists(others);
+        let results = db
+            .query_with_params(&operators.build_for(dialect), &operators.params())
+            .await
+            .expect("Query failed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].get("name").expect("Column not found").as_str(),
+            Some("Bob")
+        );
+
+        let result = db
+            .query_rows("SELECT name, id, \"order\", id AS id FROM it_builder_users ORDER BY id")
+            .await
+            .expect("Query failed");
+        let names: Vec<&str> = result.columns().iter().map(|c| c.name.as_str()).collect();
+        assert_eq!(names, vec!["name", "id", "order", "id"]);
+        assert_eq!(result.columns()[2].declared_type.as_deref(), Some("int4"));
+        assert_eq!(
+            result.rows()[0].get(0).and_then(|v| v.as_str()),
+            Some("Alice")
+        );
+
+        let names: Vec<String> = db
+            .query_stream("SELECT name FROM it_builder_users ORDER BY id")
+            .await
+            .expect("Failed to start stream")
+            .map(|row| row.and_then(|row| row.try_get("name")))
+            .try_collect()
+            .await
+            .expect("Failed to read rows");
+        assert_eq!(names, vec!["Alice", "Bob"]);
+
+        let first: Vec<_> = db
+            .query_stream_with_params(
+                "SELECT n FROM generate_series(1, $1::int) AS n",
+                &[DatabaseValue::Int(1_000_000)],
+            )
+            .await
+            .expect("Failed to start stream")
+            .take(5)
+            .collect()
+            .await;
+        assert_eq!(first.len(), 5);
+
+        let delete = DeleteBuilder::new("it_builder_users").where_eq("name", "Alice");
+        let deleted = db
+            .execute_with_params(&delete.build_for(dialect), &delete.params())
+            .await
+            .expect("Failed to delete");
+        assert_eq!(deleted, 1);
+
+        db.execute("DROP TABLE it_builder_users")
+            .await
+            .expect("Failed to drop table");
+    }
+}
+
+#[cfg(feature = "mysql")]
+mod mysql_tests {
+    use rust_database_system::backends::mysql::MysqlDatabase;
+    use rust_database_system::core::database::Database;
+    use rust_database_system::core::value::DatabaseValue;
+    use std::sync::Arc;
+
+    // Note: These tests require a running MySQL or MariaDB instance
+    // Set MYSQL_URL environment variable to run these tests
+
+    fn get_mysql_url() -> Option<String> {
+        std::env::var("MYSQL_URL").ok()
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features mysql -- --ignored
+    async fn test_mysql_connection() {
+        let url = match get_mysql_url() {
+            Some(url) => url,
+            None => {
+                println!("Skipping test: MYSQL_URL not set");
+                return;
+            }
+        };
+
+        let db = Arc::new(MysqlDatabase::new());
+        let result = db.connect(&url).await;
+        assert!(result.is_ok());
+        assert!(db.is_connected());
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features mysql -- --ignored
+    async fn test_mysql_parameterized_query() {
+        let url = match get_mysql_url() {
+            Some(url) => url,
+            None => {
+                println!("Skipping test: MYSQL_URL not set");
+                return;
+            }
+        };
+
+        let db = Arc::new(MysqlDatabase::new());
+        db.connect(&url).await.expect("Failed to connect");
+
+        let _ = db.execute("DROP TABLE IF EXISTS it_users").await;
+        db.execute("CREATE TABLE it_users (id INT AUTO_INCREMENT PRIMARY KEY, name TEXT, age INT)")
+            .await
+            .expect("Failed to create table");
+
+        let params = vec![
+            DatabaseValue::String("Alice".to_string()),
+            DatabaseValue::Int(30),
+        ];
+        db.execute_with_params("INSERT INTO it_users (name, age) VALUES (?, ?)", &params)
+            .await
+            .expect("Failed to insert");
+
+        let results = db
+            .query_with_params(
+                "SELECT age FROM it_users WHERE name = ?",
+                &[DatabaseValue::String("Alice".to_string())],
+            )
+            .await
+            .expect("Query failed");
+
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].get("age").expect("Column not found").as_int(),
+            Some(30)
+        );
+
+        db.execute("DROP TABLE it_users")
+            .await
+            .expect("Failed to drop table");
+    }
+}
+
+#[cfg(feature = "redis_support")]
+mod redis_tests {
+    use rust_database_system::backends::redis::RedisDatabase;
+    use rust_database_system::core::database::Database;
+    use rust_database_system::core::value::DatabaseValue;
+    use std::sync::Arc;
+
+    // Note: These tests require a running Redis instance
+    // Set REDIS_URL environment variable to run these tests
+
+    fn get_redis_url() -> Option<String> {
+        std::env::var("REDIS_URL").ok()
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features redis_support -- --ignored
+    async fn test_redis_raw_commands() {
+        let url = match get_redis_url() {
+            Some(url) => url,
+            None => {
+                println!("Skipping test: REDIS_URL not set");
+                return;
+            }
+        };
+
+        let db = Arc::new(RedisDatabase::new());
+        db.connect(&url).await.expect("Failed to connect");
+        assert!(db.is_connected());
+
+        db.execute_with_params(
+            "SET it:greeting",
+            &[DatabaseValue::String("hello world".to_string())],
+        )
+        .await
+        .expect("Failed to set");
+
+        let results = db.query("GET it:greeting").await.expect("Query failed");
+        assert_eq!(results.len(), 1);
+        assert_eq!(
+            results[0].get("value").expect("Column not found").as_str(),
+            Some("hello world")
+        );
+
+        db.execute("DEL it:greeting")
+            .await
+            .expect("Failed to delete");
+    }
+}
+
+#[cfg(feature = "mongodb_support")]
+mod mongodb_tests {
+    use rust_database_system::backends::mongodb::{MongoDatabase, MongoFilter};
+    use rust_database_system::core::database::Database;
+    use rust_database_system::core::value::{DatabaseRow, DatabaseValue};
+    use std::sync::Arc;
+
+    // Note: These tests require a running MongoDB instance
+    // Set MONGODB_URL environment variable to run these tests
+
+    fn get_mongodb_url() -> Option<String> {
+        std::env::var("MONGODB_URL").ok()
+    }
+
+    #[tokio::test]
+    #[ignore] // Run with: cargo test --features mongodb_support -- --ignored
+    async fn test_mongodb_connection() {
+        let url = match get_mongodb_url() {
+            Some(url) => url,
+            None => {
+                println!("Skipping test: MONGODB_URL not set");
+                return;
+            }
+        };
+
+        let db = Arc::new(MongoDatabase::new());
+        db.connect(&url).await.expect("Failed to connect");
+        assert!(db.is_connected());
+
+        let mut document = DatabaseRow::new();
+        document.insert("name".to_string(), DatabaseValue::from("Alice"));
+        db.insert_one("it_users", &document)
+            .await
+            .expect("Failed to insert");
+
+        let found = db
+            .find_one("it_users", MongoFilter::new().eq("name", "Alice"))
+            .await
+            .expect("Query failed")
+            .expect("Document not found");
+        assert_eq!(found.get("name").and_then(|v| v.as_str()), Some("Alice"));
+
+        db.delete("it_users", MongoFilter::new())
+            .await
+            .expect("Failed to delete");
+    }
 }